@@ -0,0 +1,89 @@
+//! Minimal 2D affine transform helpers used by the patch based
+//! generators.
+//!
+//! A transform `[a, b, c, d, e, f]` maps `(x, y)` to
+//! `(a x + b y + c, d x + e y + f)`.
+
+pub(crate) type DPoint = [f64; 2];
+pub(crate) type Affine = [f64; 6];
+
+pub(crate) const IDENTITY: Affine = [1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+
+pub(crate) fn mul(a: &Affine, b: &Affine) -> Affine {
+    [
+        a[0] * b[0] + a[1] * b[3],
+        a[0] * b[1] + a[1] * b[4],
+        a[0] * b[2] + a[1] * b[5] + a[2],
+        a[3] * b[0] + a[4] * b[3],
+        a[3] * b[1] + a[4] * b[4],
+        a[3] * b[2] + a[4] * b[5] + a[5],
+    ]
+}
+
+pub(crate) fn inverse(t: &Affine) -> Affine {
+    let det = determinant(t);
+    [
+        t[4] / det,
+        -t[1] / det,
+        (t[1] * t[5] - t[2] * t[4]) / det,
+        -t[3] / det,
+        t[0] / det,
+        (t[2] * t[3] - t[0] * t[5]) / det,
+    ]
+}
+
+pub(crate) fn determinant(t: &Affine) -> f64 {
+    t[0] * t[4] - t[1] * t[3]
+}
+
+pub(crate) fn translation(x: f64, y: f64) -> Affine {
+    [1.0, 0.0, x, 0.0, 1.0, y]
+}
+
+pub(crate) fn rotation(angle: f64) -> Affine {
+    let (s, c) = angle.sin_cos();
+    [c, -s, 0.0, s, c, 0.0]
+}
+
+/// Rotation by `angle` about `p`.
+pub(crate) fn rotation_about(p: DPoint, angle: f64) -> Affine {
+    mul(
+        &translation(p[0], p[1]),
+        &mul(&rotation(angle), &translation(-p[0], -p[1])),
+    )
+}
+
+pub(crate) fn apply(t: &Affine, p: DPoint) -> DPoint {
+    [
+        t[0] * p[0] + t[1] * p[1] + t[2],
+        t[3] * p[0] + t[4] * p[1] + t[5],
+    ]
+}
+
+/// The similarity that maps the segment `p`–`q` onto `(0, 0)`–`(1, 0)`,
+/// inverted.
+fn match_segment(p: DPoint, q: DPoint) -> Affine {
+    [
+        q[0] - p[0],
+        p[1] - q[1],
+        p[0],
+        q[1] - p[1],
+        q[0] - p[0],
+        p[1],
+    ]
+}
+
+/// The similarity that maps `p1` to `p2` and `q1` to `q2`.
+pub(crate) fn match_two(p1: DPoint, q1: DPoint, p2: DPoint, q2: DPoint) -> Affine {
+    mul(&match_segment(p2, q2), &inverse(&match_segment(p1, q1)))
+}
+
+/// Like [`match_two()`], but mirrors before mapping, i.e. the result
+/// reverses orientation.
+pub(crate) fn match_two_reflected(p1: DPoint, q1: DPoint, p2: DPoint, q2: DPoint) -> Affine {
+    let mirror = [1.0, 0.0, 0.0, 0.0, -1.0, 0.0];
+    mul(
+        &match_two(apply(&mirror, p1), apply(&mirror, q1), p2, q2),
+        &mirror,
+    )
+}
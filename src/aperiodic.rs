@@ -0,0 +1,754 @@
+//! Aperiodic monotile tilings.
+//!
+//! Patches are grown with the substitution systems from
+//! [Smith, Myers, Kaplan & Goodman-Strauss](https://arxiv.org/abs/2303.10798)
+//! (the *hat*) and
+//! [Smith, Myers, Kaplan & Goodman-Strauss](https://arxiv.org/abs/2305.17743)
+//! (the *spectre*).
+use crate::{
+    affine::{self, Affine, DPoint, IDENTITY},
+    builder::MeshBuilder,
+    FaceIndex, Point, Points,
+};
+use std::{collections::HashMap, f64::consts::PI, rc::Rc};
+
+const HR3: f64 = 0.866_025_403_784_438_6;
+
+fn hex_point(x: f64, y: f64) -> DPoint {
+    [x + 0.5 * y, HR3 * y]
+}
+
+fn hat_outline() -> [DPoint; 13] {
+    [
+        hex_point(0.0, 0.0),
+        hex_point(-1.0, -1.0),
+        hex_point(0.0, -2.0),
+        hex_point(2.0, -2.0),
+        hex_point(2.0, -1.0),
+        hex_point(4.0, -2.0),
+        hex_point(5.0, -1.0),
+        hex_point(4.0, 0.0),
+        hex_point(3.0, 0.0),
+        hex_point(2.0, 2.0),
+        hex_point(0.0, 3.0),
+        hex_point(0.0, 2.0),
+        hex_point(-1.0, 2.0),
+    ]
+}
+
+/// The hat polygon as emitted. The straight angle between outline
+/// corners `2` and `3` gets a vertex so neighbouring kites meet
+/// edge-to-edge.
+fn hat_polygon() -> Vec<DPoint> {
+    let outline = hat_outline();
+    let mut polygon = outline[..3].to_vec();
+    polygon.push(hex_point(1.0, -2.0));
+    polygon.extend_from_slice(&outline[3..]);
+    polygon
+}
+
+fn spectre_polygon() -> [DPoint; 14] {
+    let s = 0.5 * crate::SQRT_3;
+    [
+        [0.0, 0.0],
+        [1.0, 0.0],
+        [1.5, -s],
+        [1.5 + s, 0.5 - s],
+        [1.5 + s, 1.5 - s],
+        [2.5 + s, 1.5 - s],
+        [3.0 + s, 1.5],
+        [3.0, 2.0],
+        [3.0 - s, 1.5],
+        [2.5 - s, 1.5 + s],
+        [1.5 - s, 1.5 + s],
+        [0.5 - s, 1.5 + s],
+        [-s, 1.5],
+        [0.0, 1.0],
+    ]
+}
+
+#[derive(Clone, Copy)]
+enum Prototile {
+    Hat,
+    Spectre,
+}
+
+enum Node {
+    Tile(Prototile),
+    Meta(MetaTile),
+}
+
+struct MetaTile {
+    shape: Vec<DPoint>,
+    children: Vec<(Affine, Rc<Node>)>,
+}
+
+impl MetaTile {
+    fn new(shape: Vec<DPoint>) -> Self {
+        Self {
+            shape,
+            children: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, transform: Affine, node: &Rc<Node>) {
+        self.children.push((transform, node.clone()));
+    }
+
+    /// Corner `corner` of child `child` in this tile's frame.
+    fn eval_child(&self, child: usize, corner: usize) -> DPoint {
+        let (transform, node) = &self.children[child];
+        affine::apply(transform, node.shape()[corner])
+    }
+
+    fn recentre(&mut self) {
+        let n = self.shape.len() as f64;
+        let (cx, cy) = self
+            .shape
+            .iter()
+            .fold((0.0, 0.0), |(x, y), p| (x + p[0] / n, y + p[1] / n));
+
+        for p in &mut self.shape {
+            p[0] -= cx;
+            p[1] -= cy;
+        }
+
+        let m = affine::translation(-cx, -cy);
+        for (transform, _) in &mut self.children {
+            *transform = affine::mul(&m, transform);
+        }
+    }
+}
+
+impl Node {
+    fn shape(&self) -> &[DPoint] {
+        match self {
+            Node::Meta(meta) => &meta.shape,
+            Node::Tile(_) => unreachable!("prototiles are never matched against"),
+        }
+    }
+
+    /// Collects every prototile below this node with its accumulated
+    /// transform.
+    fn flatten(&self, transform: &Affine, result: &mut Vec<(Prototile, Affine)>) {
+        match self {
+            Node::Tile(prototile) => result.push((*prototile, *transform)),
+            Node::Meta(meta) => {
+                for (child_transform, child) in &meta.children {
+                    child.flatten(&affine::mul(transform, child_transform), result);
+                }
+            }
+        }
+    }
+}
+
+fn intersect(p1: DPoint, q1: DPoint, p2: DPoint, q2: DPoint) -> DPoint {
+    let d = (q2[1] - p2[1]) * (q1[0] - p1[0]) - (q2[0] - p2[0]) * (q1[1] - p1[1]);
+    let u = ((q2[0] - p2[0]) * (p1[1] - p2[1]) - (q2[1] - p2[1]) * (p1[0] - p2[0])) / d;
+    [p1[0] + u * (q1[0] - p1[0]), p1[1] + u * (q1[1] - p1[1])]
+}
+
+fn add(p: DPoint, q: DPoint) -> DPoint {
+    [p[0] + q[0], p[1] + q[1]]
+}
+
+fn sub(p: DPoint, q: DPoint) -> DPoint {
+    [p[0] - q[0], p[1] - q[1]]
+}
+
+/// The four hat metatiles `[H, T, P, F]` at level zero.
+fn hat_metatiles() -> [Rc<Node>; 4] {
+    let hat = Rc::new(Node::Tile(Prototile::Hat));
+    let outline = hat_outline();
+
+    let mut h = MetaTile::new(vec![
+        [0.0, 0.0],
+        [4.0, 0.0],
+        [4.5, HR3],
+        [2.5, 5.0 * HR3],
+        [1.5, 5.0 * HR3],
+        [-0.5, HR3],
+    ]);
+    h.add(
+        affine::match_two(outline[5], outline[7], h.shape[5], h.shape[0]),
+        &hat,
+    );
+    h.add(
+        affine::match_two(outline[9], outline[11], h.shape[1], h.shape[2]),
+        &hat,
+    );
+    h.add(
+        affine::match_two(outline[5], outline[7], h.shape[3], h.shape[4]),
+        &hat,
+    );
+    // The one reflected hat.
+    h.add(
+        affine::mul(
+            &affine::translation(2.5, HR3),
+            &affine::mul(
+                &[-0.5, -HR3, 0.0, HR3, -0.5, 0.0],
+                &[0.5, 0.0, 0.0, 0.0, -0.5, 0.0],
+            ),
+        ),
+        &hat,
+    );
+
+    let mut t = MetaTile::new(vec![[0.0, 0.0], [3.0, 0.0], [1.5, 3.0 * HR3]]);
+    t.add([0.5, 0.0, 0.5, 0.0, 0.5, HR3], &hat);
+
+    let pair = |shape: Vec<DPoint>| {
+        let mut meta = MetaTile::new(shape);
+        meta.add([0.5, 0.0, 1.5, 0.0, 0.5, HR3], &hat);
+        meta.add(
+            affine::mul(
+                &affine::translation(0.0, 2.0 * HR3),
+                &affine::mul(
+                    &[0.5, HR3, 0.0, -HR3, 0.5, 0.0],
+                    &[0.5, 0.0, 0.0, 0.0, 0.5, 0.0],
+                ),
+            ),
+            &hat,
+        );
+        meta
+    };
+
+    let p = pair(vec![
+        [0.0, 0.0],
+        [4.0, 0.0],
+        [3.0, 2.0 * HR3],
+        [-1.0, 2.0 * HR3],
+    ]);
+    let f = pair(vec![
+        [0.0, 0.0],
+        [3.0, 0.0],
+        [3.5, HR3],
+        [3.0, 2.0 * HR3],
+        [-1.0, 2.0 * HR3],
+    ]);
+
+    [h, t, p, f].map(|meta| Rc::new(Node::Meta(meta)))
+}
+
+/// How to attach each child of a hat patch. Indices refer to earlier
+/// children and their outline corners.
+enum PatchRule {
+    Root(usize),
+    Edge(usize, usize, usize, usize),
+    Corners(usize, usize, usize, usize, usize, usize),
+}
+
+const H: usize = 0;
+const T: usize = 1;
+const P: usize = 2;
+const F: usize = 3;
+
+const HAT_PATCH_RULES: [PatchRule; 29] = {
+    use PatchRule::*;
+    [
+        Root(H),
+        Edge(0, 0, P, 2),
+        Edge(1, 0, H, 2),
+        Edge(2, 0, P, 2),
+        Edge(3, 0, H, 2),
+        Edge(4, 4, P, 2),
+        Edge(0, 4, F, 3),
+        Edge(2, 4, F, 3),
+        Corners(4, 1, 3, 2, F, 0),
+        Edge(8, 3, H, 0),
+        Edge(9, 2, P, 0),
+        Edge(10, 2, H, 0),
+        Edge(11, 4, P, 2),
+        Edge(12, 0, H, 2),
+        Edge(13, 0, F, 3),
+        Edge(14, 2, F, 1),
+        Edge(15, 3, H, 4),
+        Edge(8, 2, F, 1),
+        Edge(17, 3, H, 0),
+        Edge(18, 2, P, 0),
+        Edge(19, 2, H, 2),
+        Edge(20, 4, F, 3),
+        Edge(20, 0, P, 2),
+        Edge(22, 0, H, 2),
+        Edge(23, 4, F, 3),
+        Edge(23, 0, F, 3),
+        Edge(16, 0, P, 2),
+        Corners(9, 4, 0, 2, T, 2),
+        Edge(4, 0, F, 3),
+    ]
+};
+
+fn hat_patch(metatiles: &[Rc<Node>; 4]) -> MetaTile {
+    let mut patch = MetaTile::new(Vec::new());
+
+    let attach = |node: &Rc<Node>, corner: usize, p: DPoint, q: DPoint| {
+        let shape = node.shape();
+        affine::match_two(shape[corner], shape[(corner + 1) % shape.len()], p, q)
+    };
+
+    for rule in HAT_PATCH_RULES.iter() {
+        match *rule {
+            PatchRule::Root(kind) => patch.add(IDENTITY, &metatiles[kind]),
+            PatchRule::Edge(child, corner, kind, new_corner) => {
+                let n = patch.children[child].1.shape().len();
+                let p = patch.eval_child(child, (corner + 1) % n);
+                let q = patch.eval_child(child, corner);
+                let transform = attach(&metatiles[kind], new_corner, p, q);
+                patch.add(transform, &metatiles[kind]);
+            }
+            PatchRule::Corners(child_p, corner_p, child_q, corner_q, kind, new_corner) => {
+                let p = patch.eval_child(child_q, corner_q);
+                let q = patch.eval_child(child_p, corner_p);
+                let transform = attach(&metatiles[kind], new_corner, p, q);
+                patch.add(transform, &metatiles[kind]);
+            }
+        }
+    }
+
+    patch
+}
+
+/// Derives the next level `[H, T, P, F]` metatiles from a patch.
+fn hat_supertiles(patch: &MetaTile) -> [Rc<Node>; 4] {
+    let bps1 = patch.eval_child(8, 2);
+    let bps2 = patch.eval_child(21, 2);
+    let rbps = affine::apply(&affine::rotation_about(bps1, -2.0 * PI / 3.0), bps2);
+
+    let p72 = patch.eval_child(7, 2);
+    let p252 = patch.eval_child(25, 2);
+
+    let llc = intersect(bps1, rbps, patch.eval_child(6, 2), p72);
+    let mut w = sub(patch.eval_child(6, 2), llc);
+
+    let mut h_outline = vec![llc, bps1];
+    w = affine::apply(&affine::rotation(-PI / 3.0), w);
+    h_outline.push(add(h_outline[1], w));
+    h_outline.push(patch.eval_child(14, 2));
+    w = affine::apply(&affine::rotation(-PI / 3.0), w);
+    h_outline.push(sub(h_outline[3], w));
+    h_outline.push(patch.eval_child(6, 2));
+
+    let p_outline = vec![p72, add(p72, sub(bps1, llc)), bps1, llc];
+
+    let f_outline = vec![
+        bps2,
+        patch.eval_child(24, 2),
+        patch.eval_child(25, 0),
+        p252,
+        add(p252, sub(llc, bps1)),
+    ];
+
+    let a = h_outline[2];
+    let b = add(h_outline[1], sub(h_outline[4], h_outline[5]));
+    let c = affine::apply(&affine::rotation_about(b, -PI / 3.0), a);
+    let t_outline = vec![b, c, a];
+
+    let supertile = |outline: Vec<DPoint>, children: &[usize]| {
+        let mut meta = MetaTile::new(outline);
+        for &child in children {
+            let (transform, node) = &patch.children[child];
+            meta.add(*transform, node);
+        }
+        meta.recentre();
+        Rc::new(Node::Meta(meta))
+    };
+
+    [
+        supertile(h_outline, &[0, 9, 16, 27, 26, 6, 1, 8, 10, 15]),
+        supertile(t_outline, &[11]),
+        supertile(p_outline, &[7, 2, 3, 4, 28]),
+        supertile(f_outline, &[21, 20, 22, 23, 24, 25]),
+    ]
+}
+
+/// The spectre metatile labels.
+const SPECTRE_LABELS: [&str; 9] = [
+    "Gamma", "Delta", "Theta", "Lambda", "Xi", "Pi", "Sigma", "Phi", "Psi",
+];
+
+/// Which metatile replaces each of the eight slots of a spectre
+/// supertile. `None` marks the slot the Mystic (`Gamma`) leaves empty.
+const SPECTRE_RULES: [[Option<&str>; 8]; 9] = [
+    [
+        Some("Pi"),
+        Some("Delta"),
+        None,
+        Some("Theta"),
+        Some("Sigma"),
+        Some("Xi"),
+        Some("Phi"),
+        Some("Gamma"),
+    ],
+    [
+        Some("Xi"),
+        Some("Delta"),
+        Some("Xi"),
+        Some("Phi"),
+        Some("Sigma"),
+        Some("Pi"),
+        Some("Phi"),
+        Some("Gamma"),
+    ],
+    [
+        Some("Psi"),
+        Some("Delta"),
+        Some("Pi"),
+        Some("Phi"),
+        Some("Sigma"),
+        Some("Pi"),
+        Some("Phi"),
+        Some("Gamma"),
+    ],
+    [
+        Some("Psi"),
+        Some("Delta"),
+        Some("Xi"),
+        Some("Phi"),
+        Some("Sigma"),
+        Some("Pi"),
+        Some("Phi"),
+        Some("Gamma"),
+    ],
+    [
+        Some("Psi"),
+        Some("Delta"),
+        Some("Pi"),
+        Some("Phi"),
+        Some("Sigma"),
+        Some("Psi"),
+        Some("Phi"),
+        Some("Gamma"),
+    ],
+    [
+        Some("Psi"),
+        Some("Delta"),
+        Some("Xi"),
+        Some("Phi"),
+        Some("Sigma"),
+        Some("Psi"),
+        Some("Phi"),
+        Some("Gamma"),
+    ],
+    [
+        Some("Xi"),
+        Some("Delta"),
+        Some("Xi"),
+        Some("Phi"),
+        Some("Sigma"),
+        Some("Pi"),
+        Some("Lambda"),
+        Some("Gamma"),
+    ],
+    [
+        Some("Psi"),
+        Some("Delta"),
+        Some("Psi"),
+        Some("Phi"),
+        Some("Sigma"),
+        Some("Pi"),
+        Some("Phi"),
+        Some("Gamma"),
+    ],
+    [
+        Some("Psi"),
+        Some("Delta"),
+        Some("Psi"),
+        Some("Phi"),
+        Some("Sigma"),
+        Some("Psi"),
+        Some("Phi"),
+        Some("Gamma"),
+    ],
+];
+
+/// Grows the spectre metatile `Delta` through `levels` substitutions.
+fn spectre_root(levels: u32) -> Rc<Node> {
+    let outline = spectre_polygon();
+    let spectre = Rc::new(Node::Tile(Prototile::Spectre));
+
+    let mut quad = [outline[3], outline[5], outline[7], outline[11]];
+
+    let mut tiles: HashMap<&str, Rc<Node>> = SPECTRE_LABELS
+        .iter()
+        .map(|&label| (label, spectre.clone()))
+        .collect();
+    // The Mystic is a pair of spectres.
+    tiles.insert(
+        "Gamma",
+        Rc::new(Node::Meta(MetaTile {
+            shape: Vec::new(),
+            children: vec![
+                (IDENTITY, spectre.clone()),
+                (
+                    affine::mul(
+                        &affine::translation(outline[8][0], outline[8][1]),
+                        &affine::rotation(PI / 6.0),
+                    ),
+                    spectre,
+                ),
+            ],
+        })),
+    );
+
+    for _ in 0..levels {
+        let reflection = [-1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+
+        let mut transformations = vec![IDENTITY];
+        let mut total_angle = 0.0;
+        let mut rotation = IDENTITY;
+        let mut rotated_quad = quad;
+
+        for &(angle, from, to) in &[
+            (60.0, 3, 1),
+            (0.0, 2, 0),
+            (60.0, 3, 1),
+            (60.0, 3, 1),
+            (0.0, 2, 0),
+            (60.0, 3, 1),
+            (-120.0, 3, 3),
+        ] {
+            if angle != 0.0 {
+                total_angle += angle;
+                rotation = affine::rotation(f64::to_radians(total_angle));
+                rotated_quad = quad.map(|p| affine::apply(&rotation, p));
+            }
+            let target = affine::apply(transformations.last().unwrap(), quad[from]);
+            let translation = affine::translation(
+                target[0] - rotated_quad[to][0],
+                target[1] - rotated_quad[to][1],
+            );
+            transformations.push(affine::mul(&translation, &rotation));
+        }
+
+        let transformations: Vec<Affine> = transformations
+            .iter()
+            .map(|t| affine::mul(&reflection, t))
+            .collect();
+
+        quad = [
+            affine::apply(&transformations[6], quad[2]),
+            affine::apply(&transformations[5], quad[1]),
+            affine::apply(&transformations[3], quad[2]),
+            affine::apply(&transformations[0], quad[1]),
+        ];
+
+        tiles = SPECTRE_LABELS
+            .iter()
+            .zip(SPECTRE_RULES.iter())
+            .map(|(&label, rule)| {
+                let children = rule
+                    .iter()
+                    .zip(transformations.iter())
+                    .filter_map(|(substitution, transform)| {
+                        substitution.map(|substitution| (*transform, tiles[substitution].clone()))
+                    })
+                    .collect();
+
+                (
+                    label,
+                    Rc::new(Node::Meta(MetaTile {
+                        shape: Vec::new(),
+                        children,
+                    })),
+                )
+            })
+            .collect();
+    }
+
+    tiles["Delta"].clone()
+}
+
+/// Turns a substitution tree into a welded mesh with unit length
+/// (short) edges.
+fn build(root: &Node) -> (Points, FaceIndex, Vec<bool>) {
+    let mut prototiles = Vec::new();
+    root.flatten(&IDENTITY, &mut prototiles);
+
+    let hat = hat_polygon();
+    let spectre = spectre_polygon();
+
+    let mut builder = MeshBuilder::new();
+    let mut reflected = Vec::with_capacity(prototiles.len());
+
+    for (prototile, transform) in &prototiles {
+        let outline: &[DPoint] = match prototile {
+            Prototile::Hat => &hat,
+            Prototile::Spectre => &spectre,
+        };
+
+        // All prototiles share one scale; normalize it to one.
+        let determinant = affine::determinant(transform);
+        let scale = 1.0 / determinant.abs().sqrt();
+
+        let mut corners: Vec<DPoint> = outline
+            .iter()
+            .map(|&p| {
+                let p = affine::apply(transform, p);
+                [p[0] * scale, p[1] * scale]
+            })
+            .collect();
+
+        // Keep all faces counter-clockwise.
+        if determinant < 0.0 {
+            corners.reverse();
+        }
+
+        builder.polygon(&corners);
+        reflected.push(determinant < 0.0);
+    }
+
+    let (points, face_index) = builder.build();
+    (points, face_index, reflected)
+}
+
+/// A patch of an aperiodic monotile tiling.
+///
+/// Every face is a copy of a single prototile. Copies that are mirror
+/// images of the prototile are flagged in [`reflected()`](Self::reflected).
+pub struct AperiodicTiling {
+    face_index: FaceIndex,
+    points: Points,
+    reflected: Vec<bool>,
+    name: String,
+    topology: crate::topology::TopologyCache,
+    classes: crate::class::ClassCache,
+}
+
+impl AperiodicTiling {
+    default_methods! {}
+
+    /// Creates a patch of the *hat* tiling.
+    ///
+    /// The patch is the `H` metatile after `levels` substitution steps.
+    /// Each step multiplies the number of hats by roughly 6.85.
+    ///
+    /// ![](https://upload.wikimedia.org/wikipedia/commons/thumb/c/c6/Smith_Myers_Kaplan_Goodman-Strauss_2023_einstein_tiling.svg/1920px-Smith_Myers_Kaplan_Goodman-Strauss_2023_einstein_tiling.svg.png)
+    pub fn hat(levels: u32) -> Self {
+        let mut metatiles = hat_metatiles();
+        for _ in 0..levels {
+            metatiles = hat_supertiles(&hat_patch(&metatiles));
+        }
+
+        let (points, face_index, reflected) = build(&metatiles[H]);
+
+        Self {
+            name: "HAT".to_string(),
+            points,
+            face_index,
+            classes: crate::class::mirrored(&reflected),
+            reflected,
+            topology: Default::default(),
+        }
+    }
+
+    /// Creates a patch of the *spectre* tiling.
+    ///
+    /// The patch is the `Delta` metatile after `levels` substitution
+    /// steps. Each step multiplies the number of spectres by roughly
+    /// 7.3.
+    ///
+    /// Spectres tile the plane without reflections, so no tile is
+    /// flagged reflected.
+    pub fn spectre(levels: u32) -> Self {
+        let mut root = spectre_root(levels);
+        // Every substitution step mirrors the patch; mirror it back.
+        if levels % 2 == 1 {
+            root = Rc::new(Node::Meta(MetaTile {
+                shape: Vec::new(),
+                children: vec![([-1.0, 0.0, 0.0, 0.0, 1.0, 0.0], root)],
+            }));
+        }
+        let (points, face_index, reflected) = build(&root);
+
+        Self {
+            name: "SPECTRE".to_string(),
+            points,
+            face_index,
+            classes: crate::class::mirrored(&reflected),
+            reflected,
+            topology: Default::default(),
+        }
+    }
+
+    /// Per-face flags telling which tiles are mirror images of the
+    /// prototile.
+    pub fn reflected(&self) -> &[bool] {
+        &self.reflected
+    }
+
+    /// Keeps only the tiles that lie completely inside the axis aligned
+    /// rectangle spanned by `min` and `max`.
+    pub fn clipped(self, min: Point, max: Point) -> Self {
+        let inside = |p: &Point| p.x >= min.x && p.y >= min.y && p.x <= max.x && p.y <= max.y;
+
+        let mut remap = vec![None; self.points.len()];
+        let mut points = Vec::new();
+        let mut face_index = Vec::new();
+        let mut reflected = Vec::new();
+
+        for (face, &is_reflected) in self.face_index.iter().zip(self.reflected.iter()) {
+            if face.iter().all(|&v| inside(&self.points[v as usize])) {
+                face_index.push(
+                    face.iter()
+                        .map(|&v| {
+                            *remap[v as usize].get_or_insert_with(|| {
+                                points.push(self.points[v as usize]);
+                                (points.len() - 1) as _
+                            })
+                        })
+                        .collect(),
+                );
+                reflected.push(is_reflected);
+            }
+        }
+
+        Self {
+            face_index,
+            points,
+            classes: crate::class::mirrored(&reflected),
+            reflected,
+            name: self.name,
+            topology: Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+fn assert_edge_to_edge(face_index: &FaceIndex) {
+    use std::collections::HashSet;
+
+    let mut directed = HashSet::new();
+    for face in face_index {
+        for (i, &a) in face.iter().enumerate() {
+            let b = face[(i + 1) % face.len()];
+            assert!(
+                directed.insert((a, b)),
+                "overlapping tiles at edge {}-{}",
+                a,
+                b
+            );
+        }
+    }
+}
+
+#[test]
+fn hat() {
+    let tiling = AperiodicTiling::hat(2);
+    assert_edge_to_edge(tiling.faces());
+    assert!(tiling.faces().iter().all(|face| face.len() == 14));
+
+    let reflected = tiling.reflected().iter().filter(|&&r| r).count();
+    assert!(0 < reflected && reflected < tiling.faces().len() / 4);
+}
+
+#[test]
+fn spectre() {
+    let tiling = AperiodicTiling::spectre(2);
+    assert_edge_to_edge(tiling.faces());
+    assert!(tiling.faces().iter().all(|face| face.len() == 14));
+    for levels in 0..4 {
+        assert!(AperiodicTiling::spectre(levels)
+            .reflected()
+            .iter()
+            .all(|&r| !r));
+    }
+}
@@ -0,0 +1,356 @@
+//! Cellular automata with a cell per face of a tiling.
+use crate::{
+    builder::MeshBuilder, random::Random, topology::Topology, Adjacency, Error, FaceIndex, Point,
+    Position,
+};
+use std::{collections::BTreeMap, str::FromStr};
+
+/// A two-state, outer totalistic rule in the notation `B3/S23`: a dead
+/// cell is born with a number of live neighbours listed after `B`, a
+/// live cell survives with a number listed after `S`.
+///
+/// Faces of the crate's tilings can have up to twelve neighbours, so
+/// counts above nine are separated by commas, e.g. `B3/S2,3,10`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LifeRule {
+    /// The live neighbour counts at which a dead cell is born.
+    pub birth: Vec<usize>,
+    /// The live neighbour counts at which a live cell survives.
+    pub survival: Vec<usize>,
+}
+
+impl FromStr for LifeRule {
+    type Err = Error;
+
+    fn from_str(notation: &str) -> Result<Self, Error> {
+        let error = |message| Error::Parse { line: 1, message };
+        let counts = |part: &str, prefix: char| -> Result<Vec<usize>, Error> {
+            let part = part
+                .trim()
+                .strip_prefix(prefix)
+                .or_else(|| part.trim().strip_prefix(prefix.to_ascii_lowercase()))
+                .ok_or_else(|| error("expected a rule like B3/S23"))?;
+            if part.contains(',') {
+                part.split(',')
+                    .map(|count| count.trim().parse())
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| error("invalid neighbour count"))
+            } else {
+                part.chars()
+                    .map(|digit| digit.to_digit(10).map(|d| d as usize))
+                    .collect::<Option<_>>()
+                    .ok_or_else(|| error("invalid neighbour count"))
+            }
+        };
+
+        match notation.split('/').collect::<Vec<_>>()[..] {
+            [birth, survival] => Ok(Self {
+                birth: counts(birth, 'B')?,
+                survival: counts(survival, 'S')?,
+            }),
+            _ => Err(error("expected a rule like B3/S23")),
+        }
+    }
+}
+
+impl LifeRule {
+    /// The next state of a cell in `state` with `live` live neighbours.
+    pub fn next(&self, state: u8, live: usize) -> u8 {
+        let counts = if state == 0 {
+            &self.birth
+        } else {
+            &self.survival
+        };
+        counts.contains(&live) as u8
+    }
+}
+
+/// A cell and its neighbours, as seen by a rule.
+pub struct Cell<'a> {
+    /// The index of the face.
+    pub face: usize,
+    /// The current state of the cell.
+    pub state: u8,
+    /// The tile class of the face, see
+    /// [`RegularTiling::classes()`](crate::RegularTiling::classes).
+    pub class: u32,
+    neighbours: &'a [usize],
+    states: &'a [u8],
+    classes: &'a [u32],
+}
+
+impl Cell<'_> {
+    /// The class and state of every neighbour.
+    pub fn neighbours(&self) -> impl Iterator<Item = (u32, u8)> + '_ {
+        self.neighbours
+            .iter()
+            .map(move |&g| (self.classes[g], self.states[g]))
+    }
+
+    /// The number of neighbours in `state`.
+    pub fn count(&self, state: u8) -> usize {
+        self.neighbours().filter(|&(_, s)| s == state).count()
+    }
+
+    /// The number of neighbours of tile class `class` in `state`.
+    pub fn count_in_class(&self, state: u8, class: u32) -> usize {
+        self.neighbours()
+            .filter(|&(c, s)| c == class && s == state)
+            .count()
+    }
+
+    /// The number of neighbours in any state but `0`.
+    pub fn live(&self) -> usize {
+        self.neighbours().filter(|&(_, s)| s != 0).count()
+    }
+}
+
+/// A cellular automaton with a state per face of a tiling.
+///
+/// States are small integers, `0` being dead or empty.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Automaton {
+    neighbours: Vec<Vec<usize>>,
+    classes: Vec<u32>,
+    states: Vec<u8>,
+}
+
+impl Automaton {
+    pub(crate) fn new<P: Position>(
+        points: &[P],
+        face_index: &FaceIndex,
+        classes: &[u32],
+        topology: &Topology,
+        adjacency: Adjacency,
+        wrap: Option<[Point; 2]>,
+    ) -> Self {
+        let neighbours = match wrap {
+            None => (0..face_index.len())
+                .map(|f| topology.face_neighbours(f, adjacency).to_vec())
+                .collect(),
+            Some(periods) => {
+                let wrapped = wrap_vertices(points, face_index, periods);
+                let topology = Topology::new(points.len(), &wrapped);
+                (0..face_index.len())
+                    .map(|f| topology.face_neighbours(f, adjacency).to_vec())
+                    .collect()
+            }
+        };
+
+        Self {
+            neighbours,
+            classes: classes.to_vec(),
+            states: vec![0; face_index.len()],
+        }
+    }
+
+    /// The state of every face.
+    pub fn states(&self) -> &[u8] {
+        &self.states
+    }
+
+    /// Sets the state of `face`.
+    ///
+    /// # Panics
+    /// If there is no face with index `face`.
+    pub fn set_state(&mut self, face: usize, state: u8) {
+        self.states[face] = state;
+    }
+
+    /// The faces whose states a face's next state depends on.
+    pub fn neighbours(&self, face: usize) -> &[usize] {
+        &self.neighbours[face]
+    }
+
+    /// Sets every face to `1` with probability `density` and to `0`
+    /// otherwise. The same `seed` yields the same states.
+    pub fn randomize(&mut self, seed: u64, density: f64) {
+        let mut random = Random::new(seed);
+        for state in &mut self.states {
+            *state = (random.unit() < density) as u8;
+        }
+    }
+
+    /// Advances all faces at once by `rule`.
+    pub fn step_with(&mut self, rule: impl Fn(&Cell<'_>) -> u8) {
+        let next = (0..self.states.len())
+            .map(|face| {
+                rule(&Cell {
+                    face,
+                    state: self.states[face],
+                    class: self.classes[face],
+                    neighbours: &self.neighbours[face],
+                    states: &self.states,
+                    classes: &self.classes,
+                })
+            })
+            .collect();
+        self.states = next;
+    }
+
+    /// Advances all faces by a life-like rule.
+    pub fn step(&mut self, rule: &LifeRule) {
+        self.step_with(|cell| rule.next(cell.state, cell.live()));
+    }
+
+    /// Advances all faces by the rule for their tile class, e.g. with
+    /// different rules for the squares and octagons of the truncated
+    /// square tiling. Faces of other classes keep their state.
+    pub fn step_by_class(&mut self, rules: &BTreeMap<u32, LifeRule>) {
+        self.step_with(|cell| match rules.get(&cell.class) {
+            Some(rule) => rule.next(cell.state, cell.live()),
+            None => cell.state,
+        });
+    }
+}
+
+/// Replaces every vertex by a representative of the vertices it is
+/// identified with when translating by integer combinations of
+/// `periods`, so faces on opposite sides of a patch become neighbours.
+fn wrap_vertices<P: Position>(
+    points: &[P],
+    face_index: &FaceIndex,
+    periods: [Point; 2],
+) -> FaceIndex {
+    let mut lookup = MeshBuilder::new();
+    let positions: Vec<[f64; 2]> = points
+        .iter()
+        .map(|p| {
+            let [x, y, _] = p.xyz();
+            [x as f64, y as f64]
+        })
+        .collect();
+    // Coinciding vertices stay apart; translations find the first.
+    let mut first = Vec::new();
+    for (v, &p) in positions.iter().enumerate() {
+        if lookup.vertex(p) as usize == first.len() {
+            first.push(v as u32);
+        }
+    }
+
+    // Union-find over the vertices.
+    let mut parent: Vec<u32> = (0..points.len() as u32).collect();
+    fn root(parent: &mut [u32], mut v: u32) -> u32 {
+        while parent[v as usize] != v {
+            parent[v as usize] = parent[parent[v as usize] as usize];
+            v = parent[v as usize];
+        }
+        v
+    }
+
+    // The other translations are found from the other end.
+    let [u, v] = periods;
+    for (a, p) in positions.iter().enumerate() {
+        for &(i, j) in &[(1.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, -1.0)] {
+            let q = [
+                p[0] + i * u.x as f64 + j * v.x as f64,
+                p[1] + i * u.y as f64 + j * v.y as f64,
+            ];
+            if let Some(key) = lookup.find(q) {
+                let b = first[key as usize];
+                let (ra, rb) = (root(&mut parent, a as u32), root(&mut parent, b));
+                parent[ra.max(rb) as usize] = ra.min(rb);
+            }
+        }
+    }
+
+    face_index
+        .iter()
+        .map(|face| face.iter().map(|&v| root(&mut parent, v)).collect())
+        .collect()
+}
+
+#[test]
+fn life_on_a_torus() {
+    use crate::{Point, RegularTiling, SemiRegularTiling};
+
+    // A 12 x 12 grid of points has 11 x 11 squares, wrapped at 11.
+    let squares = RegularTiling::square(12, 12);
+    let periods = [Point::new(11.0, 0.0), Point::new(0.0, 11.0)];
+    let mut life = squares.automaton(Adjacency::Vertex, Some(periods));
+    assert!((0..121).all(|f| life.neighbours(f).len() == 8));
+    let face_at = |x: f32, y: f32| {
+        squares
+            .faces()
+            .iter()
+            .position(|face| {
+                let p: Point = squares.points()[face[0] as usize];
+                (p.x - x).abs() < 0.5 && (p.y - y).abs() < 0.5
+            })
+            .unwrap()
+    };
+
+    let rule: LifeRule = "B3/S23".parse().unwrap();
+    for &(x, y) in &[(1.0, 0.0), (2.0, 1.0), (0.0, 2.0), (1.0, 2.0), (2.0, 2.0)] {
+        life.set_state(face_at(x, y), 1);
+    }
+    let glider = life.clone();
+    // A glider moves one cell diagonally every four generations and
+    // returns after crossing the torus.
+    for _ in 0..4 * 11 {
+        life.step(&rule);
+        assert_eq!(life.states().iter().filter(|&&s| s == 1).count(), 5);
+    }
+    assert_eq!(life, glider);
+
+    let mut blinker = squares.automaton(Adjacency::Vertex, None);
+    for &x in &[4.0, 5.0, 6.0] {
+        blinker.set_state(face_at(x, 5.0), 1);
+    }
+    let start = blinker.clone();
+    blinker.step(&rule);
+    assert_ne!(blinker, start);
+    blinker.step(&rule);
+    assert_eq!(blinker, start);
+
+    let mut random = squares.automaton(Adjacency::Edge, None);
+    random.randomize(7, 0.5);
+    let live = random.states().iter().filter(|&&s| s == 1).count();
+    assert!(live > 30 && live < 90);
+
+    let hexagons = RegularTiling::hexagon(12, 12);
+    let automaton = hexagons.automaton(Adjacency::Edge, None);
+    assert!((0..hexagons.faces().len()).any(|f| automaton.neighbours(f).len() == 6));
+
+    // Squares and octagons of the truncated square tiling follow
+    // their own rules.
+    let tiling = SemiRegularTiling::two(20, 20);
+    let mut automaton = tiling.automaton(Adjacency::Edge, None);
+    automaton.randomize(1, 0.5);
+    let before = automaton.states().to_vec();
+    // Squares, class 0, flip; octagons, class 1, have no rule and keep
+    // their state.
+    let rules = BTreeMap::from([(0, "B012345678/S".parse().unwrap())]);
+    automaton.step_by_class(&rules);
+    for (f, face) in tiling.faces().iter().enumerate() {
+        if face.len() == 4 {
+            assert_eq!(automaton.states()[f], 1 - before[f]);
+        } else {
+            assert_eq!(automaton.states()[f], before[f]);
+        }
+    }
+    automaton.step_with(|cell| (cell.count_in_class(1, 1) > 0 && cell.class == 0) as u8);
+    assert!(automaton.states().contains(&1));
+
+    // Mirrored hats are a class of their own.
+    let hats = crate::AperiodicTiling::hat(1);
+    let mut automaton = hats.automaton(Adjacency::Edge, None);
+    automaton.step_with(|cell| cell.class as u8);
+    let mirrored: Vec<u8> = hats.reflected().iter().map(|&r| r as u8).collect();
+    assert_eq!(automaton.states(), mirrored);
+
+    assert_eq!(
+        "B3S23".parse::<LifeRule>(),
+        Err(Error::Parse {
+            line: 1,
+            message: "expected a rule like B3/S23"
+        })
+    );
+    assert_eq!(
+        "B36/S2,3,10".parse::<LifeRule>(),
+        Ok(LifeRule {
+            birth: vec![3, 6],
+            survival: vec![2, 3, 10]
+        })
+    );
+}
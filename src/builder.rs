@@ -0,0 +1,277 @@
+//! Assembles a shared-vertex mesh from loose polygons.
+use crate::{
+    affine::{self, Affine, DPoint, IDENTITY},
+    polygon, Face, FaceIndex, Point, Points, VertexKey,
+};
+use std::collections::{HashMap, VecDeque};
+
+/// Positions closer than this are considered the same vertex.
+pub(crate) const WELD_TOLERANCE: f64 = 1.0e-4;
+
+/// Collects polygons given by their corner positions and welds
+/// coincident corners into shared vertices.
+#[derive(Default)]
+pub(crate) struct MeshBuilder {
+    points: Vec<DPoint>,
+    face_index: FaceIndex,
+    cells: HashMap<(i64, i64), Vec<VertexKey>>,
+}
+
+impl MeshBuilder {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    fn cell(p: DPoint) -> (i64, i64) {
+        (
+            (p[0] / WELD_TOLERANCE).floor() as _,
+            (p[1] / WELD_TOLERANCE).floor() as _,
+        )
+    }
+
+    /// Returns the key of the vertex at `p`, if there is one.
+    pub(crate) fn find(&self, p: DPoint) -> Option<VertexKey> {
+        let (cx, cy) = Self::cell(p);
+
+        for x in cx - 1..=cx + 1 {
+            for y in cy - 1..=cy + 1 {
+                if let Some(keys) = self.cells.get(&(x, y)) {
+                    for &key in keys {
+                        let q = self.points[key as usize];
+                        if (q[0] - p[0]).abs() < WELD_TOLERANCE
+                            && (q[1] - p[1]).abs() < WELD_TOLERANCE
+                        {
+                            return Some(key);
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the key of the vertex at `p`, creating it if needed.
+    pub(crate) fn vertex(&mut self, p: DPoint) -> VertexKey {
+        if let Some(key) = self.find(p) {
+            return key;
+        }
+
+        let (cx, cy) = Self::cell(p);
+        let key = self.points.len() as VertexKey;
+        self.points.push(p);
+        self.cells.entry((cx, cy)).or_default().push(key);
+        key
+    }
+
+    /// Adds a polygon and returns its index.
+    pub(crate) fn polygon(&mut self, corners: &[DPoint]) -> usize {
+        let face: Face = corners.iter().map(|&p| self.vertex(p)).collect();
+        self.face_index.push(face);
+        self.face_index.len() - 1
+    }
+
+    /// Inserts every vertex that lies on the interior of a face edge
+    /// into that face, so faces meeting at T-junctions share edges.
+    pub(crate) fn split_t_junctions(&mut self) {
+        let edge_count: usize = self.face_index.iter().map(|face| face.len()).sum();
+        if edge_count == 0 {
+            return;
+        }
+
+        let length = |a: DPoint, b: DPoint| ((b[0] - a[0]).powi(2) + (b[1] - a[1]).powi(2)).sqrt();
+        let cell_size = self
+            .face_index
+            .iter()
+            .flat_map(|face| (0..face.len()).map(move |i| (face[i], face[(i + 1) % face.len()])))
+            .map(|(a, b)| length(self.points[a as usize], self.points[b as usize]))
+            .sum::<f64>()
+            / edge_count as f64;
+
+        let cell = |p: DPoint| {
+            (
+                (p[0] / cell_size).floor() as i64,
+                (p[1] / cell_size).floor() as i64,
+            )
+        };
+
+        let mut grid: HashMap<(i64, i64), Vec<VertexKey>> = HashMap::new();
+        for (key, &p) in self.points.iter().enumerate() {
+            grid.entry(cell(p)).or_default().push(key as _);
+        }
+
+        for face in &mut self.face_index {
+            let mut split = Face::with_capacity(face.len());
+
+            for i in 0..face.len() {
+                let (a, b) = (face[i], face[(i + 1) % face.len()]);
+                let (pa, pb) = (self.points[a as usize], self.points[b as usize]);
+                let d = [pb[0] - pa[0], pb[1] - pa[1]];
+                let length_squared = d[0] * d[0] + d[1] * d[1];
+
+                let (min, max) = (
+                    cell([pa[0].min(pb[0]), pa[1].min(pb[1])]),
+                    cell([pa[0].max(pb[0]), pa[1].max(pb[1])]),
+                );
+
+                let mut on_edge = Vec::new();
+                for x in min.0 - 1..=max.0 + 1 {
+                    for y in min.1 - 1..=max.1 + 1 {
+                        for &v in grid.get(&(x, y)).into_iter().flatten() {
+                            if v == a || v == b {
+                                continue;
+                            }
+                            let p = self.points[v as usize];
+                            let t =
+                                ((p[0] - pa[0]) * d[0] + (p[1] - pa[1]) * d[1]) / length_squared;
+                            let distance = ((p[0] - pa[0]) * d[1] - (p[1] - pa[1]) * d[0]).abs()
+                                / length_squared.sqrt();
+                            if 0.0 < t && t < 1.0 && distance < WELD_TOLERANCE {
+                                on_edge.push((t, v));
+                            }
+                        }
+                    }
+                }
+                on_edge.sort_by(|p, q| p.0.partial_cmp(&q.0).unwrap());
+
+                split.push(a);
+                split.extend(on_edge.into_iter().map(|(_, v)| v));
+            }
+
+            *face = split;
+        }
+    }
+
+    pub(crate) fn vertex_count(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns the welded positions at full precision.
+    pub(crate) fn build_f64(self) -> (Vec<DPoint>, FaceIndex) {
+        (self.points, self.face_index)
+    }
+
+    pub(crate) fn build(self) -> (Points, FaceIndex) {
+        (
+            self.points
+                .into_iter()
+                .map(|p| Point::new(p[0] as _, p[1] as _))
+                .collect(),
+            self.face_index,
+        )
+    }
+}
+
+/// Grows a patch of copies of `tile` from the group generated by
+/// `generators`, keeping every copy whose image of `center` lies in the
+/// rectangle from the origin to `(width, height)`.
+///
+/// The generators are applied in the frame of each copy, so the
+/// transforms taking a tile to its edge neighbours work as is. Returns
+/// the builder and per-copy flags telling which copies are mirrored.
+pub(crate) fn grow_patch(
+    tile: &[DPoint],
+    center: DPoint,
+    generators: &[Affine],
+    width: f64,
+    height: f64,
+) -> (MeshBuilder, Vec<bool>) {
+    let inside = |p: DPoint| p[0] >= 0.0 && p[1] >= 0.0 && p[0] <= width && p[1] <= height;
+
+    let mut centers = MeshBuilder::new();
+    centers.vertex(center);
+
+    let mut builder = MeshBuilder::new();
+    let mut reflected = Vec::new();
+    let mut queue = VecDeque::new();
+    queue.push_back(IDENTITY);
+
+    while let Some(transform) = queue.pop_front() {
+        let mut copy: Vec<DPoint> = tile.iter().map(|&p| affine::apply(&transform, p)).collect();
+        let is_reflected = affine::determinant(&transform) < 0.0;
+        if is_reflected {
+            copy.reverse();
+        }
+        builder.polygon(&copy);
+        reflected.push(is_reflected);
+
+        for generator in generators {
+            let next = affine::mul(&transform, generator);
+            let next_center = affine::apply(&next, center);
+            if inside(next_center) {
+                let count = centers.vertex_count();
+                if centers.vertex(next_center) as usize == count {
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+
+    (builder, reflected)
+}
+
+/// Tiles the plane with translated copies of `cluster`, whose copies by
+/// integer combinations of `periods` do not overlap, keeping every tile
+/// whose centroid lies in the rectangle from the origin to
+/// `(width, height)`.
+///
+/// Tiles flagged in `reflected` are mirror images, whose corners run
+/// clockwise; they are reversed. Returns the builder and, per face, the
+/// index of the cluster tile it is a copy of.
+pub(crate) fn grow_lattice(
+    cluster: &[Vec<DPoint>],
+    reflected: &[bool],
+    periods: [DPoint; 2],
+    width: f64,
+    height: f64,
+) -> (MeshBuilder, Vec<usize>) {
+    let [u, v] = periods;
+    let det = u[0] * v[1] - u[1] * v[0];
+    // Coordinates of `p` in the basis of the periods.
+    let lattice = |p: DPoint| {
+        [
+            (p[0] * v[1] - p[1] * v[0]) / det,
+            (u[0] * p[1] - u[1] * p[0]) / det,
+        ]
+    };
+    let corners = [[0.0, 0.0], [width, 0.0], [0.0, height], [width, height]];
+
+    let mut builder = MeshBuilder::new();
+    let mut copies = Vec::new();
+    for (index, tile) in cluster.iter().enumerate() {
+        let center = polygon::centroid(tile);
+        let coordinates: Vec<DPoint> = corners
+            .iter()
+            .map(|c| lattice([c[0] - center[0], c[1] - center[1]]))
+            .collect();
+        let range = |k: usize| {
+            let values = coordinates.iter().map(|c| c[k]);
+            let min = values.clone().fold(f64::INFINITY, f64::min).floor() as i64;
+            let max = values.fold(f64::NEG_INFINITY, f64::max).ceil() as i64;
+            min..=max
+        };
+
+        for i in range(0) {
+            for j in range(1) {
+                let offset = [
+                    i as f64 * u[0] + j as f64 * v[0],
+                    i as f64 * u[1] + j as f64 * v[1],
+                ];
+                let (x, y) = (center[0] + offset[0], center[1] + offset[1]);
+                if x < 0.0 || y < 0.0 || x > width || y > height {
+                    continue;
+                }
+                let mut copy: Vec<DPoint> = tile
+                    .iter()
+                    .map(|p| [p[0] + offset[0], p[1] + offset[1]])
+                    .collect();
+                if reflected[index] {
+                    copy.reverse();
+                }
+                builder.polygon(&copy);
+                copies.push(index);
+            }
+        }
+    }
+
+    (builder, copies)
+}
@@ -0,0 +1,75 @@
+//! Per-face tile classes that writers color and group faces by.
+use crate::FaceIndex;
+use std::sync::OnceLock;
+
+/// A tiling's face classes, given when it is built or derived from its
+/// faces when first needed.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ClassCache(OnceLock<Vec<u32>>);
+
+impl ClassCache {
+    pub(crate) fn get(&self, face_index: &FaceIndex) -> &[u32] {
+        self.0.get_or_init(|| polygon_classes(face_index))
+    }
+}
+
+impl From<Vec<u32>> for ClassCache {
+    fn from(classes: Vec<u32>) -> Self {
+        Self(OnceLock::from(classes))
+    }
+}
+
+/// Classes faces by the kind of polygon they are: `0` for the kind with
+/// the fewest vertices, `1` for the next and so on. For tilings by
+/// regular polygons this is the kind of tile, e.g. triangles and
+/// squares.
+fn polygon_classes(face_index: &FaceIndex) -> Vec<u32> {
+    let mut kinds: Vec<usize> = face_index.iter().map(Vec::len).collect();
+    kinds.sort_unstable();
+    kinds.dedup();
+
+    face_index
+        .iter()
+        .map(|face| kinds.binary_search(&face.len()).unwrap() as u32)
+        .collect()
+}
+
+/// Classes tiles as `0`, or `1` if they are mirror images.
+pub(crate) fn mirrored(reflected: &[bool]) -> ClassCache {
+    reflected
+        .iter()
+        .map(|&r| r as u32)
+        .collect::<Vec<_>>()
+        .into()
+}
+
+/// Ranks `keys` by first appearance, e.g. per-face tile shapes.
+pub(crate) fn rank<T: PartialEq>(keys: &[T]) -> Vec<u32> {
+    let mut seen: Vec<&T> = Vec::new();
+    keys.iter()
+        .map(|key| match seen.iter().position(|&k| k == key) {
+            Some(class) => class as u32,
+            None => {
+                seen.push(key);
+                (seen.len() - 1) as u32
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn classes_of_semi_regular_tilings() {
+    // Triangles and squares.
+    let tiling = crate::SemiRegularTiling::three(6, 6);
+    for (face, &class) in tiling.faces().iter().zip(tiling.classes()) {
+        assert_eq!(class, if face.len() == 3 { 0 } else { 1 });
+    }
+
+    assert_eq!(rank(&["a", "b", "a", "c"]), vec![0, 1, 0, 2]);
+
+    // Meshes with the same faces but other classes differ.
+    let inset = tiling.inset(0.1, 0.0, 0).unwrap();
+    let plain = crate::Mesh::new(inset.name(), inset.points().clone(), inset.faces().clone());
+    assert_eq!(plain, inset.clone().with_classes(tiling.classes()));
+    assert_ne!(plain, inset.with_classes(&vec![0; tiling.faces().len()]));
+}
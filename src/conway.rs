@@ -0,0 +1,367 @@
+//! [Conway operators](https://en.wikipedia.org/wiki/Conway_polyhedron_notation)
+//! deriving new tilings from the vertices, edges and faces of a tiling.
+//!
+//! Faces that would surround a vertex on the boundary of a patch are
+//! left out, as are faces across boundary edges.
+use crate::{polygon, topology, Face, FaceIndex, Mesh, Position};
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Operator {
+    Dual,
+    Ambo,
+    Truncate,
+    Kis,
+    Snub,
+    Expand,
+    Gyro,
+    Join,
+    Ortho,
+}
+
+/// What a vertex of the result is derived from.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Element {
+    Vertex(u32),
+    /// The centroid of a face.
+    Face(usize),
+    /// The midpoint of an edge, given by its end points in ascending
+    /// order.
+    Edge(u32, u32),
+    /// A point on the edge from the first vertex to the second, near
+    /// the first.
+    Near(u32, u32),
+    /// A corner of a face moved into it.
+    Corner(usize, u32),
+}
+
+/// Collects the faces of the result, creating vertices on first use.
+struct Builder<P> {
+    points: Vec<P>,
+    keys: HashMap<Element, u32>,
+    face_index: FaceIndex,
+}
+
+impl<P: Position> Builder<P> {
+    /// Adds a face through the given elements, placing new vertices at
+    /// the given positions.
+    fn face(&mut self, corners: impl IntoIterator<Item = (Element, [f32; 3])>) {
+        let face: Face = corners
+            .into_iter()
+            .map(|(element, position)| {
+                let points = &mut self.points;
+                *self.keys.entry(element).or_insert_with(|| {
+                    points.push(P::from_xyz(position));
+                    points.len() as u32 - 1
+                })
+            })
+            .collect();
+        self.face_index.push(face);
+    }
+}
+
+fn lerp(a: [f32; 3], b: [f32; 3], t: f32) -> [f32; 3] {
+    [0, 1, 2].map(|k| a[k] + t * (b[k] - a[k]))
+}
+
+/// The angle at corner `v` of `face`, assuming it is convex.
+fn corner_angle<P: Position>(points: &[P], face: &Face, v: u32) -> f32 {
+    let n = face.len();
+    let i = face.iter().position(|&w| w == v).unwrap();
+    let [p, q, r] = [face[(i + n - 1) % n], v, face[(i + 1) % n]].map(|w| points[w as usize].xyz());
+    let (u, w) = (
+        [0, 1, 2].map(|k| p[k] - q[k]),
+        [0, 1, 2].map(|k| r[k] - q[k]),
+    );
+    let dot = u[0] * w[0] + u[1] * w[1] + u[2] * w[2];
+    let length = |v: [f32; 3]| (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+    (dot / (length(u) * length(w))).clamp(-1.0, 1.0).acos()
+}
+
+pub(crate) fn apply<P: Position>(
+    name: &str,
+    points: &[P],
+    face_index: &FaceIndex,
+    operator: Operator,
+) -> Mesh<P> {
+    let (points, face_index) = if operator == Operator::Snub {
+        // Snub is the dual of gyro.
+        let (points, face_index) = operate(points, face_index, Operator::Gyro);
+        operate(&points, &face_index, Operator::Dual)
+    } else {
+        operate(points, face_index, operator)
+    };
+    let suffix = match operator {
+        Operator::Dual => "dual",
+        Operator::Ambo => "ambo",
+        Operator::Truncate => "truncated",
+        Operator::Kis => "kis",
+        Operator::Snub => "snub",
+        Operator::Expand => "expanded",
+        Operator::Gyro => "gyro",
+        Operator::Join => "join",
+        Operator::Ortho => "ortho",
+    };
+    Mesh::new(&format!("{}-{}", name, suffix), points, face_index)
+}
+
+fn operate<P: Position>(
+    points: &[P],
+    face_index: &FaceIndex,
+    operator: Operator,
+) -> (Vec<P>, FaceIndex) {
+    let mut builder = Builder {
+        points: Vec::new(),
+        keys: HashMap::new(),
+        face_index: FaceIndex::new(),
+    };
+
+    let position = |v: u32| points[v as usize].xyz();
+    let centroid = |f: usize| {
+        let face = &face_index[f];
+        let sum = face.iter().fold([0.0; 3], |c, &v| {
+            let p = position(v);
+            [c[0] + p[0], c[1] + p[1], c[2] + p[2]]
+        });
+        sum.map(|c| c / face.len() as f32)
+    };
+    let next = |f: usize, v: u32| {
+        let face = &face_index[f];
+        face[(face.iter().position(|&w| w == v).unwrap() + 1) % face.len()]
+    };
+    let midpoint = |a: u32, b: u32| lerp(position(a), position(b), 0.5);
+    let edge = |a: u32, b: u32| Element::Edge(a.min(b), a.max(b));
+    let mut owners = HashMap::new();
+    for (f, face) in face_index.iter().enumerate() {
+        for i in 0..face.len() {
+            owners.insert((face[i], face[(i + 1) % face.len()]), f);
+        }
+    }
+
+    // Interior edges once, as (a, b, face traversing a to b, face
+    // traversing b to a).
+    let mut interior_edges: Vec<(u32, u32, usize, usize)> = owners
+        .iter()
+        .filter(|&(&(a, b), _)| a < b)
+        .filter_map(|(&(a, b), &f)| owners.get(&(b, a)).map(|&g| (a, b, f, g)))
+        .collect();
+    interior_edges.sort_unstable();
+
+    let vertex = |v: u32| (Element::Vertex(v), position(v));
+    let center = |f: usize| (Element::Face(f), centroid(f));
+    let middle = |a: u32, b: u32| (edge(a, b), midpoint(a, b));
+
+    match operator {
+        Operator::Dual => {
+            for (_, fan) in topology::fans(face_index) {
+                builder.face(fan.into_iter().map(center));
+            }
+        }
+        Operator::Ambo => {
+            for face in face_index {
+                builder.face((0..face.len()).map(|i| middle(face[i], face[(i + 1) % face.len()])));
+            }
+            for (v, fan) in topology::fans(face_index) {
+                builder.face(fan.into_iter().map(|f| middle(v, next(f, v))));
+            }
+        }
+        Operator::Truncate => {
+            // Cutting corners at this fraction of an edge turns regular
+            // polygons into regular polygons with twice the corners.
+            let near = |a: u32, b: u32| {
+                let sines: Vec<f32> = [owners.get(&(a, b)), owners.get(&(b, a))]
+                    .iter()
+                    .flatten()
+                    .map(|&&f| (corner_angle(points, &face_index[f], a) / 2.0).sin())
+                    .collect();
+                let sine = sines.iter().sum::<f32>() / sines.len() as f32;
+                (
+                    Element::Near(a, b),
+                    lerp(position(a), position(b), 1.0 / (2.0 + 2.0 * sine)),
+                )
+            };
+            for face in face_index {
+                let n = face.len();
+                builder.face((0..n).flat_map(|i| {
+                    let (a, b) = (face[i], face[(i + 1) % n]);
+                    [near(a, b), near(b, a)]
+                }));
+            }
+            for (v, fan) in topology::fans(face_index) {
+                builder.face(fan.into_iter().map(|f| near(v, next(f, v))));
+            }
+        }
+        Operator::Kis => {
+            for (f, face) in face_index.iter().enumerate() {
+                for i in 0..face.len() {
+                    builder.face([
+                        vertex(face[i]),
+                        vertex(face[(i + 1) % face.len()]),
+                        center(f),
+                    ]);
+                }
+            }
+        }
+        Operator::Gyro | Operator::Snub => {
+            let near = |a: u32, b: u32| {
+                (
+                    Element::Near(a, b),
+                    lerp(position(a), position(b), 1.0 / 3.0),
+                )
+            };
+            for (f, face) in face_index.iter().enumerate() {
+                let n = face.len();
+                for i in 0..n {
+                    let (a, b, c) = (face[(i + n - 1) % n], face[i], face[(i + 1) % n]);
+                    builder.face([center(f), near(a, b), near(b, a), vertex(b), near(b, c)]);
+                }
+            }
+        }
+        Operator::Expand => {
+            // Moving corners in by this distance turns the faces
+            // between regular polygons into squares.
+            let corners: Vec<Vec<[f32; 3]>> = face_index
+                .iter()
+                .map(|face| {
+                    let n = face.len();
+                    let corners: Vec<[f32; 3]> = face.iter().map(|&v| position(v)).collect();
+                    let perimeter: f32 = (0..n)
+                        .map(|i| {
+                            let (p, q) = (corners[i], corners[(i + 1) % n]);
+                            let d = [0, 1, 2].map(|k| q[k] - p[k]);
+                            (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt()
+                        })
+                        .sum();
+                    let half_angle = std::f32::consts::PI * (n as f32 - 2.0) / (2.0 * n as f32);
+                    let distance = perimeter / n as f32 / (2.0 + 2.0 / half_angle.tan());
+                    polygon::inset(&corners, distance)
+                })
+                .collect();
+            let corner = |f: usize, v: u32| {
+                let i = face_index[f].iter().position(|&w| w == v).unwrap();
+                (Element::Corner(f, v), corners[f][i])
+            };
+
+            for (f, face) in face_index.iter().enumerate() {
+                builder.face(face.iter().map(|&v| corner(f, v)));
+            }
+            for &(a, b, f, g) in &interior_edges {
+                builder.face([corner(g, a), corner(g, b), corner(f, b), corner(f, a)]);
+            }
+            for (v, fan) in topology::fans(face_index) {
+                builder.face(fan.into_iter().map(|f| corner(f, v)));
+            }
+        }
+        Operator::Join => {
+            for &(a, b, f, g) in &interior_edges {
+                builder.face([vertex(a), center(g), vertex(b), center(f)]);
+            }
+        }
+        Operator::Ortho => {
+            for (f, face) in face_index.iter().enumerate() {
+                let n = face.len();
+                for i in 0..n {
+                    let (a, b, c) = (face[(i + n - 1) % n], face[i], face[(i + 1) % n]);
+                    builder.face([center(f), middle(a, b), vertex(b), middle(b, c)]);
+                }
+            }
+        }
+    }
+
+    (builder.points, builder.face_index)
+}
+
+#[test]
+fn conway_operators() {
+    use crate::{Point, RegularTiling, SemiRegularTiling};
+
+    // The sorted face sizes around every interior vertex.
+    let configurations = |faces: &FaceIndex| -> Vec<Vec<usize>> {
+        let mut configurations: Vec<Vec<usize>> = topology::fans(faces)
+            .into_iter()
+            .map(|(_, fan)| {
+                let mut sizes: Vec<usize> = fan.iter().map(|&f| faces[f].len()).collect();
+                sizes.sort_unstable();
+                sizes
+            })
+            .collect();
+        configurations.sort();
+        configurations.dedup();
+        configurations
+    };
+    // Regular faces with sides of the same length throughout.
+    let regular = |mesh: &Mesh| {
+        let sides: Vec<f32> = mesh
+            .faces()
+            .iter()
+            .flat_map(|face| {
+                (0..face.len()).map(move |i| {
+                    let p: Point = mesh.points()[face[i] as usize];
+                    (p - mesh.points()[face[(i + 1) % face.len()] as usize]).mag()
+                })
+            })
+            .collect();
+        let angles_regular = mesh.faces().iter().all(|face| {
+            let n = face.len();
+            (0..n).all(|i| {
+                let angle = corner_angle(mesh.points(), face, face[i]);
+                (angle - std::f32::consts::PI * (n as f32 - 2.0) / n as f32).abs() < 1.0e-3
+            })
+        });
+        angles_regular && sides.iter().all(|&side| (side - sides[0]).abs() < 1.0e-4)
+    };
+
+    let squares = RegularTiling::square(8, 8);
+    let truncated = squares.truncate();
+    assert_eq!(configurations(truncated.faces()), vec![vec![4, 8, 8]]);
+    assert_eq!(
+        configurations(truncated.faces()),
+        configurations(SemiRegularTiling::two(20, 20).faces())
+    );
+    assert!(regular(&truncated));
+
+    let hexagons = RegularTiling::hexagon(12, 12);
+    let ambo = hexagons.ambo();
+    assert_eq!(configurations(ambo.faces()), vec![vec![3, 3, 6, 6]]);
+    assert!(regular(&ambo));
+    let expanded = hexagons.expand();
+    assert_eq!(configurations(expanded.faces()), vec![vec![3, 4, 4, 6]]);
+    assert!(regular(&expanded));
+    assert_eq!(
+        configurations(hexagons.truncate().faces()),
+        vec![vec![3, 12, 12]]
+    );
+
+    // Triangles and hexagons are dual to each other.
+    let dual = RegularTiling::triangle(10, 10).dual();
+    assert!(dual.faces().iter().all(|face| face.len() == 6));
+    assert!(regular(&dual));
+    assert_eq!(
+        configurations(squares.dual().faces()),
+        vec![vec![4, 4, 4, 4]]
+    );
+
+    let kis = squares.kis();
+    assert_eq!(kis.faces().len(), 4 * squares.faces().len());
+    assert_eq!(
+        configurations(kis.faces()),
+        vec![vec![3, 3, 3, 3], vec![3; 8]]
+    );
+
+    let interior_edges = squares.edges().iter().filter(|e| !e.is_boundary()).count();
+    assert_eq!(squares.join().faces().len(), interior_edges);
+    let ortho = squares.ortho();
+    assert_eq!(ortho.faces().len(), 4 * squares.faces().len());
+    assert!(regular(&ortho));
+
+    let gyro = squares.gyro();
+    assert!(gyro.faces().iter().all(|face| face.len() == 5));
+    assert_eq!(
+        configurations(gyro.faces()),
+        vec![vec![5, 5, 5], vec![5, 5, 5, 5]]
+    );
+    assert_eq!(
+        configurations(squares.snub().faces()),
+        vec![vec![3, 3, 3, 4, 4]]
+    );
+}
@@ -0,0 +1,167 @@
+//! Motifs drawn on every face of a tiling, e.g. Truchet tiles.
+use crate::{
+    affine::DPoint, builder::MeshBuilder, polygon, random::Random, FaceIndex, Mesh, Point,
+    Polyline, Position,
+};
+use std::f64::consts::PI;
+
+/// A motif placed on every face.
+///
+/// Motifs pair the midpoints of consecutive edges around every other
+/// corner, starting at a face's rotation. On faces with an odd number
+/// of edges, the midpoint left over is connected to the centroid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Motif {
+    /// Circular arcs around the corner between paired midpoints, made
+    /// of `segments` pieces, i.e. Smith's Truchet tiles on squares.
+    Arcs { segments: u32 },
+    /// Straight lines between paired midpoints.
+    Lines,
+    /// The face is cut in half by a line from the corner at its
+    /// rotation to the opposite corner, or the midpoint of the opposite
+    /// edge on faces with an odd number of edges, and the first half is
+    /// filled, i.e. Truchet's original tiles on squares.
+    Triangles,
+}
+
+/// The motifs of all faces.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Decoration {
+    /// The lines of the motifs.
+    pub curves: Vec<Polyline>,
+    /// The filled parts of the motifs, e.g. the sectors between the
+    /// arcs and their corners.
+    pub shapes: Mesh,
+}
+
+/// Decorates every face with `motif`, rotated by a random number of
+/// edges if a `seed` is given.
+pub(crate) fn decorate<P: Position>(
+    name: &str,
+    points: &[P],
+    face_index: &FaceIndex,
+    motif: Motif,
+    seed: Option<u64>,
+) -> Decoration {
+    let mut random = seed.map(Random::new);
+    let mut curves = Vec::new();
+    let mut shapes = MeshBuilder::new();
+    let to_point = |p: DPoint| Point::new(p[0] as f32, p[1] as f32);
+
+    for face in face_index {
+        let n = face.len();
+        let corners: Vec<DPoint> = face
+            .iter()
+            .map(|&v| {
+                let [x, y, _] = points[v as usize].xyz();
+                [x as f64, y as f64]
+            })
+            .collect();
+        let rotation = random.as_mut().map_or(0, |random| random.below(n));
+        let corner = |i: usize| corners[i % n];
+        let middle = |i: usize| {
+            let (a, b) = (corner(i), corner(i + 1));
+            [(a[0] + b[0]) / 2.0, (a[1] + b[1]) / 2.0]
+        };
+
+        if motif == Motif::Triangles {
+            let mut half: Vec<DPoint> = (rotation..=rotation + n / 2).map(corner).collect();
+            if n % 2 == 1 {
+                half.push(middle(rotation + n / 2));
+            }
+            curves.push(vec![to_point(half[0]), to_point(half[half.len() - 1])]);
+            shapes.polygon(&half);
+            continue;
+        }
+
+        for k in 0..n / 2 {
+            let i = rotation + 2 * k;
+            let (from, center, to) = (middle(i), corner(i + 1), middle(i + 1));
+
+            let mut curve = vec![from];
+            if let Motif::Arcs { segments } = motif {
+                // The arc sweeps clockwise through the interior of a
+                // counter-clockwise face.
+                let angle = |p: DPoint| (p[1] - center[1]).atan2(p[0] - center[0]);
+                let radius =
+                    |p: DPoint| ((p[0] - center[0]).powi(2) + (p[1] - center[1]).powi(2)).sqrt();
+                let mut sweep = angle(to) - angle(from);
+                if polygon::signed_area(&corners) > 0.0 {
+                    if sweep > 0.0 {
+                        sweep -= 2.0 * PI;
+                    }
+                } else if sweep < 0.0 {
+                    sweep += 2.0 * PI;
+                }
+                for s in 1..segments.max(1) {
+                    let t = s as f64 / segments as f64;
+                    let (a, r) = (
+                        angle(from) + t * sweep,
+                        (1.0 - t) * radius(from) + t * radius(to),
+                    );
+                    curve.push([center[0] + r * a.cos(), center[1] + r * a.sin()]);
+                }
+            }
+            curve.push(to);
+
+            let mut shape = vec![center];
+            shape.extend(curve.iter().rev());
+            shapes.polygon(&shape);
+            curves.push(curve.into_iter().map(to_point).collect());
+        }
+
+        if n % 2 == 1 {
+            let centroid = polygon::centroid(&corners);
+            curves.push(vec![to_point(middle(rotation + n - 1)), to_point(centroid)]);
+        }
+    }
+
+    let (points, face_index) = shapes.build();
+    Decoration {
+        curves,
+        shapes: Mesh::new(&format!("{}-decorated", name), points, face_index),
+    }
+}
+
+#[test]
+fn truchet_squares() {
+    let squares = crate::RegularTiling::square(6, 6);
+
+    let smith = squares.decorate(Motif::Arcs { segments: 16 }, Some(7));
+    assert_eq!(smith.curves.len(), 2 * squares.faces().len());
+    assert!(smith.curves.iter().all(|curve| curve.len() == 17));
+    // Two quarter discs of radius 1/2 per square.
+    let discs = squares.faces().len() as f64 * PI / 8.0;
+    assert!((polygon::mesh_area(&smith.shapes) - discs).abs() < 0.01 * discs);
+
+    // Seeds are reproducible and rotate faces differently.
+    assert_eq!(
+        smith,
+        squares.decorate(Motif::Arcs { segments: 16 }, Some(7))
+    );
+    assert_ne!(
+        smith,
+        squares.decorate(Motif::Arcs { segments: 16 }, Some(8))
+    );
+    let plain = squares.decorate(Motif::Arcs { segments: 16 }, None);
+    assert_ne!(smith, plain);
+
+    let truchet = squares.decorate(Motif::Triangles, Some(1));
+    assert_eq!(truchet.shapes.faces().len(), squares.faces().len());
+    assert!(
+        (polygon::mesh_area(&truchet.shapes) - squares.faces().len() as f64 / 2.0).abs() < 1.0e-4
+    );
+
+    // Triangles have a midpoint left over.
+    let triangles = crate::RegularTiling::triangle(5, 5);
+    let lines = triangles.decorate(Motif::Lines, Some(3));
+    assert_eq!(lines.curves.len(), 2 * triangles.faces().len());
+    assert_eq!(lines.shapes.faces().len(), triangles.faces().len());
+
+    // Triangles are cut from a corner to the opposite midpoint.
+    let halves = triangles.decorate(Motif::Triangles, Some(3));
+    assert_eq!(halves.shapes.faces().len(), triangles.faces().len());
+    assert!(halves.shapes.faces().iter().all(|face| face.len() >= 3));
+    let half = triangles.faces().len() as f64 * crate::SQRT_3 / 8.0;
+    assert!((polygon::mesh_area(&halves.shapes) - half).abs() < 1.0e-4 * half);
+}
@@ -0,0 +1,589 @@
+//! [DXF](https://help.autodesk.com/view/OARX/2024/ENU/?guid=GUID-235B22E0-A567-4CF6-92D3-38A2306D73F3)
+//! writer.
+use crate::{FaceIndex, Position};
+use std::{collections::BTreeSet, io::Write};
+
+/// Layer shared edges are written to.
+const EDGE_LAYER: &str = "EDGES";
+
+/// Corners of a polyline closer in `z` than this, relative to its
+/// elevation, count as level.
+const ELEVATION_TOLERANCE: f32 = 1.0e-6;
+
+/// The only line type, drawing solid lines.
+const LINE_TYPE: &str = "CONTINUOUS";
+
+fn pair(file: &mut Vec<u8>, code: u32, value: impl std::fmt::Display) -> std::io::Result<()> {
+    writeln!(file, "{:>3}", code)?;
+    writeln!(file, "{}", value)
+}
+
+fn layer_name(class: u32) -> String {
+    format!("CLASS-{}", class)
+}
+
+/// Hands out object handles, written as hexadecimal numbers. `0` means
+/// no object.
+struct Handles(u32);
+
+impl Handles {
+    fn next(&mut self) -> String {
+        self.0 += 1;
+        format!("{:X}", self.0)
+    }
+}
+
+/// Starts a symbol table; its entries are owned by `handle`.
+fn table(
+    file: &mut Vec<u8>,
+    name: &str,
+    handle: &str,
+    entries: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    pair(file, 0, "TABLE")?;
+    pair(file, 2, name)?;
+    // Dimension styles are the one table using code 105 for handles.
+    pair(file, if name == "DIMSTYLE" { 105 } else { 5 }, handle)?;
+    pair(file, 330, 0)?;
+    pair(file, 100, "AcDbSymbolTable")?;
+    pair(file, 70, entries)?;
+    if name == "DIMSTYLE" {
+        pair(file, 100, "AcDbDimStyleTable")?;
+    }
+    Ok(())
+}
+
+/// Starts an entry of the table `owner` named `name`.
+fn record(
+    file: &mut Vec<u8>,
+    kind: &str,
+    subclass: &str,
+    handle: &str,
+    owner: &str,
+    name: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    pair(file, 0, kind)?;
+    pair(file, if kind == "DIMSTYLE" { 105 } else { 5 }, handle)?;
+    pair(file, 330, owner)?;
+    pair(file, 100, "AcDbSymbolTableRecord")?;
+    pair(file, 100, subclass)?;
+    pair(file, 2, name)?;
+    pair(file, 70, 0)?;
+    Ok(())
+}
+
+/// Starts an entity in model space.
+fn entity(
+    file: &mut Vec<u8>,
+    kind: &str,
+    subclass: &str,
+    handle: &str,
+    owner: &str,
+    layer: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    pair(file, 0, kind)?;
+    pair(file, 5, handle)?;
+    pair(file, 330, owner)?;
+    pair(file, 100, "AcDbEntity")?;
+    pair(file, 8, layer)?;
+    pair(file, 100, subclass)?;
+    Ok(())
+}
+
+/// Writes an AutoCAD 2000 (R2000) file with every face as a closed
+/// `LWPOLYLINE` on a layer named after its tile class, e.g. `CLASS-1`,
+/// or, if `unique_edges` is set, every edge once as a `LINE` on the
+/// `EDGES` layer.
+///
+/// Besides the entities, the file holds the tables, blocks and objects
+/// an R2000 file requires, with a handle for every object.
+///
+/// Polylines lie in a plane parallel to the `x`, `y` plane at the
+/// elevation of their corners; faces whose corners differ in `z` are
+/// rejected.
+pub(crate) fn write<P: Position>(
+    points: &[P],
+    face_index: &FaceIndex,
+    classes: &[u32],
+    unique_edges: bool,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut file = Vec::new();
+    let mut handles = Handles(0);
+
+    // Objects referenced before they are written.
+    let dictionary = handles.next();
+    let groups = handles.next();
+    let plot_styles = handles.next();
+    let normal_plot_style = handles.next();
+    let model_space = handles.next();
+    let paper_space = handles.next();
+
+    let layers: Vec<String> = if unique_edges {
+        vec![EDGE_LAYER.to_string()]
+    } else {
+        classes
+            .iter()
+            .copied()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(layer_name)
+            .collect()
+    };
+
+    let (mut min, mut max) = ([f32::MAX; 2], [f32::MIN; 2]);
+    for point in points {
+        let [x, y, _] = point.xyz();
+        min = [min[0].min(x), min[1].min(y)];
+        max = [max[0].max(x), max[1].max(y)];
+    }
+    if points.is_empty() {
+        (min, max) = ([0.0; 2], [1.0; 2]);
+    }
+
+    pair(&mut file, 0, "SECTION")?;
+    pair(&mut file, 2, "TABLES")?;
+
+    let viewports = handles.next();
+    table(&mut file, "VPORT", &viewports, 1)?;
+    record(
+        &mut file,
+        "VPORT",
+        "AcDbViewportTableRecord",
+        &handles.next(),
+        &viewports,
+        "*ACTIVE",
+    )?;
+    // Lower left and upper right corner, center, snap base, snap and
+    // grid spacing, view direction and target.
+    for (code, value) in [
+        (10, 0.0),
+        (20, 0.0),
+        (11, 1.0),
+        (21, 1.0),
+        (12, (min[0] + max[0]) / 2.0),
+        (22, (min[1] + max[1]) / 2.0),
+        (13, 0.0),
+        (23, 0.0),
+        (14, 1.0),
+        (24, 1.0),
+        (15, 1.0),
+        (25, 1.0),
+        (16, 0.0),
+        (26, 0.0),
+        (36, 1.0),
+        (17, 0.0),
+        (27, 0.0),
+        (37, 0.0),
+    ] {
+        pair(&mut file, code, value)?;
+    }
+    // View height and aspect ratio.
+    pair(
+        &mut file,
+        40,
+        (max[1] - min[1]).max(max[0] - min[0]).max(1.0),
+    )?;
+    pair(&mut file, 41, 1.0)?;
+    pair(&mut file, 0, "ENDTAB")?;
+
+    let line_types = handles.next();
+    table(&mut file, "LTYPE", &line_types, 3)?;
+    for (name, description) in [("BYBLOCK", ""), ("BYLAYER", ""), (LINE_TYPE, "Solid line")] {
+        record(
+            &mut file,
+            "LTYPE",
+            "AcDbLinetypeTableRecord",
+            &handles.next(),
+            &line_types,
+            name,
+        )?;
+        pair(&mut file, 3, description)?;
+        pair(&mut file, 72, 65)?;
+        // No dashes.
+        pair(&mut file, 73, 0)?;
+        pair(&mut file, 40, 0.0)?;
+    }
+    pair(&mut file, 0, "ENDTAB")?;
+
+    let layer_table = handles.next();
+    table(&mut file, "LAYER", &layer_table, 1 + layers.len())?;
+    // Layer `0` always exists.
+    for (i, layer) in std::iter::once("0")
+        .chain(layers.iter().map(String::as_str))
+        .enumerate()
+    {
+        record(
+            &mut file,
+            "LAYER",
+            "AcDbLayerTableRecord",
+            &handles.next(),
+            &layer_table,
+            layer,
+        )?;
+        // AutoCAD color index: white for layer `0`, then skipping black
+        // and white.
+        pair(&mut file, 62, if i == 0 { 7 } else { 1 + (i - 1) % 6 })?;
+        pair(&mut file, 6, LINE_TYPE)?;
+        // Default line weight.
+        pair(&mut file, 370, -3)?;
+        pair(&mut file, 390, &normal_plot_style)?;
+    }
+    pair(&mut file, 0, "ENDTAB")?;
+
+    let styles = handles.next();
+    table(&mut file, "STYLE", &styles, 1)?;
+    record(
+        &mut file,
+        "STYLE",
+        "AcDbTextStyleTableRecord",
+        &handles.next(),
+        &styles,
+        "STANDARD",
+    )?;
+    pair(&mut file, 40, 0.0)?;
+    pair(&mut file, 41, 1.0)?;
+    pair(&mut file, 50, 0.0)?;
+    pair(&mut file, 71, 0)?;
+    pair(&mut file, 42, 2.5)?;
+    pair(&mut file, 3, "txt")?;
+    pair(&mut file, 4, "")?;
+    pair(&mut file, 0, "ENDTAB")?;
+
+    for name in ["VIEW", "UCS"] {
+        table(&mut file, name, &handles.next(), 0)?;
+        pair(&mut file, 0, "ENDTAB")?;
+    }
+
+    let applications = handles.next();
+    table(&mut file, "APPID", &applications, 1)?;
+    record(
+        &mut file,
+        "APPID",
+        "AcDbRegAppTableRecord",
+        &handles.next(),
+        &applications,
+        "ACAD",
+    )?;
+    pair(&mut file, 0, "ENDTAB")?;
+
+    let dimension_styles = handles.next();
+    table(&mut file, "DIMSTYLE", &dimension_styles, 1)?;
+    record(
+        &mut file,
+        "DIMSTYLE",
+        "AcDbDimStyleTableRecord",
+        &handles.next(),
+        &dimension_styles,
+        "STANDARD",
+    )?;
+    pair(&mut file, 0, "ENDTAB")?;
+
+    let block_records = handles.next();
+    table(&mut file, "BLOCK_RECORD", &block_records, 2)?;
+    for (handle, name) in [
+        (&model_space, "*Model_Space"),
+        (&paper_space, "*Paper_Space"),
+    ] {
+        record(
+            &mut file,
+            "BLOCK_RECORD",
+            "AcDbBlockTableRecord",
+            handle,
+            &block_records,
+            name,
+        )?;
+    }
+    pair(&mut file, 0, "ENDTAB")?;
+
+    pair(&mut file, 0, "ENDSEC")?;
+
+    pair(&mut file, 0, "SECTION")?;
+    pair(&mut file, 2, "BLOCKS")?;
+    for (owner, name) in [
+        (&model_space, "*Model_Space"),
+        (&paper_space, "*Paper_Space"),
+    ] {
+        pair(&mut file, 0, "BLOCK")?;
+        pair(&mut file, 5, handles.next())?;
+        pair(&mut file, 330, owner)?;
+        pair(&mut file, 100, "AcDbEntity")?;
+        if owner == &paper_space {
+            pair(&mut file, 67, 1)?;
+        }
+        pair(&mut file, 8, "0")?;
+        pair(&mut file, 100, "AcDbBlockBegin")?;
+        pair(&mut file, 2, name)?;
+        pair(&mut file, 70, 0)?;
+        pair(&mut file, 10, 0.0)?;
+        pair(&mut file, 20, 0.0)?;
+        pair(&mut file, 30, 0.0)?;
+        pair(&mut file, 3, name)?;
+        pair(&mut file, 1, "")?;
+
+        pair(&mut file, 0, "ENDBLK")?;
+        pair(&mut file, 5, handles.next())?;
+        pair(&mut file, 330, owner)?;
+        pair(&mut file, 100, "AcDbEntity")?;
+        if owner == &paper_space {
+            pair(&mut file, 67, 1)?;
+        }
+        pair(&mut file, 8, "0")?;
+        pair(&mut file, 100, "AcDbBlockEnd")?;
+    }
+    pair(&mut file, 0, "ENDSEC")?;
+
+    pair(&mut file, 0, "SECTION")?;
+    pair(&mut file, 2, "ENTITIES")?;
+
+    if unique_edges {
+        for edge in crate::topology::edges(face_index) {
+            let [a, b] = edge.vertices;
+            let [x0, y0, z0] = points[a as usize].xyz();
+            let [x1, y1, z1] = points[b as usize].xyz();
+            entity(
+                &mut file,
+                "LINE",
+                "AcDbLine",
+                &handles.next(),
+                &model_space,
+                EDGE_LAYER,
+            )?;
+            pair(&mut file, 10, x0)?;
+            pair(&mut file, 20, y0)?;
+            pair(&mut file, 30, z0)?;
+            pair(&mut file, 11, x1)?;
+            pair(&mut file, 21, y1)?;
+            pair(&mut file, 31, z1)?;
+        }
+    } else {
+        for (face, &class) in face_index.iter().zip(classes) {
+            let elevation = face.first().map_or(0.0, |&v| points[v as usize].xyz()[2]);
+            if face.iter().any(|&v| {
+                (points[v as usize].xyz()[2] - elevation).abs()
+                    > ELEVATION_TOLERANCE * elevation.abs().max(1.0)
+            }) {
+                return Err(
+                    "DXF polylines need faces parallel to the x-y plane, write unique edges instead"
+                        .into(),
+                );
+            }
+
+            entity(
+                &mut file,
+                "LWPOLYLINE",
+                "AcDbPolyline",
+                &handles.next(),
+                &model_space,
+                &layer_name(class),
+            )?;
+            pair(&mut file, 90, face.len())?;
+            // Closed.
+            pair(&mut file, 70, 1)?;
+            pair(&mut file, 38, elevation)?;
+            for &vertex in face {
+                let [x, y, _] = points[vertex as usize].xyz();
+                pair(&mut file, 10, x)?;
+                pair(&mut file, 20, y)?;
+            }
+        }
+    }
+
+    pair(&mut file, 0, "ENDSEC")?;
+
+    pair(&mut file, 0, "SECTION")?;
+    pair(&mut file, 2, "OBJECTS")?;
+
+    pair(&mut file, 0, "DICTIONARY")?;
+    pair(&mut file, 5, &dictionary)?;
+    pair(&mut file, 330, 0)?;
+    pair(&mut file, 100, "AcDbDictionary")?;
+    pair(&mut file, 281, 1)?;
+    pair(&mut file, 3, "ACAD_GROUP")?;
+    pair(&mut file, 350, &groups)?;
+    pair(&mut file, 3, "ACAD_PLOTSTYLENAME")?;
+    pair(&mut file, 350, &plot_styles)?;
+
+    pair(&mut file, 0, "DICTIONARY")?;
+    pair(&mut file, 5, &groups)?;
+    pair(&mut file, 330, &dictionary)?;
+    pair(&mut file, 100, "AcDbDictionary")?;
+    pair(&mut file, 281, 1)?;
+
+    pair(&mut file, 0, "ACDBDICTIONARYWDFLT")?;
+    pair(&mut file, 5, &plot_styles)?;
+    pair(&mut file, 330, &dictionary)?;
+    pair(&mut file, 100, "AcDbDictionary")?;
+    pair(&mut file, 281, 1)?;
+    pair(&mut file, 3, "Normal")?;
+    pair(&mut file, 350, &normal_plot_style)?;
+    pair(&mut file, 100, "AcDbDictionaryWithDefault")?;
+    pair(&mut file, 340, &normal_plot_style)?;
+
+    pair(&mut file, 0, "ACDBPLACEHOLDER")?;
+    pair(&mut file, 5, &normal_plot_style)?;
+    pair(&mut file, 330, &plot_styles)?;
+
+    pair(&mut file, 0, "ENDSEC")?;
+    pair(&mut file, 0, "EOF")?;
+
+    // The header names the next free handle, so it is written last.
+    let mut header = Vec::new();
+    pair(&mut header, 0, "SECTION")?;
+    pair(&mut header, 2, "HEADER")?;
+    pair(&mut header, 9, "$ACADVER")?;
+    pair(&mut header, 1, "AC1015")?;
+    pair(&mut header, 9, "$HANDSEED")?;
+    pair(&mut header, 5, handles.next())?;
+    pair(&mut header, 9, "$EXTMIN")?;
+    pair(&mut header, 10, min[0])?;
+    pair(&mut header, 20, min[1])?;
+    pair(&mut header, 30, 0.0)?;
+    pair(&mut header, 9, "$EXTMAX")?;
+    pair(&mut header, 10, max[0])?;
+    pair(&mut header, 20, max[1])?;
+    pair(&mut header, 30, 0.0)?;
+    pair(&mut header, 0, "ENDSEC")?;
+
+    header.append(&mut file);
+    Ok(header)
+}
+
+#[cfg(test)]
+fn pairs(dxf: &str) -> Vec<(u32, &str)> {
+    let lines: Vec<&str> = dxf.lines().collect();
+    lines
+        .chunks(2)
+        .map(|pair| (pair[0].trim().parse().unwrap(), pair[1]))
+        .collect()
+}
+
+#[cfg(test)]
+fn value<'a>(object: &[(u32, &'a str)], code: u32) -> Option<&'a str> {
+    object
+        .iter()
+        .find(|&&(c, _)| c == code)
+        .map(|&(_, value)| value)
+}
+
+#[test]
+fn dxf() -> Result<(), Box<dyn std::error::Error>> {
+    use std::collections::{HashMap, HashSet};
+
+    let tiling = crate::SemiRegularTiling::seven(20, 20);
+
+    for unique_edges in [false, true] {
+        let dxf = String::from_utf8(tiling.to_dxf(unique_edges)?)?;
+        let pairs = pairs(&dxf);
+
+        // Split into objects, each starting with a code 0 pair.
+        let mut objects: Vec<&[(u32, &str)]> = Vec::new();
+        let mut start = 0;
+        for i in 1..=pairs.len() {
+            if i == pairs.len() || pairs[i].0 == 0 {
+                objects.push(&pairs[start..i]);
+                start = i;
+            }
+        }
+
+        let sections: Vec<&str> = objects
+            .iter()
+            .filter(|object| object[0].1 == "SECTION")
+            .map(|object| value(object, 2).unwrap())
+            .collect();
+        assert_eq!(
+            sections,
+            ["HEADER", "TABLES", "BLOCKS", "ENTITIES", "OBJECTS"]
+        );
+        assert_eq!(objects.last().unwrap()[0].1, "EOF");
+
+        // Every object but the section markers, the header and the ends
+        // of tables has a unique handle below the handle seed, and is
+        // owned by an object that exists.
+        let seed = u32::from_str_radix(
+            pairs
+                .iter()
+                .skip_while(|&&pair| pair != (9, "$HANDSEED"))
+                .nth(1)
+                .unwrap()
+                .1,
+            16,
+        )?;
+        let mut handles = HashSet::new();
+        let mut owners = Vec::new();
+        for object in &objects[2..] {
+            if ["SECTION", "ENDSEC", "ENDTAB", "EOF"].contains(&object[0].1) {
+                continue;
+            }
+            let handle = value(object, 5).or_else(|| value(object, 105)).unwrap();
+            let handle = u32::from_str_radix(handle, 16)?;
+            assert!(0 < handle && handle < seed);
+            assert!(handles.insert(handle), "{:?}", object);
+            owners.push(u32::from_str_radix(value(object, 330).unwrap(), 16)?);
+        }
+        assert!(owners
+            .iter()
+            .all(|owner| *owner == 0 || handles.contains(owner)));
+
+        // Entities name their class and a layer from the layer table,
+        // drawn with a line type from the line type table.
+        let names = |kind: &str| -> HashMap<&str, &[(u32, &str)]> {
+            objects
+                .iter()
+                .filter(|object| object[0].1 == kind)
+                .map(|&object| (value(object, 2).unwrap(), object))
+                .collect()
+        };
+        let (layers, line_types) = (names("LAYER"), names("LTYPE"));
+        assert!(layers.values().all(|layer| {
+            value(layer, 100) == Some("AcDbSymbolTableRecord")
+                && layer.contains(&(100, "AcDbLayerTableRecord"))
+                && line_types.contains_key(value(layer, 6).unwrap())
+        }));
+        assert!(line_types.contains_key(LINE_TYPE));
+
+        let (kind, subclass) = if unique_edges {
+            ("LINE", "AcDbLine")
+        } else {
+            ("LWPOLYLINE", "AcDbPolyline")
+        };
+        let entities: Vec<_> = objects
+            .iter()
+            .filter(|object| object[0].1 == kind)
+            .collect();
+        for entity in &entities {
+            assert_eq!(value(entity, 100), Some("AcDbEntity"));
+            assert!(entity.contains(&(100, subclass)));
+            assert!(layers.contains_key(value(entity, 8).unwrap()));
+            if !unique_edges {
+                let vertices: usize = value(entity, 90).unwrap().parse()?;
+                assert_eq!(entity.iter().filter(|&&(c, _)| c == 10).count(), vertices);
+            }
+        }
+
+        let directed: usize = tiling.faces().iter().map(|face| face.len()).sum();
+        if unique_edges {
+            // Interior edges are shared by two faces.
+            assert!(directed / 2 < entities.len() && entities.len() < directed);
+        } else {
+            assert_eq!(entities.len(), tiling.faces().len());
+            // Triangles, squares and hexagons.
+            assert!(["CLASS-0", "CLASS-1", "CLASS-2"]
+                .iter()
+                .all(|layer| layers.contains_key(layer)));
+        }
+    }
+
+    // Mirrored hats get a layer of their own.
+    let hats = String::from_utf8(crate::AperiodicTiling::hat(1).to_dxf(false)?)?;
+    assert!(hats.contains("\nCLASS-1\n"));
+
+    // Level faces keep their elevation, others cannot be polylines.
+    let raised = tiling.map_with(|p| crate::Point3::new(p.x, p.y, 2.5), 1);
+    let raised = String::from_utf8(raised.to_dxf(false)?)?;
+    assert_eq!(raised.matches(" 38\n2.5\n").count(), tiling.faces().len());
+    let sphere = tiling.map_onto(crate::Surface::Sphere { radius: 1.0 }, 1);
+    assert!(sphere.to_dxf(false).is_err());
+    assert!(sphere.to_dxf(true).is_ok());
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use std::fmt;
+
+/// Errors returned by constructors that validate their parameters.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The Schläfli symbol `{p, q}` does not describe a tiling of the
+    /// requested geometry.
+    SchlafliSymbol { p: u32, q: u32 },
+    /// A constructor expected a different number of parameters.
+    ParameterCount { expected: usize, found: usize },
+    /// The parameters yield a tile that is not a simple polygon with
+    /// positive area.
+    DegenerateTile,
+    /// The tile violates a condition its tiling type imposes, e.g.
+    /// `"B + C = 180°"`.
+    Constraint(&'static str),
+    /// A file could not be read. Lines are counted from one.
+    Parse { line: usize, message: &'static str },
+    /// The points of a planar tiling do not lie in one plane.
+    NotPlanar,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::SchlafliSymbol { p, q } => {
+                write!(f, "{{{}, {}}} is not a tiling of this geometry", p, q)
+            }
+            Error::ParameterCount { expected, found } => {
+                write!(f, "expected {} parameters but got {}", expected, found)
+            }
+            Error::DegenerateTile => write!(f, "the tile is not a simple polygon"),
+            Error::Constraint(constraint) => write!(f, "the tile violates {}", constraint),
+            Error::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            Error::NotPlanar => write!(f, "the points do not lie in one plane"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
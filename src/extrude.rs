@@ -0,0 +1,219 @@
+//! Solid tiles made from the faces of a tiling.
+use crate::{polygon, Error, Face, FaceIndex, Mesh, Point3, Points3, Position};
+
+fn check(grout: f32, thickness: f32, bevel: f32) -> Result<(), Error> {
+    if !(grout >= 0.0 && grout.is_finite()) {
+        return Err(Error::Constraint("grout ≥ 0"));
+    }
+    if !(thickness > 0.0 && thickness.is_finite()) {
+        return Err(Error::Constraint("thickness > 0"));
+    }
+    if !(bevel >= 0.0 && bevel < thickness) {
+        return Err(Error::Constraint("0 ≤ bevel < thickness"));
+    }
+    Ok(())
+}
+
+/// Turns a face into a closed solid: the face is inset by half the
+/// grout width, extruded along its normal by `thickness` and its top
+/// edges are chamfered by `bevel`.
+fn extrude_face(
+    corners: &[[f32; 3]],
+    grout: f32,
+    thickness: f32,
+    bevel: f32,
+) -> Result<(Points3, FaceIndex), Error> {
+    let n = corners.len();
+    let normal = polygon::newell_normal(corners);
+    let length = (normal[0] * normal[0] + normal[1] * normal[1] + normal[2] * normal[2]).sqrt();
+    let normal = normal.map(|c| (c / length) as f32);
+    let lift = |ring: Vec<[f32; 3]>, height: f32| -> Vec<[f32; 3]> {
+        ring.into_iter()
+            .map(|p| [0, 1, 2].map(|k| p[k] + height * normal[k]))
+            .collect()
+    };
+
+    let base = polygon::inset(corners, grout / 2.0);
+    if polygon::collapsed(corners, &base) {
+        return Err(Error::DegenerateTile);
+    }
+    let mut rings = vec![base.clone()];
+    if bevel > 0.0 {
+        let top = polygon::inset(&base, bevel);
+        if polygon::collapsed(&base, &top) {
+            return Err(Error::DegenerateTile);
+        }
+        rings.push(lift(base, thickness - bevel));
+        rings.push(lift(top, thickness));
+    } else {
+        rings.push(lift(base, thickness));
+    }
+
+    let points = rings.concat().into_iter().map(Point3::from).collect();
+
+    let key = |ring: usize, i: usize| (ring * n + i % n) as u32;
+    let mut face_index: FaceIndex = Vec::with_capacity(2 + n * (rings.len() - 1));
+    face_index.push((0..n).rev().map(|i| key(0, i)).collect());
+    for ring in 0..rings.len() - 1 {
+        for i in 0..n {
+            face_index.push(vec![
+                key(ring, i),
+                key(ring, i + 1),
+                key(ring + 1, i + 1),
+                key(ring + 1, i),
+            ]);
+        }
+    }
+    face_index.push((0..n).map(|i| key(rings.len() - 1, i)).collect());
+
+    Ok((points, face_index))
+}
+
+/// One closed solid per face.
+pub(crate) fn extrude_tiles<P: Position>(
+    name: &str,
+    points: &[P],
+    face_index: &FaceIndex,
+    grout: f32,
+    thickness: f32,
+    bevel: f32,
+) -> Result<Vec<Mesh<Point3>>, Error> {
+    check(grout, thickness, bevel)?;
+
+    face_index
+        .iter()
+        .enumerate()
+        .map(|(index, face)| {
+            let corners: Vec<[f32; 3]> = face.iter().map(|&v| points[v as usize].xyz()).collect();
+            let (points, face_index) = extrude_face(&corners, grout, thickness, bevel)?;
+            Ok(Mesh::new(
+                &format!("{}-tile-{}", name, index),
+                points,
+                face_index,
+            ))
+        })
+        .collect()
+}
+
+/// All solids in one mesh.
+pub(crate) fn extrude<P: Position>(
+    name: &str,
+    points: &[P],
+    face_index: &FaceIndex,
+    grout: f32,
+    thickness: f32,
+    bevel: f32,
+) -> Result<Mesh<Point3>, Error> {
+    check(grout, thickness, bevel)?;
+
+    let mut merged_points = Points3::new();
+    let mut merged_faces = FaceIndex::new();
+
+    for face in face_index {
+        let corners: Vec<[f32; 3]> = face.iter().map(|&v| points[v as usize].xyz()).collect();
+        let (points, face_index) = extrude_face(&corners, grout, thickness, bevel)?;
+        let offset = merged_points.len() as u32;
+        merged_faces.extend(
+            face_index
+                .into_iter()
+                .map(|face| face.into_iter().map(|v| v + offset).collect::<Face>()),
+        );
+        merged_points.extend(points);
+    }
+
+    Ok(Mesh::new(
+        &format!("{}-extruded", name),
+        merged_points,
+        merged_faces,
+    ))
+}
+
+#[test]
+fn extrude_hats() {
+    use std::collections::HashSet;
+
+    let tiling = crate::AperiodicTiling::hat(1);
+
+    for &bevel in &[0.0, 0.05] {
+        let solid = tiling.extrude(0.1, 0.3, bevel).unwrap();
+
+        // Closed and manifold: every edge is traversed once in each
+        // direction.
+        let mut directed = HashSet::new();
+        for face in solid.faces() {
+            for i in 0..face.len() {
+                assert!(directed.insert((face[i], face[(i + 1) % face.len()])));
+            }
+        }
+        assert!(directed.iter().all(|&(a, b)| directed.contains(&(b, a))));
+
+        // One sphere-like shell per tile.
+        let euler =
+            solid.points().len() as i64 - directed.len() as i64 / 2 + solid.faces().len() as i64;
+        assert_eq!(euler, 2 * tiling.faces().len() as i64);
+
+        assert!(solid
+            .points()
+            .iter()
+            .all(|p| p.z >= 0.0 && p.z <= 0.3 + 1.0e-6));
+    }
+
+    let tiles = tiling.extrude_tiles(0.1, 0.3, 0.0).unwrap();
+    assert_eq!(tiles.len(), tiling.faces().len());
+    // Grout shrinks the footprint of a tile.
+    let area = |corners: Vec<[f32; 3]>| {
+        polygon::signed_area(
+            &corners
+                .iter()
+                .map(|p| [p[0] as f64, p[1] as f64])
+                .collect::<Vec<_>>(),
+        )
+    };
+    let face = &tiling.faces()[0];
+    let bottom = &tiles[0].faces()[0];
+    assert!(
+        -area(
+            bottom
+                .iter()
+                .map(|&v| Position::xyz(&tiles[0].points()[v as usize]))
+                .collect()
+        ) < area(
+            face.iter()
+                .map(|&v| Position::xyz(&tiling.points()[v as usize]))
+                .collect()
+        )
+    );
+}
+
+#[test]
+fn extrude_errors() {
+    let squares = crate::RegularTiling::square(3, 3);
+
+    assert_eq!(
+        squares.extrude(-0.1, 0.3, 0.0).err(),
+        Some(Error::Constraint("grout ≥ 0"))
+    );
+    assert_eq!(
+        squares.extrude(0.1, -0.3, 0.0).err(),
+        Some(Error::Constraint("thickness > 0"))
+    );
+    assert_eq!(
+        squares.extrude_tiles(0.1, 0.3, 0.3).err(),
+        Some(Error::Constraint("0 ≤ bevel < thickness"))
+    );
+    assert_eq!(
+        squares.extrude(0.1, 0.3, -0.1).err(),
+        Some(Error::Constraint("0 ≤ bevel < thickness"))
+    );
+
+    // Unit squares vanish with grout as wide as they are, or a bevel
+    // reaching past their middle.
+    assert_eq!(
+        squares.extrude(1.0, 0.3, 0.0).err(),
+        Some(Error::DegenerateTile)
+    );
+    assert_eq!(
+        squares.extrude(0.2, 1.0, 0.45).err(),
+        Some(Error::DegenerateTile)
+    );
+}
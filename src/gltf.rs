@@ -0,0 +1,315 @@
+//! [glTF 2.0](https://registry.khronos.org/glTF/specs/2.0/glTF-2.0.html)
+//! writer.
+use crate::{palette::class_color, polygon, Encoding, FaceIndex, Position};
+use std::{collections::BTreeMap, fmt::Write as _, io::Write};
+
+/// Optional contents of a glTF file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GltfOptions {
+    /// Write texture coordinates that map the bounding box of the
+    /// tiling's `x`, `y` coordinates to the unit square, keeping its
+    /// aspect ratio.
+    pub uvs: bool,
+    /// Write vertex normals averaged from the adjacent faces. Points no
+    /// face uses get the normal `(0, 0, 1)`.
+    pub normals: bool,
+    /// Write the corners of every triangle in clockwise order, flipping
+    /// the side it faces.
+    pub reverse_face_winding: bool,
+}
+
+const FLOAT: u32 = 5126;
+const UNSIGNED_INT: u32 = 5125;
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+/// Writes a single mesh with one primitive and material per tile
+/// class.
+///
+/// [`Encoding::Ascii`] yields a `.gltf` file with the buffer embedded
+/// as a data URI, [`Encoding::Binary`] a `.glb` file.
+pub(crate) fn write<P: Position>(
+    name: &str,
+    points: &[P],
+    face_index: &FaceIndex,
+    classes: &[u32],
+    encoding: Encoding,
+    options: &GltfOptions,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let positions: Vec<[f32; 3]> = points.iter().map(Position::xyz).collect();
+
+    // Triangles grouped by tile class.
+    let mut triangle_classes: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+    for (face, &class) in face_index.iter().zip(classes) {
+        let corners: Vec<[f32; 3]> = face.iter().map(|&v| positions[v as usize]).collect();
+        let triangles = polygon::triangulate(&corners);
+        if triangles.is_empty() {
+            continue;
+        }
+
+        let indices = triangle_classes.entry(class).or_default();
+        for [a, b, c] in triangles {
+            let (a, b, c) = if options.reverse_face_winding {
+                (face[c], face[b], face[a])
+            } else {
+                (face[a], face[b], face[c])
+            };
+            indices.extend_from_slice(&[a, b, c]);
+        }
+    }
+
+    // Empty buffers and accessors are not allowed.
+    if triangle_classes.is_empty() {
+        return Err("glTF files need at least one face with three vertices".into());
+    }
+
+    let mut buffer = Vec::new();
+    // (offset, length, target) per buffer view.
+    let mut views = Vec::new();
+    let mut accessors = Vec::new();
+
+    let mut attribute = |buffer: &mut Vec<u8>, values: &[f32], kind: &str, bounds: &str| {
+        views.push((buffer.len(), 4 * values.len(), ARRAY_BUFFER));
+        for value in values {
+            buffer.extend_from_slice(&value.to_le_bytes());
+        }
+        let components = if kind == "VEC2" { 2 } else { 3 };
+        accessors.push(format!(
+            r#"{{"bufferView":{},"componentType":{},"count":{},"type":"{}"{}}}"#,
+            views.len() - 1,
+            FLOAT,
+            values.len() / components,
+            kind,
+            bounds
+        ));
+        accessors.len() - 1
+    };
+
+    let mut min = [f32::MAX; 3];
+    let mut max = [f32::MIN; 3];
+    for p in &positions {
+        for k in 0..3 {
+            min[k] = min[k].min(p[k]);
+            max[k] = max[k].max(p[k]);
+        }
+    }
+    let bounds = if positions.is_empty() {
+        String::new()
+    } else {
+        format!(
+            r#","min":[{},{},{}],"max":[{},{},{}]"#,
+            min[0], min[1], min[2], max[0], max[1], max[2]
+        )
+    };
+
+    let mut attributes = format!(
+        r#""POSITION":{}"#,
+        attribute(&mut buffer, &positions.concat(), "VEC3", &bounds)
+    );
+
+    if options.normals {
+        let sign = if options.reverse_face_winding {
+            -1.0
+        } else {
+            1.0
+        };
+        let normals: Vec<f32> = polygon::vertex_normals(&positions, face_index)
+            .iter()
+            .flat_map(|n| n.map(|c| sign * c))
+            .collect();
+        write!(
+            attributes,
+            r#","NORMAL":{}"#,
+            attribute(&mut buffer, &normals, "VEC3", "")
+        )?;
+    }
+
+    if options.uvs {
+        let extent = (max[0] - min[0]).max(max[1] - min[1]);
+        let extent = if extent > 0.0 { extent } else { 1.0 };
+        // glTF's v axis points down.
+        let uvs: Vec<f32> = positions
+            .iter()
+            .flat_map(|p| [(p[0] - min[0]) / extent, 1.0 - (p[1] - min[1]) / extent])
+            .collect();
+        write!(
+            attributes,
+            r#","TEXCOORD_0":{}"#,
+            attribute(&mut buffer, &uvs, "VEC2", "")
+        )?;
+    }
+
+    let mut primitives = Vec::new();
+    let mut materials = Vec::new();
+    for (class, indices) in &triangle_classes {
+        views.push((buffer.len(), 4 * indices.len(), ELEMENT_ARRAY_BUFFER));
+        for index in indices {
+            buffer.extend_from_slice(&index.to_le_bytes());
+        }
+        accessors.push(format!(
+            r#"{{"bufferView":{},"componentType":{},"count":{},"type":"SCALAR"}}"#,
+            views.len() - 1,
+            UNSIGNED_INT,
+            indices.len()
+        ));
+
+        // Base color factors are linear.
+        let [r, g, b] = class_color(*class).map(|c| {
+            let c = c as f32 / 255.0;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        });
+        materials.push(format!(
+            r#"{{"name":"class-{}","doubleSided":true,"pbrMetallicRoughness":{{"baseColorFactor":[{},{},{},1],"metallicFactor":0,"roughnessFactor":1}}}}"#,
+            class, r, g, b
+        ));
+        primitives.push(format!(
+            r#"{{"attributes":{{{}}},"indices":{},"material":{},"mode":4}}"#,
+            attributes,
+            accessors.len() - 1,
+            materials.len() - 1
+        ));
+    }
+
+    let uri = match encoding {
+        Encoding::Ascii => format!(
+            r#","uri":"data:application/octet-stream;base64,{}""#,
+            base64(&buffer)
+        ),
+        Encoding::Binary => String::new(),
+    };
+    let name = name.replace('\\', "\\\\").replace('"', "\\\"");
+
+    let json = format!(
+        concat!(
+            r#"{{"asset":{{"version":"2.0","generator":"tilings"}},"#,
+            r#""scene":0,"scenes":[{{"nodes":[0]}}],"#,
+            r#""nodes":[{{"mesh":0,"name":"{name}-tiling"}}],"#,
+            r#""meshes":[{{"name":"{name}","primitives":[{primitives}]}}],"#,
+            r#""materials":[{materials}],"#,
+            r#""buffers":[{{"byteLength":{length}{uri}}}],"#,
+            r#""bufferViews":[{views}],"#,
+            r#""accessors":[{accessors}]}}"#
+        ),
+        name = name,
+        primitives = primitives.join(","),
+        materials = materials.join(","),
+        length = buffer.len(),
+        uri = uri,
+        views = views
+            .iter()
+            .map(|(offset, length, target)| format!(
+                r#"{{"buffer":0,"byteOffset":{},"byteLength":{},"target":{}}}"#,
+                offset, length, target
+            ))
+            .collect::<Vec<_>>()
+            .join(","),
+        accessors = accessors.join(","),
+    );
+
+    Ok(match encoding {
+        Encoding::Ascii => json.into_bytes(),
+        Encoding::Binary => {
+            let mut json = json.into_bytes();
+            while json.len() % 4 != 0 {
+                json.push(b' ');
+            }
+            while buffer.len() % 4 != 0 {
+                buffer.push(0);
+            }
+
+            let mut file = Vec::with_capacity(28 + json.len() + buffer.len());
+            file.write_all(b"glTF")?;
+            file.write_all(&2u32.to_le_bytes())?;
+            file.write_all(&(28 + json.len() as u32 + buffer.len() as u32).to_le_bytes())?;
+            file.write_all(&(json.len() as u32).to_le_bytes())?;
+            file.write_all(b"JSON")?;
+            file.write_all(&json)?;
+            file.write_all(&(buffer.len() as u32).to_le_bytes())?;
+            file.write_all(b"BIN\0")?;
+            file.write_all(&buffer)?;
+            file
+        }
+    })
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(4 * data.len().div_ceil(3));
+    for chunk in data.chunks(3) {
+        let bits = chunk.iter().enumerate().fold(0u32, |bits, (i, &byte)| {
+            bits | (byte as u32) << (16 - 8 * i)
+        });
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(bits >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[test]
+fn gltf() -> Result<(), Box<dyn std::error::Error>> {
+    assert_eq!(base64(b"tilings"), "dGlsaW5ncw==");
+
+    let tiling = crate::SemiRegularTiling::seven(6, 6);
+    let options = GltfOptions {
+        uvs: true,
+        normals: true,
+        ..Default::default()
+    };
+
+    let gltf = String::from_utf8(tiling.to_gltf(Encoding::Ascii, &options)?)?;
+    assert!(gltf.contains(r#""NORMAL":1,"TEXCOORD_0":2"#));
+    // Triangles and squares.
+    assert_eq!(gltf.matches(r#""mode":4"#).count(), 2);
+
+    // Mirrored hats get a material of their own.
+    let hats = crate::AperiodicTiling::hat(1);
+    let gltf = String::from_utf8(hats.to_gltf(Encoding::Ascii, &GltfOptions::default())?)?;
+    assert!(gltf.contains(r#""name":"class-1""#));
+    assert_eq!(gltf.matches(r#""mode":4"#).count(), 2);
+
+    let empty = crate::Mesh::<crate::Point>::new("empty", Vec::new(), Vec::new());
+    assert!(empty.to_gltf(Encoding::Binary, &options).is_err());
+
+    let glb = tiling.to_gltf(Encoding::Binary, &options)?;
+    assert_eq!(&glb[0..4], b"glTF");
+    assert_eq!(&glb[8..12], &(glb.len() as u32).to_le_bytes());
+    let json_length = u32::from_le_bytes([glb[12], glb[13], glb[14], glb[15]]) as usize;
+    let bin_length = u32::from_le_bytes([
+        glb[20 + json_length],
+        glb[21 + json_length],
+        glb[22 + json_length],
+        glb[23 + json_length],
+    ]) as usize;
+    assert_eq!(28 + json_length + bin_length, glb.len());
+
+    let triangles: usize = tiling.faces().iter().map(|face| face.len() - 2).sum();
+    // Positions, normals, UVs and indices.
+    assert_eq!(
+        bin_length,
+        tiling.points().len() * 4 * (3 + 3 + 2) + triangles * 3 * 4
+    );
+
+    // Most of the grid's points are not corners of any face, yet every
+    // normal must have unit length.
+    let bin = &glb[28 + json_length..];
+    let mut normals = bin[tiling.points().len() * 12..][..tiling.points().len() * 12]
+        .chunks(12)
+        .map(|n| {
+            let [x, y, z] =
+                [0, 4, 8].map(|i| f32::from_le_bytes([n[i], n[i + 1], n[i + 2], n[i + 3]]));
+            (x * x + y * y + z * z).sqrt()
+        });
+    assert!(normals.all(|length| (length - 1.0).abs() < 1.0e-5));
+
+    Ok(())
+}
@@ -0,0 +1,256 @@
+//! Islamic star patterns by Hankin's polygons-in-contact method.
+use crate::{
+    affine::DPoint,
+    builder::MeshBuilder,
+    polygon,
+    strand::{self, Crossing, InteriorCrossing, Piece, Strand},
+    Error, FaceIndex, Mesh, Position,
+};
+use std::collections::BTreeSet;
+
+/// A star pattern drawn over a tiling.
+///
+/// Two rays leave every edge at the contact angle and run into the
+/// faces on either side until they meet a ray from a neighbouring edge.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HankinPattern {
+    /// The lines of the pattern, joined across the edges of the tiling.
+    pub strands: Vec<Strand>,
+    /// Where strands cross and which one passes over. Empty unless the
+    /// pattern is interlaced.
+    pub crossings: Vec<Crossing>,
+    /// The pieces the lines cut the faces into: a star or rosette per
+    /// face, a piece per corner and, with an offset, a triangle per
+    /// edge. Corners whose rays do not meet get no piece.
+    pub regions: Mesh,
+}
+
+/// Identifies where a ray leaves an edge: the edge's end points in
+/// ascending order and a flag telling the two rays apart.
+type ContactKey = (u32, u32, bool);
+
+fn along(p: DPoint, direction: DPoint, t: f64) -> DPoint {
+    [p[0] + t * direction[0], p[1] + t * direction[1]]
+}
+
+pub(crate) fn hankin<P: Position>(
+    name: &str,
+    points: &[P],
+    face_index: &FaceIndex,
+    contact_angle: f32,
+    offset: f32,
+    interlaced: bool,
+) -> Result<HankinPattern, Error> {
+    if !(contact_angle > 0.0 && contact_angle < std::f32::consts::FRAC_PI_2) {
+        return Err(Error::Constraint("0 < contact angle < 90°"));
+    }
+    if !(offset >= 0.0 && offset.is_finite()) {
+        return Err(Error::Constraint("offset ≥ 0"));
+    }
+    let (sin, cos) = (contact_angle as f64).sin_cos();
+    let offset = offset as f64;
+
+    let mut pieces: Vec<Piece<ContactKey>> = Vec::new();
+    let mut interior: Vec<InteriorCrossing> = Vec::new();
+    let mut edges = BTreeSet::new();
+    let mut regions = MeshBuilder::new();
+
+    for face in face_index {
+        let n = face.len();
+        let corners: Vec<DPoint> = face
+            .iter()
+            .map(|&v| {
+                let [x, y, _] = points[v as usize].xyz();
+                [x as f64, y as f64]
+            })
+            .collect();
+        // Rays run into the face whichever way it winds.
+        let inward = polygon::signed_area(&corners).signum();
+
+        // Start and direction of the ray leaning towards the end of
+        // each edge and of the one leaning towards its start. With an
+        // offset they cross at a point above the edge.
+        let mut to_end = Vec::with_capacity(n);
+        let mut to_start = Vec::with_capacity(n);
+        let mut above = Vec::with_capacity(n);
+        for i in 0..n {
+            let (a, b) = (corners[i], corners[(i + 1) % n]);
+            let length = ((b[0] - a[0]).powi(2) + (b[1] - a[1]).powi(2)).sqrt();
+            let d = [(b[0] - a[0]) / length, (b[1] - a[1]) / length];
+            let normal = [-inward * d[1], inward * d[0]];
+            let middle = [(a[0] + b[0]) / 2.0, (a[1] + b[1]) / 2.0];
+            let shift = offset.min(length / 2.0);
+
+            to_end.push((
+                along(middle, d, -shift),
+                [cos * d[0] + sin * normal[0], cos * d[1] + sin * normal[1]],
+            ));
+            to_start.push((
+                along(middle, d, shift),
+                [-cos * d[0] + sin * normal[0], -cos * d[1] + sin * normal[1]],
+            ));
+            above.push(along(middle, normal, shift * sin / cos));
+
+            let (va, vb) = (face[i], face[(i + 1) % n]);
+            edges.insert((va.min(vb), va.max(vb)));
+        }
+
+        let key = |i: usize, towards_end: bool| -> ContactKey {
+            let (a, b) = (face[i], face[(i + 1) % n]);
+            // Without an offset, a line continues straight into the
+            // neighbouring face; with one, it is reflected at the edge.
+            (
+                a.min(b),
+                a.max(b),
+                if offset == 0.0 {
+                    towards_end
+                } else {
+                    towards_end == (a < b)
+                },
+            )
+        };
+
+        // The rays meeting near each corner, if they do.
+        let meets: Vec<Option<DPoint>> = (0..n)
+            .map(|i| {
+                let ((p, u), (q, w)) = (to_end[i], to_start[(i + 1) % n]);
+                match polygon::line_intersection(p, u, q, w) {
+                    Some((s, t)) if s > 0.0 && t > 0.0 => Some(along(p, u, s)),
+                    Some(_) => None,
+                    // Rays along the same line meet halfway.
+                    None => Some([(p[0] + q[0]) / 2.0, (p[1] + q[1]) / 2.0]),
+                }
+            })
+            .collect();
+
+        let first = pieces.len();
+        for (i, meet) in meets.iter().enumerate() {
+            let j = (i + 1) % n;
+            if offset > 0.0 {
+                regions.polygon(&[to_end[i].0, to_start[i].0, above[i]]);
+            }
+            if let Some(meet) = *meet {
+                pieces.push(Piece {
+                    points: vec![to_end[i].0, meet, to_start[j].0],
+                    ends: [key(i, true), key(j, false)],
+                });
+                let mut corner = vec![to_start[i].0, corners[j], to_end[j].0];
+                if offset > 0.0 {
+                    corner.extend_from_slice(&[above[j], meet, above[i]]);
+                } else {
+                    corner.push(meet);
+                }
+                regions.polygon(&corner);
+            }
+        }
+
+        if interlaced {
+            for p1 in first..pieces.len() {
+                for p2 in p1 + 1..pieces.len() {
+                    for k1 in 0..2 {
+                        for k2 in 0..2 {
+                            let (a, b) = (pieces[p1].points[k1], pieces[p1].points[k1 + 1]);
+                            let (c, d) = (pieces[p2].points[k2], pieces[p2].points[k2 + 1]);
+                            let u = [b[0] - a[0], b[1] - a[1]];
+                            let w = [d[0] - c[0], d[1] - c[1]];
+                            const EPSILON: f64 = 1.0e-9;
+                            let inside = |t: f64| t > EPSILON && t < 1.0 - EPSILON;
+                            if let Some((s, t)) = polygon::line_intersection(a, u, c, w) {
+                                if inside(s) && inside(t) {
+                                    interior.push((
+                                        [(p1, k1 as f64 + s), (p2, k2 as f64 + t)],
+                                        along(a, u, s),
+                                    ));
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut star = Vec::with_capacity(2 * n);
+        for i in 0..n {
+            star.push(above[i]);
+            star.push(meets[i].unwrap_or(corners[(i + 1) % n]));
+        }
+        regions.polygon(&star);
+    }
+
+    // Without an offset, the lines through an edge's midpoint cross
+    // there.
+    let joints: Vec<[ContactKey; 2]> = if interlaced && offset == 0.0 {
+        edges
+            .into_iter()
+            .map(|(a, b)| [(a, b, true), (a, b, false)])
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let (strands, crossings) = strand::assemble(&pieces, &interior, &joints);
+
+    let (points, face_index) = regions.build();
+    Ok(HankinPattern {
+        strands,
+        crossings,
+        regions: Mesh::new(&format!("{}-hankin", name), points, face_index),
+    })
+}
+
+#[test]
+fn hankin_squares_and_hexagons() {
+    let squares = crate::RegularTiling::square(4, 4);
+    let pattern = squares.hankin(60f32.to_radians(), 0.0, true).unwrap();
+    // Lines cross at the midpoint of every interior edge.
+    let interior_edges = squares.edges().iter().filter(|e| !e.is_boundary()).count();
+    assert_eq!(pattern.crossings.len(), interior_edges);
+    // A star and four corners per square, covering it.
+    assert_eq!(pattern.regions.faces().len(), 5 * squares.faces().len());
+    // Squares have unit sides.
+    assert!((polygon::mesh_area(&pattern.regions) - squares.faces().len() as f64).abs() < 1.0e-3);
+
+    let hexagons = crate::RegularTiling::hexagon(12, 12);
+    for &(angle, offset) in &[(70f32, 0.0), (70.0, 0.1), (50.0, 0.05)] {
+        let pattern = hexagons.hankin(angle.to_radians(), offset, true).unwrap();
+        assert!(!pattern.crossings.is_empty());
+        // Unit hexagons have an area of 3 √3 / 2.
+        let hexagon_area = 3.0 * 3f64.sqrt() / 2.0;
+        assert!(
+            (polygon::mesh_area(&pattern.regions) - hexagon_area * hexagons.faces().len() as f64)
+                .abs()
+                < 1.0e-3
+        );
+
+        // Crossings alternate along every strand.
+        for (s, strand) in pattern.strands.iter().enumerate() {
+            let mut along: Vec<(usize, bool)> = pattern
+                .crossings
+                .iter()
+                .flat_map(|c| [(c.over, true), (c.under, false)])
+                .filter(|&((strand, _), _)| strand == s)
+                .map(|((_, i), over)| (i, over))
+                .collect();
+            along.sort_unstable();
+            assert!(along.windows(2).all(|w| w[0].1 != w[1].1));
+            for &(i, _) in &along {
+                assert!(i < strand.points.len());
+            }
+        }
+        for crossing in &pattern.crossings {
+            let (s, i) = crossing.over;
+            assert_eq!(pattern.strands[s].points[i], crossing.position);
+            let (s, i) = crossing.under;
+            assert!((pattern.strands[s].points[i] - crossing.position).mag() < 1.0e-5);
+        }
+    }
+
+    assert!(hexagons
+        .hankin(70f32.to_radians(), 0.0, false)
+        .unwrap()
+        .crossings
+        .is_empty());
+    assert_eq!(
+        hexagons.hankin(0.0, 0.0, false),
+        Err(Error::Constraint("0 < contact angle < 90°"))
+    );
+}
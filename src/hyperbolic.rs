@@ -0,0 +1,283 @@
+//! Regular tilings `{p, q}` of the hyperbolic plane.
+//!
+//! Tiles are generated on the hyperboloid, where reflections in edges
+//! are linear maps, and only projected into one of the planar models
+//! at the end.
+use crate::{builder::MeshBuilder, Error, FaceIndex, Point, Points};
+use std::{collections::VecDeque, f64::consts::PI};
+
+type Hyperboloid = [f64; 3];
+
+/// The largest `p` and `q` accepted.
+const MAX_SIDES: u32 = 1024;
+/// The largest number of rings grown around the first tile.
+const MAX_DEPTH: u32 = 16;
+/// The largest number of tile corners, counting those along subdivided
+/// edges, before shared ones are welded.
+const MAX_CORNERS: u64 = 1 << 24;
+
+/// The planar model the hyperbolic plane is drawn in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HyperbolicModel {
+    /// Conformal model inside the unit disk. Edges are circular arcs.
+    #[default]
+    PoincareDisk,
+    /// Projective model inside the unit disk. Edges are straight.
+    Klein,
+    /// Conformal model in the upper half-plane `y > 0`.
+    UpperHalfPlane,
+}
+
+fn dot(a: &Hyperboloid, b: &Hyperboloid) -> f64 {
+    a[0] * b[0] + a[1] * b[1] - a[2] * b[2]
+}
+
+/// Normal of the plane through the origin containing `a` and `b`, which
+/// cuts the hyperboloid in the geodesic through both points.
+fn normal(a: &Hyperboloid, b: &Hyperboloid) -> Hyperboloid {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        -(a[0] * b[1] - a[1] * b[0]),
+    ]
+}
+
+fn reflect(v: &Hyperboloid, n: &Hyperboloid) -> Hyperboloid {
+    let s = 2.0 * dot(v, n) / dot(n, n);
+    [v[0] - s * n[0], v[1] - s * n[1], v[2] - s * n[2]]
+}
+
+/// The point a fraction `u` along the geodesic from `a` to `b`.
+fn interpolate(a: &Hyperboloid, b: &Hyperboloid, u: f64) -> Hyperboloid {
+    // Far from the origin, renormalizing loses all precision, so the
+    // start point, a tile's corner, is returned as it is.
+    if u == 0.0 {
+        return *a;
+    }
+    let w = [
+        a[0] * (1.0 - u) + b[0] * u,
+        a[1] * (1.0 - u) + b[1] * u,
+        a[2] * (1.0 - u) + b[2] * u,
+    ];
+    let s = 1.0 / (-dot(&w, &w)).sqrt();
+    [w[0] * s, w[1] * s, w[2] * s]
+}
+
+fn project(v: &Hyperboloid, model: HyperbolicModel) -> Point {
+    let (x, y) = match model {
+        HyperbolicModel::PoincareDisk => (v[0] / (1.0 + v[2]), v[1] / (1.0 + v[2])),
+        HyperbolicModel::Klein => (v[0] / v[2], v[1] / v[2]),
+        HyperbolicModel::UpperHalfPlane => {
+            // Cayley transform of the Poincaré disk.
+            let (u, v) = (v[0] / (1.0 + v[2]), v[1] / (1.0 + v[2]));
+            let d = (1.0 - u) * (1.0 - u) + v * v;
+            (-2.0 * v / d, (1.0 - u * u - v * v) / d)
+        }
+    };
+    Point::new(x as _, y as _)
+}
+
+/// A patch of the regular hyperbolic tiling `{p, q}`: regular `p`-gons,
+/// `q` of which meet at every vertex.
+///
+/// ![](https://upload.wikimedia.org/wikipedia/commons/thumb/f/f6/Heptagonal_tiling.svg/1920px-Heptagonal_tiling.svg.png)
+pub struct HyperbolicTiling {
+    face_index: FaceIndex,
+    points: Points,
+    hyperboloid: Vec<Hyperboloid>,
+    model: HyperbolicModel,
+    name: String,
+    topology: crate::topology::TopologyCache,
+    classes: crate::class::ClassCache,
+}
+
+impl HyperbolicTiling {
+    default_methods! {}
+
+    /// Creates the tiling `{p, q}` in the Poincaré disk.
+    ///
+    /// The patch starts with a `p`-gon centered at the origin and grows
+    /// by `depth` rings of edge-adjacent tiles.
+    ///
+    /// Each edge is split into `edge_subdivisions + 1` segments along
+    /// its geodesic so edges render as proper arcs when drawn as
+    /// polylines.
+    ///
+    /// The number of tiles grows exponentially with `depth`, so patches
+    /// are limited to 2²⁴ corners in total, i.e. tiles times
+    /// `p (edge_subdivisions + 1)`.
+    ///
+    /// # Errors
+    /// Returns [`Error::SchlafliSymbol`] unless `(p - 2)(q - 2) > 4`,
+    /// i.e. `1/p + 1/q < 1/2`, or if `p` or `q` exceeds 1024.
+    ///
+    /// Returns [`Error::Constraint`] if `depth` exceeds 16 or the patch
+    /// would have more than 2²⁴ corners.
+    pub fn new(p: u32, q: u32, depth: u32, edge_subdivisions: u32) -> Result<Self, Error> {
+        if p < 3 || q < 3 || (p as u64 - 2) * (q as u64 - 2) <= 4 || MAX_SIDES < p.max(q) {
+            return Err(Error::SchlafliSymbol { p, q });
+        }
+        if MAX_DEPTH < depth {
+            return Err(Error::Constraint("depth ≤ 16"));
+        }
+        let tile_corners = p as u64 * (edge_subdivisions as u64 + 1);
+        let too_many = |tiles: usize| MAX_CORNERS < tiles as u64 * tile_corners;
+        if too_many(1) {
+            return Err(Error::Constraint("at most 2²⁴ corners"));
+        }
+
+        let circumradius = ((PI / p as f64).tan() * (PI / q as f64).tan())
+            .recip()
+            .acosh();
+        let center = [0.0, 0.0, 1.0];
+        let first: Vec<Hyperboloid> = (0..p)
+            .map(|i| {
+                let angle = 2.0 * PI * i as f64 / p as f64;
+                [
+                    circumradius.sinh() * angle.cos(),
+                    circumradius.sinh() * angle.sin(),
+                    circumradius.cosh(),
+                ]
+            })
+            .collect();
+
+        let mut centers = MeshBuilder::new();
+        centers.vertex([center[0], center[1]]);
+
+        let mut tiles = Vec::new();
+        let mut queue = VecDeque::new();
+        queue.push_back((first, center, 0));
+
+        while let Some((vertices, center, layer)) = queue.pop_front() {
+            if layer < depth {
+                for i in 0..vertices.len() {
+                    let n = normal(&vertices[i], &vertices[(i + 1) % vertices.len()]);
+                    let neighbor_center = reflect(&center, &n);
+
+                    let count = centers.vertex_count();
+                    if centers.vertex([neighbor_center[0], neighbor_center[1]]) as usize == count {
+                        if too_many(count + 1) {
+                            return Err(Error::Constraint("at most 2²⁴ corners"));
+                        }
+                        // Reflections flip the winding; reverse to keep it.
+                        let neighbor = vertices.iter().rev().map(|v| reflect(v, &n)).collect();
+                        queue.push_back((neighbor, neighbor_center, layer + 1));
+                    }
+                }
+            }
+            tiles.push(vertices);
+        }
+
+        // Weld on the hyperboloid, where distinct vertices never crowd
+        // together the way they do near the rim of the disk.
+        let mut builder = MeshBuilder::new();
+        for vertices in &tiles {
+            let corners: Vec<[f64; 2]> = (0..vertices.len())
+                .flat_map(|i| {
+                    let a = vertices[i];
+                    let b = vertices[(i + 1) % vertices.len()];
+                    (0..=edge_subdivisions).map(move |k| {
+                        let v = interpolate(&a, &b, k as f64 / (edge_subdivisions + 1) as f64);
+                        [v[0], v[1]]
+                    })
+                })
+                .collect();
+            builder.polygon(&corners);
+        }
+
+        let (positions, face_index) = builder.build_f64();
+        let hyperboloid: Vec<Hyperboloid> = positions
+            .into_iter()
+            .map(|[x, y]| [x, y, (1.0 + x * x + y * y).sqrt()])
+            .collect();
+
+        let model = HyperbolicModel::default();
+
+        Ok(Self {
+            name: format!("HYPERBOLIC-{}-{}", p, q),
+            points: hyperboloid.iter().map(|v| project(v, model)).collect(),
+            hyperboloid,
+            face_index,
+            model,
+            topology: Default::default(),
+            classes: Default::default(),
+        })
+    }
+
+    /// Returns the tiling re-projected into `model`.
+    pub fn with_model(mut self, model: HyperbolicModel) -> Self {
+        self.points = self.hyperboloid.iter().map(|v| project(v, model)).collect();
+        self.model = model;
+        self
+    }
+
+    /// The model [`points()`](Self::points) are given in.
+    pub fn model(&self) -> HyperbolicModel {
+        self.model
+    }
+}
+
+#[test]
+fn hyperbolic() {
+    assert_eq!(
+        HyperbolicTiling::new(6, 3, 1, 0).err(),
+        Some(Error::SchlafliSymbol { p: 6, q: 3 })
+    );
+    // Far too many sides, rings or corners to fit in memory.
+    assert_eq!(
+        HyperbolicTiling::new(u32::MAX, u32::MAX, 1, 0).err(),
+        Some(Error::SchlafliSymbol {
+            p: u32::MAX,
+            q: u32::MAX
+        })
+    );
+    assert_eq!(
+        HyperbolicTiling::new(7, 3, u32::MAX, 0).err(),
+        Some(Error::Constraint("depth ≤ 16"))
+    );
+    assert_eq!(
+        HyperbolicTiling::new(1024, 1024, 16, 0).err(),
+        Some(Error::Constraint("at most 2²⁴ corners"))
+    );
+    assert_eq!(
+        HyperbolicTiling::new(7, 3, 0, u32::MAX).err(),
+        Some(Error::Constraint("at most 2²⁴ corners"))
+    );
+    assert!(HyperbolicTiling::new(1024, 1024, 0, 0).is_ok());
+
+    let tiling = HyperbolicTiling::new(7, 3, 2, 0).unwrap();
+    // One heptagon, its 7 edge neighbours and 4 more per neighbour, of
+    // which each neighbouring pair shares one.
+    assert_eq!(tiling.faces().len(), 1 + 7 + 7 * 4 - 7);
+    assert!(tiling.points().iter().all(|p| p.mag() < 1.0));
+
+    // No vertex is shared by more than three heptagons.
+    let mut valence = vec![0; tiling.points().len()];
+    for face in tiling.faces() {
+        for &v in face {
+            valence[v as usize] += 1;
+        }
+    }
+    assert!(valence.iter().all(|&n| n <= 3));
+
+    let tiling = tiling.with_model(HyperbolicModel::UpperHalfPlane);
+    assert!(tiling.points().iter().all(|p| p.y > 0.0));
+}
+
+#[test]
+fn interpolate_far_from_the_origin() {
+    // Points this far out have coordinates around 10⁹, whose squares
+    // cancel in the Minkowski norm.
+    let point = |distance: f64, angle: f64| {
+        [
+            distance.sinh() * angle.cos(),
+            distance.sinh() * angle.sin(),
+            distance.cosh(),
+        ]
+    };
+    let (a, b) = (point(21.0, 0.3), point(21.0, 0.30001));
+
+    // Corners of a tile are its edges' start points and must not move,
+    // or tiles no longer weld.
+    assert_eq!(interpolate(&a, &b, 0.0), a);
+}
@@ -0,0 +1,1330 @@
+//! Isohedral tilings with deformable tile edges.
+//!
+//! A tile type fixes how the edges of a prototile are paired up with
+//! edges of its neighbours – by translation, by a half-turn about the
+//! edge midpoint, by a rotation about a shared corner, by a glide
+//! reflection or by a reflection in the edge itself – and which
+//! symmetries the prototile has. Any polyline put on one edge is
+//! carried over to every edge it is paired with or mapped to by a
+//! symmetry of the tile, so the tiling stays consistent no matter how
+//! the edges are deformed.
+//!
+//! The types are the 81 isohedral types of Grünbaum and Shephard, each
+//! a deformation of the tile of one of the eleven Laves tilings.
+use crate::{
+    affine::{self, Affine, DPoint},
+    builder, polygon, Error, FaceIndex, Points, Polyline,
+};
+use std::{f64::consts::PI, iter};
+
+/// How an edge is mapped onto its partner in a neighbouring tile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdgePairing {
+    /// Translated onto the edge with the given index.
+    Translation(usize),
+    /// Rotated about their shared corner onto the adjacent edge with the
+    /// given index.
+    Rotation(usize),
+    /// Rotated onto itself by a half-turn about its midpoint.
+    HalfTurn,
+    /// Glide reflected onto the edge with the given index.
+    Glide(usize),
+    /// Reflected onto itself in the line through its corners.
+    Mirror,
+}
+
+/// The shape an edge curve must have to keep the tiling consistent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdgeShape {
+    /// Any curve.
+    J,
+    /// A curve that is symmetric under a half-turn about its midpoint.
+    S,
+    /// A curve that is mirror symmetric about the perpendicular bisector
+    /// of the edge.
+    U,
+    /// A straight line, since the edge lies on a mirror axis.
+    I,
+}
+
+/// Maps `x` to `1 - x` in the frame of an edge, reversing its curve.
+const REVERSE: u8 = 1;
+/// Maps `y` to `-y` in the frame of an edge, flipping its curve over.
+const FLIP: u8 = 2;
+
+/// The isohedral tiling types, numbered after Grünbaum and Shephard.
+///
+/// The twelve numbers that are left out belong to types that only
+/// exist for marked tiles: any unmarked tile of theirs has more
+/// symmetry and thus another type.
+///
+/// Edge `i` of the prototile runs from corner `i` to corner `i + 1`.
+/// Types whose tiles have no symmetry of their own are described by
+/// their Heesch symbol: `T` edges are paired by translation, `C` edges
+/// are half-turned about their midpoint, `Cn` edge pairs are rotated
+/// about their shared corner, which is an `n`-fold centre of rotation,
+/// `G` edge pairs are glide reflected and `I` edges are mirrored in
+/// themselves.
+///
+/// The parameters are corner coordinates. Corner 0 is at the origin and
+/// corner 1 at `(1, 0)`, unless the `y` of corner 1 is a parameter; the
+/// other coordinates follow from the parameters and the type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IsohedralType {
+    /// Hexagon `TTTTTT`, group `p1`. Parameters: corners 2 and 3.
+    IH1,
+    /// Hexagon `TG1G1TG2G2`, group `pg`. Parameters: `y` of corner 1,
+    /// corner 2 and `x` of corner 5.
+    IH2,
+    /// Hexagon `TG1G2TG2G1`, group `pg`. Parameters: `y` of corner 1,
+    /// corner 2 and `y` of corner 3.
+    IH3,
+    /// Hexagon `TCCTCC`, group `p2`. Parameters: corners 2, 3 and 5.
+    IH4,
+    /// Hexagon `TCCTGG`, group `pgg`. Parameters: `y` of corner 1, corner
+    /// 2, `y` of corner 3 and `x` of corner 5.
+    IH5,
+    /// Hexagon `CG1CG2G1G2`, group `pgg`. Parameters: `y` of corner 1,
+    /// corner 2, `y` of corner 3 and `y` of corner 4.
+    IH6,
+    /// Hexagon `C3C3C3C3C3C3`, group `p3`. Parameters: corner 3.
+    IH7,
+    /// Hexagon with two-fold rotational symmetry, group `p2`. Parameters:
+    /// corners 2 and 3.
+    IH8,
+    /// Hexagon with two-fold rotational symmetry, group `pgg`. Parameters:
+    /// `y` of corner 1 and corner 2.
+    IH9,
+    /// Hexagon mirror symmetric about the line through corner 0 and corner
+    /// 3, group `p31m`. Parameters: `y` of corner 2.
+    IH10,
+    /// Hexagon mirror symmetric about the line through corner 0 and corner
+    /// 3, group `pmg`. Parameters: `y` of corner 1, `y` of corner 2 and `y`
+    /// of corner 3.
+    IH11,
+    /// Hexagon mirror symmetric about the line through corner 0 and corner
+    /// 3, group `cm`. Parameters: `y` of corner 1 and `y` of corner 2.
+    IH12,
+    /// Hexagon mirror symmetric about the line through the midpoint of edge
+    /// 0 and the midpoint of edge 3, group `pmg`. Parameters: corner 2 and
+    /// `y` of corner 3.
+    IH13,
+    /// Hexagon mirror symmetric about the line through the midpoint of edge
+    /// 0 and the midpoint of edge 3, group `cm`. Parameters: corner 2.
+    IH14,
+    /// Hexagon with three-fold rotational symmetry, group `p3`.
+    IH15,
+    /// Hexagon with two mirror axes, group `cmm`. Parameters: `y` of corner
+    /// 1 and `y` of corner 2.
+    IH16,
+    /// Hexagon with six-fold rotational symmetry, group `p6`.
+    IH17,
+    /// Hexagon with three mirror axes, group `p31m`.
+    IH18,
+    /// Hexagon with six mirror axes, group `p6m`.
+    IH20,
+    /// Pentagon `CC3C3C6C6`, group `p6`. Parameters: corner 2.
+    IH21,
+    /// Pentagon `ITGGT`, group `cm`. Parameters: corner 2 and `y` of corner
+    /// 3.
+    IH22,
+    /// Pentagon `TCTCC`, group `p2`. Parameters: corners 2 and 4.
+    IH23,
+    /// Pentagon `TCTGG`, group `pgg`. Parameters: `y` of corner 1, `y` of
+    /// corner 2 and `x` of corner 4.
+    IH24,
+    /// Pentagon `ITCCT`, group `pmg`. Parameters: corners 2 and 3.
+    IH25,
+    /// Pentagon mirror symmetric about the line through corner 0 and the
+    /// midpoint of edge 2, group `cmm`. Parameters: `y` of corner 1 and `y`
+    /// of corner 2.
+    IH26,
+    /// Pentagon `CG1G2G1G2`, group `pgg`. Parameters: `y` of corner 1, `y`
+    /// of corner 2 and `y` of corner 3.
+    IH27,
+    /// Pentagon `CC4C4C4C4`, group `p4`. Parameters: corner 2.
+    IH28,
+    /// Pentagon mirror symmetric about the line through corner 0 and the
+    /// midpoint of edge 2, group `p4g`. Parameters: `y` of corner 1.
+    IH29,
+    /// Quadrilateral `IIC3C3`, group `p31m`. Parameters: `y` of corner 2.
+    IH30,
+    /// Quadrilateral `C3C3C6C6`, group `p6`.
+    IH31,
+    /// Quadrilateral mirror symmetric about the line through corner 0 and
+    /// corner 2, group `p6m`.
+    IH32,
+    /// Quadrilateral `C3C3C3C3`, group `p3`.
+    IH33,
+    /// Quadrilateral with two-fold rotational symmetry, group `p6`.
+    IH34,
+    /// Quadrilateral mirror symmetric about the line through corner 0 and
+    /// corner 2, group `p31m`.
+    IH36,
+    /// Quadrilateral with two mirror axes, group `p6m`.
+    IH37,
+    /// Triangle `IC3C3`, group `p31m`.
+    IH38,
+    /// Triangle `CC3C3`, group `p6`.
+    IH39,
+    /// Triangle mirror symmetric about the line through corner 0 and the
+    /// midpoint of edge 1, group `p6m`.
+    IH40,
+    /// Quadrilateral `TTTT`, group `p1`. Parameters: corner 2.
+    IH41,
+    /// Quadrilateral `G1G1G2G2`, group `pg`. Parameters: `y` of corner 1
+    /// and `y` of corner 3.
+    IH42,
+    /// Quadrilateral `TGTG`, group `pg`. Parameters: `y` of corner 1 and
+    /// `y` of corner 2.
+    IH43,
+    /// Quadrilateral `IGIG`, group `cm`. Parameters: corner 2.
+    IH44,
+    /// Quadrilateral `ITIT`, group `pm`. Parameters: corner 2.
+    IH45,
+    /// Quadrilateral `CCCC`, group `p2`. Parameters: corners 2 and 3.
+    IH46,
+    /// Quadrilateral `TCTC`, group `p2`. Parameters: corner 2.
+    IH47,
+    /// Quadrilateral `CCGG`, group `pgg`. Parameters: `y` of corner 1, `y`
+    /// of corner 2 and `y` of corner 3.
+    IH49,
+    /// Quadrilateral `CGCG`, group `pgg`. Parameters: `y` of corner 1 and
+    /// corner 2.
+    IH50,
+    /// Quadrilateral `G1G2G1G2`, group `pgg`. Parameters: `y` of corner 2.
+    IH51,
+    /// Quadrilateral `ICIC`, group `pmg`. Parameters: corner 2 and `x` of
+    /// corner 3.
+    IH52,
+    /// Quadrilateral `ITCT`, group `pmg`. Parameters: corner 2.
+    IH53,
+    /// Quadrilateral `IIIC`, group `cmm`. Parameters: `y` of corner 2 and
+    /// `x` of corner 3.
+    IH54,
+    /// Quadrilateral `C4C4C4C4`, group `p4`.
+    IH55,
+    /// Quadrilateral `IIC4C4`, group `p4g`. Parameters: `y` of corner 2.
+    IH56,
+    /// Quadrilateral mirror symmetric about the line through the midpoint
+    /// of edge 0 and the midpoint of edge 2, group `cmm`. Parameters:
+    /// corner 2.
+    IH57,
+    /// Quadrilateral mirror symmetric about the line through the midpoint
+    /// of edge 0 and the midpoint of edge 2, group `pm`. Parameters: `y` of
+    /// corner 2.
+    IH58,
+    /// Quadrilateral mirror symmetric about the line through the midpoint
+    /// of edge 0 and the midpoint of edge 2, group `pmg`. Parameters: `y`
+    /// of corner 2.
+    IH59,
+    /// Quadrilateral mirror symmetric about the line through corner 0 and
+    /// corner 2, group `pmg`. Parameters: `y` of corner 1 and `y` of corner
+    /// 2.
+    IH61,
+    /// Quadrilateral mirror symmetric about the line through corner 0 and
+    /// corner 2, group `cm`. Parameters: `y` of corner 1.
+    IH62,
+    /// Quadrilateral mirror symmetric about the line through corner 0 and
+    /// corner 2, group `p4g`.
+    IH64,
+    /// Quadrilateral with two-fold rotational symmetry, group `pmg`.
+    /// Parameters: corner 2.
+    IH66,
+    /// Quadrilateral with two-fold rotational symmetry, group `p2`.
+    /// Parameters: corner 2.
+    IH67,
+    /// Quadrilateral with two-fold rotational symmetry, group `pgg`.
+    /// Parameters: `y` of corner 1.
+    IH68,
+    /// Quadrilateral with two-fold rotational symmetry, group `p4`.
+    IH69,
+    /// Quadrilateral with four-fold rotational symmetry, group `p4`.
+    IH71,
+    /// Quadrilateral with two mirror axes, group `pmm`. Parameters: `y` of
+    /// corner 2.
+    IH72,
+    /// Quadrilateral with two mirror axes, group `p4g`.
+    IH73,
+    /// Quadrilateral with two mirror axes, group `cmm`. Parameters: `y` of
+    /// corner 1.
+    IH74,
+    /// Quadrilateral with four mirror axes, group `p4m`.
+    IH76,
+    /// Triangle `III`, group `p6m`.
+    IH77,
+    /// Triangle `IIC`, group `cmm`. Parameters: `y` of corner 2.
+    IH78,
+    /// Triangle `CC4C4`, group `p4`.
+    IH79,
+    /// Triangle `IC4C4`, group `p4g`.
+    IH81,
+    /// Triangle mirror symmetric about the line through corner 0 and the
+    /// midpoint of edge 1, group `p4m`.
+    IH82,
+    /// Triangle `IGG`, group `cm`. Parameters: `y` of corner 2.
+    IH83,
+    /// Triangle `CCC`, group `p2`. Parameters: corner 2.
+    IH84,
+    /// Triangle `CGG`, group `pgg`. Parameters: `y` of corner 2.
+    IH85,
+    /// Triangle `ICC`, group `pmg`. Parameters: corner 2.
+    IH86,
+    /// Triangle `CC6C6`, group `p6`.
+    IH88,
+    /// Triangle mirror symmetric about the line through corner 0 and the
+    /// midpoint of edge 1, group `cmm`. Parameters: `y` of corner 1.
+    IH90,
+    /// Triangle with three-fold rotational symmetry, group `p6`.
+    IH91,
+    /// Triangle with three mirror axes, group `p6m`.
+    IH93,
+}
+
+/// The tile of the Laves tiling with the given corner valences, with
+/// corner 0 at the origin and corner 1 at `(1, 0)`.
+fn laves(valences: &[u32]) -> Vec<DPoint> {
+    let n = valences.len();
+    // The edges touch the unit circle. Their normals turn by the
+    // exterior angle at each corner, which has an interior angle of
+    // `360° / valence`.
+    let mut normals = vec![0.0];
+    for &valence in &valences[1..] {
+        normals.push(normals[normals.len() - 1] + PI - 2.0 * PI / valence as f64);
+    }
+    let corners: Vec<DPoint> = (0..n)
+        .map(|k| {
+            let (a, b) = (normals[(k + n - 1) % n], normals[k]);
+            let det = (b - a).sin();
+            [(b.sin() - a.sin()) / det, (a.cos() - b.cos()) / det]
+        })
+        .collect();
+    let normalize = affine::match_two(corners[0], corners[1], [0.0, 0.0], [1.0, 0.0]);
+    corners
+        .iter()
+        .map(|&c| affine::apply(&normalize, c))
+        .collect()
+}
+
+/// Adds the equations requiring the linear part of `transform` to map
+/// the vector from corner `a` to corner `b` onto the one from corner `c`
+/// to corner `d`, in a system over the coordinates of all corners.
+fn congruent(
+    rows: &mut Vec<Vec<f64>>,
+    unknowns: usize,
+    transform: &Affine,
+    [a, b]: [usize; 2],
+    [c, d]: [usize; 2],
+) {
+    for axis in 0..2 {
+        let mut row = vec![0.0; unknowns + 1];
+        for k in 0..2 {
+            row[2 * b + k] += transform[3 * axis + k];
+            row[2 * a + k] -= transform[3 * axis + k];
+        }
+        row[2 * d + axis] -= 1.0;
+        row[2 * c + axis] += 1.0;
+        rows.push(row);
+    }
+}
+
+/// Solves the linear system whose rows hold the coefficients of the
+/// unknowns followed by the right-hand side. The system must be
+/// consistent and determine every unknown.
+fn solve(mut rows: Vec<Vec<f64>>, unknowns: usize) -> Vec<f64> {
+    for column in 0..unknowns {
+        let pivot = (column..rows.len())
+            .max_by(|&a, &b| rows[a][column].abs().total_cmp(&rows[b][column].abs()))
+            .unwrap_or(column);
+        rows.swap(column, pivot);
+        let divisor = rows[column][column];
+        let pivot: Vec<f64> = rows[column].iter().map(|v| v / divisor).collect();
+        for row in &mut rows {
+            let factor = row[column];
+            for (value, p) in row.iter_mut().zip(&pivot) {
+                *value -= factor * p;
+            }
+        }
+        rows[column] = pivot;
+    }
+    rows[..unknowns].iter().map(|row| row[unknowns]).collect()
+}
+
+impl IsohedralType {
+    /// All isohedral types.
+    pub const ALL: [IsohedralType; 81] = [
+        IsohedralType::IH1,
+        IsohedralType::IH2,
+        IsohedralType::IH3,
+        IsohedralType::IH4,
+        IsohedralType::IH5,
+        IsohedralType::IH6,
+        IsohedralType::IH7,
+        IsohedralType::IH8,
+        IsohedralType::IH9,
+        IsohedralType::IH10,
+        IsohedralType::IH11,
+        IsohedralType::IH12,
+        IsohedralType::IH13,
+        IsohedralType::IH14,
+        IsohedralType::IH15,
+        IsohedralType::IH16,
+        IsohedralType::IH17,
+        IsohedralType::IH18,
+        IsohedralType::IH20,
+        IsohedralType::IH21,
+        IsohedralType::IH22,
+        IsohedralType::IH23,
+        IsohedralType::IH24,
+        IsohedralType::IH25,
+        IsohedralType::IH26,
+        IsohedralType::IH27,
+        IsohedralType::IH28,
+        IsohedralType::IH29,
+        IsohedralType::IH30,
+        IsohedralType::IH31,
+        IsohedralType::IH32,
+        IsohedralType::IH33,
+        IsohedralType::IH34,
+        IsohedralType::IH36,
+        IsohedralType::IH37,
+        IsohedralType::IH38,
+        IsohedralType::IH39,
+        IsohedralType::IH40,
+        IsohedralType::IH41,
+        IsohedralType::IH42,
+        IsohedralType::IH43,
+        IsohedralType::IH44,
+        IsohedralType::IH45,
+        IsohedralType::IH46,
+        IsohedralType::IH47,
+        IsohedralType::IH49,
+        IsohedralType::IH50,
+        IsohedralType::IH51,
+        IsohedralType::IH52,
+        IsohedralType::IH53,
+        IsohedralType::IH54,
+        IsohedralType::IH55,
+        IsohedralType::IH56,
+        IsohedralType::IH57,
+        IsohedralType::IH58,
+        IsohedralType::IH59,
+        IsohedralType::IH61,
+        IsohedralType::IH62,
+        IsohedralType::IH64,
+        IsohedralType::IH66,
+        IsohedralType::IH67,
+        IsohedralType::IH68,
+        IsohedralType::IH69,
+        IsohedralType::IH71,
+        IsohedralType::IH72,
+        IsohedralType::IH73,
+        IsohedralType::IH74,
+        IsohedralType::IH76,
+        IsohedralType::IH77,
+        IsohedralType::IH78,
+        IsohedralType::IH79,
+        IsohedralType::IH81,
+        IsohedralType::IH82,
+        IsohedralType::IH83,
+        IsohedralType::IH84,
+        IsohedralType::IH85,
+        IsohedralType::IH86,
+        IsohedralType::IH88,
+        IsohedralType::IH90,
+        IsohedralType::IH91,
+        IsohedralType::IH93,
+    ];
+
+    /// How each edge of the prototile is paired.
+    pub fn edges(self) -> &'static [EdgePairing] {
+        use EdgePairing::*;
+        match self {
+            IsohedralType::IH1 | IsohedralType::IH14 => &[
+                Translation(3),
+                Translation(4),
+                Translation(5),
+                Translation(0),
+                Translation(1),
+                Translation(2),
+            ],
+            IsohedralType::IH2 => &[
+                Translation(3),
+                Glide(2),
+                Glide(1),
+                Translation(0),
+                Glide(5),
+                Glide(4),
+            ],
+            IsohedralType::IH3 => &[
+                Translation(3),
+                Glide(5),
+                Glide(4),
+                Translation(0),
+                Glide(2),
+                Glide(1),
+            ],
+            IsohedralType::IH4 | IsohedralType::IH13 => &[
+                Translation(3),
+                HalfTurn,
+                HalfTurn,
+                Translation(0),
+                HalfTurn,
+                HalfTurn,
+            ],
+            IsohedralType::IH5 => &[
+                Translation(3),
+                HalfTurn,
+                HalfTurn,
+                Translation(0),
+                Glide(5),
+                Glide(4),
+            ],
+            IsohedralType::IH6 => &[HalfTurn, Glide(4), HalfTurn, Glide(5), Glide(1), Glide(3)],
+            IsohedralType::IH7 => &[
+                Rotation(1),
+                Rotation(0),
+                Rotation(3),
+                Rotation(2),
+                Rotation(5),
+                Rotation(4),
+            ],
+            IsohedralType::IH8
+            | IsohedralType::IH16
+            | IsohedralType::IH17
+            | IsohedralType::IH20 => &[HalfTurn, HalfTurn, HalfTurn, HalfTurn, HalfTurn, HalfTurn],
+            IsohedralType::IH9 => &[HalfTurn, Glide(2), Glide(1), HalfTurn, Glide(2), Glide(1)],
+            IsohedralType::IH10 => &[
+                Mirror,
+                Rotation(2),
+                Rotation(1),
+                Rotation(4),
+                Rotation(3),
+                Mirror,
+            ],
+            IsohedralType::IH11 => &[HalfTurn, Mirror, HalfTurn, HalfTurn, Mirror, HalfTurn],
+            IsohedralType::IH12 => &[
+                Translation(3),
+                Mirror,
+                Translation(5),
+                Translation(0),
+                Mirror,
+                Translation(2),
+            ],
+            IsohedralType::IH15 | IsohedralType::IH18 => &[
+                Rotation(1),
+                Rotation(0),
+                Rotation(1),
+                Translation(0),
+                Translation(1),
+                Rotation(0),
+            ],
+            IsohedralType::IH21 | IsohedralType::IH28 => {
+                &[HalfTurn, Rotation(2), Rotation(1), Rotation(4), Rotation(3)]
+            }
+            IsohedralType::IH22 => &[Mirror, Translation(4), Glide(3), Glide(2), Translation(1)],
+            IsohedralType::IH23 => &[Translation(2), HalfTurn, Translation(0), HalfTurn, HalfTurn],
+            IsohedralType::IH24 => &[Translation(2), HalfTurn, Translation(0), Glide(4), Glide(3)],
+            IsohedralType::IH25 => &[Mirror, Translation(4), HalfTurn, HalfTurn, Translation(1)],
+            IsohedralType::IH26 => &[HalfTurn, Mirror, HalfTurn, Mirror, HalfTurn],
+            IsohedralType::IH27 => &[HalfTurn, Glide(3), Glide(4), Glide(1), Glide(2)],
+            IsohedralType::IH29 => &[Rotation(1), Rotation(0), HalfTurn, Rotation(4), Rotation(3)],
+            IsohedralType::IH30 | IsohedralType::IH56 => {
+                &[Mirror, Mirror, Rotation(3), Rotation(2)]
+            }
+            IsohedralType::IH31
+            | IsohedralType::IH33
+            | IsohedralType::IH36
+            | IsohedralType::IH55
+            | IsohedralType::IH64 => &[Rotation(1), Rotation(0), Rotation(3), Rotation(2)],
+            IsohedralType::IH32 | IsohedralType::IH37 => &[Mirror, Mirror, Mirror, Mirror],
+            IsohedralType::IH34 | IsohedralType::IH69 | IsohedralType::IH73 => {
+                &[Rotation(1), Rotation(0), Rotation(1), Rotation(0)]
+            }
+            IsohedralType::IH38 | IsohedralType::IH81 => &[Mirror, Rotation(2), Rotation(1)],
+            IsohedralType::IH39 | IsohedralType::IH79 | IsohedralType::IH88 => {
+                &[HalfTurn, Rotation(2), Rotation(1)]
+            }
+            IsohedralType::IH40 | IsohedralType::IH82 => &[Mirror, HalfTurn, Mirror],
+            IsohedralType::IH41 | IsohedralType::IH62 => &[
+                Translation(2),
+                Translation(3),
+                Translation(0),
+                Translation(1),
+            ],
+            IsohedralType::IH42 => &[Glide(1), Glide(0), Glide(3), Glide(2)],
+            IsohedralType::IH43 => &[Translation(2), Glide(3), Translation(0), Glide(1)],
+            IsohedralType::IH44 => &[Mirror, Glide(3), Mirror, Glide(1)],
+            IsohedralType::IH45 => &[Mirror, Translation(3), Mirror, Translation(1)],
+            IsohedralType::IH46
+            | IsohedralType::IH57
+            | IsohedralType::IH61
+            | IsohedralType::IH67
+            | IsohedralType::IH71
+            | IsohedralType::IH72
+            | IsohedralType::IH74
+            | IsohedralType::IH76 => &[HalfTurn, HalfTurn, HalfTurn, HalfTurn],
+            IsohedralType::IH47 | IsohedralType::IH59 => {
+                &[Translation(2), HalfTurn, Translation(0), HalfTurn]
+            }
+            IsohedralType::IH49 => &[HalfTurn, HalfTurn, Glide(3), Glide(2)],
+            IsohedralType::IH50 => &[HalfTurn, Glide(3), HalfTurn, Glide(1)],
+            IsohedralType::IH51 => &[Glide(2), Glide(3), Glide(0), Glide(1)],
+            IsohedralType::IH52 | IsohedralType::IH66 => &[Mirror, HalfTurn, Mirror, HalfTurn],
+            IsohedralType::IH53 => &[Mirror, Translation(3), HalfTurn, Translation(1)],
+            IsohedralType::IH54 => &[Mirror, Mirror, Mirror, HalfTurn],
+            IsohedralType::IH58 => &[Translation(2), Mirror, Translation(0), Mirror],
+            IsohedralType::IH68 => &[Glide(1), Glide(0), Glide(1), Glide(0)],
+            IsohedralType::IH77 => &[Mirror, Mirror, Mirror],
+            IsohedralType::IH78 => &[Mirror, Mirror, HalfTurn],
+            IsohedralType::IH83 => &[Mirror, Glide(2), Glide(1)],
+            IsohedralType::IH84
+            | IsohedralType::IH90
+            | IsohedralType::IH91
+            | IsohedralType::IH93 => &[HalfTurn, HalfTurn, HalfTurn],
+            IsohedralType::IH85 => &[HalfTurn, Glide(2), Glide(1)],
+            IsohedralType::IH86 => &[Mirror, HalfTurn, HalfTurn],
+        }
+    }
+
+    /// The number of free parameters of the prototile.
+    pub fn parameter_count(self) -> usize {
+        self.default_parameters().len()
+    }
+
+    /// Parameters that give the tile of the type's Laves tiling, e.g. a
+    /// regular hexagon or a square.
+    pub fn default_parameters(self) -> &'static [f32] {
+        match self {
+            IsohedralType::IH1 | IsohedralType::IH8 => &[1.5, 0.866, 1.0, 1.732],
+            IsohedralType::IH2 => &[0.0, 1.5, 0.866, -0.5],
+            IsohedralType::IH3 => &[0.0, 1.5, 0.866, 1.732],
+            IsohedralType::IH4 => &[1.5, 0.866, 1.0, 1.732, -0.5, 0.866],
+            IsohedralType::IH5 => &[0.0, 1.5, 0.866, 1.732, -0.5],
+            IsohedralType::IH6 => &[0.0, 1.5, 0.866, 1.732, 1.732],
+            IsohedralType::IH7 => &[1.0, 1.732],
+            IsohedralType::IH9 => &[0.0, 1.5, 0.866],
+            IsohedralType::IH10
+            | IsohedralType::IH30
+            | IsohedralType::IH83
+            | IsohedralType::IH85 => &[0.866],
+            IsohedralType::IH11 => &[0.0, 0.866, 1.732],
+            IsohedralType::IH12 | IsohedralType::IH16 => &[0.0, 0.866],
+            IsohedralType::IH13 => &[1.5, 0.866, 1.732],
+            IsohedralType::IH14 | IsohedralType::IH21 => &[1.5, 0.866],
+            IsohedralType::IH15
+            | IsohedralType::IH17
+            | IsohedralType::IH18
+            | IsohedralType::IH20
+            | IsohedralType::IH31
+            | IsohedralType::IH32
+            | IsohedralType::IH33
+            | IsohedralType::IH34
+            | IsohedralType::IH36
+            | IsohedralType::IH37
+            | IsohedralType::IH38
+            | IsohedralType::IH39
+            | IsohedralType::IH40
+            | IsohedralType::IH55
+            | IsohedralType::IH64
+            | IsohedralType::IH69
+            | IsohedralType::IH71
+            | IsohedralType::IH73
+            | IsohedralType::IH76
+            | IsohedralType::IH77
+            | IsohedralType::IH79
+            | IsohedralType::IH81
+            | IsohedralType::IH82
+            | IsohedralType::IH88
+            | IsohedralType::IH91
+            | IsohedralType::IH93 => &[],
+            IsohedralType::IH22 => &[1.0, 0.789, 1.077],
+            IsohedralType::IH23 => &[1.0, 1.268, -0.366, 0.634],
+            IsohedralType::IH24 => &[0.0, 1.268, -0.366],
+            IsohedralType::IH25 => &[1.0, 0.789, 0.5, 1.077],
+            IsohedralType::IH26 => &[0.0, 1.183],
+            IsohedralType::IH27 => &[0.0, 1.183, 1.866],
+            IsohedralType::IH28 => &[1.683, 1.183],
+            IsohedralType::IH29
+            | IsohedralType::IH62
+            | IsohedralType::IH68
+            | IsohedralType::IH74
+            | IsohedralType::IH90 => &[0.0],
+            IsohedralType::IH41
+            | IsohedralType::IH44
+            | IsohedralType::IH45
+            | IsohedralType::IH47
+            | IsohedralType::IH53
+            | IsohedralType::IH57
+            | IsohedralType::IH66
+            | IsohedralType::IH67 => &[1.0, 1.0],
+            IsohedralType::IH42 | IsohedralType::IH43 | IsohedralType::IH61 => &[0.0, 1.0],
+            IsohedralType::IH46 => &[1.0, 1.0, 0.0, 1.0],
+            IsohedralType::IH49 | IsohedralType::IH50 => &[0.0, 1.0, 1.0],
+            IsohedralType::IH51
+            | IsohedralType::IH56
+            | IsohedralType::IH58
+            | IsohedralType::IH59
+            | IsohedralType::IH72
+            | IsohedralType::IH78 => &[1.0],
+            IsohedralType::IH52 => &[1.0, 1.0, 0.0],
+            IsohedralType::IH54 => &[1.0, 0.0],
+            IsohedralType::IH84 | IsohedralType::IH86 => &[0.5, 0.866],
+        }
+    }
+
+    /// The number of tiles meeting at each corner of the Laves tiling.
+    fn valences(self) -> &'static [u32] {
+        match self {
+            IsohedralType::IH1
+            | IsohedralType::IH2
+            | IsohedralType::IH3
+            | IsohedralType::IH4
+            | IsohedralType::IH5
+            | IsohedralType::IH6
+            | IsohedralType::IH7
+            | IsohedralType::IH8
+            | IsohedralType::IH9
+            | IsohedralType::IH10
+            | IsohedralType::IH11
+            | IsohedralType::IH12
+            | IsohedralType::IH13
+            | IsohedralType::IH14
+            | IsohedralType::IH15
+            | IsohedralType::IH16
+            | IsohedralType::IH17
+            | IsohedralType::IH18
+            | IsohedralType::IH20 => &[3, 3, 3, 3, 3, 3],
+            IsohedralType::IH21 => &[3, 3, 3, 3, 6],
+            IsohedralType::IH22 | IsohedralType::IH25 => &[4, 4, 3, 3, 3],
+            IsohedralType::IH23 | IsohedralType::IH24 => &[3, 4, 4, 3, 3],
+            IsohedralType::IH26 => &[3, 3, 4, 4, 3],
+            IsohedralType::IH27 | IsohedralType::IH28 => &[3, 3, 4, 3, 4],
+            IsohedralType::IH29 => &[3, 4, 3, 3, 4],
+            IsohedralType::IH30 => &[4, 6, 4, 3],
+            IsohedralType::IH31 => &[4, 3, 4, 6],
+            IsohedralType::IH32 => &[3, 4, 6, 4],
+            IsohedralType::IH33 | IsohedralType::IH36 => &[6, 3, 6, 3],
+            IsohedralType::IH34 | IsohedralType::IH37 => &[3, 6, 3, 6],
+            IsohedralType::IH38 | IsohedralType::IH39 => &[12, 12, 3],
+            IsohedralType::IH40 => &[3, 12, 12],
+            IsohedralType::IH41
+            | IsohedralType::IH42
+            | IsohedralType::IH43
+            | IsohedralType::IH44
+            | IsohedralType::IH45
+            | IsohedralType::IH46
+            | IsohedralType::IH47
+            | IsohedralType::IH49
+            | IsohedralType::IH50
+            | IsohedralType::IH51
+            | IsohedralType::IH52
+            | IsohedralType::IH53
+            | IsohedralType::IH54
+            | IsohedralType::IH55
+            | IsohedralType::IH56
+            | IsohedralType::IH57
+            | IsohedralType::IH58
+            | IsohedralType::IH59
+            | IsohedralType::IH61
+            | IsohedralType::IH62
+            | IsohedralType::IH64
+            | IsohedralType::IH66
+            | IsohedralType::IH67
+            | IsohedralType::IH68
+            | IsohedralType::IH69
+            | IsohedralType::IH71
+            | IsohedralType::IH72
+            | IsohedralType::IH73
+            | IsohedralType::IH74
+            | IsohedralType::IH76 => &[4, 4, 4, 4],
+            IsohedralType::IH77 => &[4, 6, 12],
+            IsohedralType::IH78 => &[8, 4, 8],
+            IsohedralType::IH79 | IsohedralType::IH81 => &[8, 8, 4],
+            IsohedralType::IH82 => &[4, 8, 8],
+            IsohedralType::IH83
+            | IsohedralType::IH84
+            | IsohedralType::IH85
+            | IsohedralType::IH86
+            | IsohedralType::IH88
+            | IsohedralType::IH90
+            | IsohedralType::IH91
+            | IsohedralType::IH93 => &[6, 6, 6],
+        }
+    }
+
+    /// The order of the tile's rotational symmetry and, if it is mirror
+    /// symmetric, the `m` of its mirror mapping corner `k` to corner
+    /// `m - k`.
+    fn symmetry(self) -> (usize, Option<usize>) {
+        match self {
+            IsohedralType::IH1
+            | IsohedralType::IH2
+            | IsohedralType::IH3
+            | IsohedralType::IH4
+            | IsohedralType::IH5
+            | IsohedralType::IH6
+            | IsohedralType::IH7
+            | IsohedralType::IH21
+            | IsohedralType::IH22
+            | IsohedralType::IH23
+            | IsohedralType::IH24
+            | IsohedralType::IH25
+            | IsohedralType::IH27
+            | IsohedralType::IH28
+            | IsohedralType::IH30
+            | IsohedralType::IH31
+            | IsohedralType::IH33
+            | IsohedralType::IH38
+            | IsohedralType::IH39
+            | IsohedralType::IH41
+            | IsohedralType::IH42
+            | IsohedralType::IH43
+            | IsohedralType::IH44
+            | IsohedralType::IH45
+            | IsohedralType::IH46
+            | IsohedralType::IH47
+            | IsohedralType::IH49
+            | IsohedralType::IH50
+            | IsohedralType::IH51
+            | IsohedralType::IH52
+            | IsohedralType::IH53
+            | IsohedralType::IH54
+            | IsohedralType::IH55
+            | IsohedralType::IH56
+            | IsohedralType::IH77
+            | IsohedralType::IH78
+            | IsohedralType::IH79
+            | IsohedralType::IH81
+            | IsohedralType::IH83
+            | IsohedralType::IH84
+            | IsohedralType::IH85
+            | IsohedralType::IH86
+            | IsohedralType::IH88 => (1, None),
+            IsohedralType::IH8
+            | IsohedralType::IH9
+            | IsohedralType::IH34
+            | IsohedralType::IH66
+            | IsohedralType::IH67
+            | IsohedralType::IH68
+            | IsohedralType::IH69 => (2, None),
+            IsohedralType::IH10
+            | IsohedralType::IH11
+            | IsohedralType::IH12
+            | IsohedralType::IH26
+            | IsohedralType::IH29
+            | IsohedralType::IH32
+            | IsohedralType::IH36
+            | IsohedralType::IH40
+            | IsohedralType::IH61
+            | IsohedralType::IH62
+            | IsohedralType::IH64
+            | IsohedralType::IH82
+            | IsohedralType::IH90 => (1, Some(0)),
+            IsohedralType::IH13
+            | IsohedralType::IH14
+            | IsohedralType::IH57
+            | IsohedralType::IH58
+            | IsohedralType::IH59 => (1, Some(1)),
+            IsohedralType::IH15 | IsohedralType::IH91 => (3, None),
+            IsohedralType::IH16 | IsohedralType::IH37 | IsohedralType::IH74 => (2, Some(0)),
+            IsohedralType::IH17 => (6, None),
+            IsohedralType::IH18 => (3, Some(1)),
+            IsohedralType::IH20 => (6, Some(0)),
+            IsohedralType::IH71 => (4, None),
+            IsohedralType::IH72 | IsohedralType::IH73 => (2, Some(1)),
+            IsohedralType::IH76 => (4, Some(0)),
+            IsohedralType::IH93 => (3, Some(0)),
+        }
+    }
+
+    /// The corner coordinates set by the parameters, `2 k` for the `x`
+    /// and `2 k + 1` for the `y` of corner `k`.
+    fn parameter_coordinates(self) -> &'static [usize] {
+        match self {
+            IsohedralType::IH1 | IsohedralType::IH8 | IsohedralType::IH25 | IsohedralType::IH46 => {
+                &[4, 5, 6, 7]
+            }
+            IsohedralType::IH2 => &[3, 4, 5, 10],
+            IsohedralType::IH3 => &[3, 4, 5, 7],
+            IsohedralType::IH4 => &[4, 5, 6, 7, 10, 11],
+            IsohedralType::IH5 => &[3, 4, 5, 7, 10],
+            IsohedralType::IH6 => &[3, 4, 5, 7, 9],
+            IsohedralType::IH7 => &[6, 7],
+            IsohedralType::IH9 | IsohedralType::IH50 => &[3, 4, 5],
+            IsohedralType::IH10
+            | IsohedralType::IH30
+            | IsohedralType::IH51
+            | IsohedralType::IH56
+            | IsohedralType::IH58
+            | IsohedralType::IH59
+            | IsohedralType::IH72
+            | IsohedralType::IH78
+            | IsohedralType::IH83
+            | IsohedralType::IH85 => &[5],
+            IsohedralType::IH11 | IsohedralType::IH27 | IsohedralType::IH49 => &[3, 5, 7],
+            IsohedralType::IH12
+            | IsohedralType::IH16
+            | IsohedralType::IH26
+            | IsohedralType::IH43
+            | IsohedralType::IH61 => &[3, 5],
+            IsohedralType::IH13 | IsohedralType::IH22 => &[4, 5, 7],
+            IsohedralType::IH14
+            | IsohedralType::IH21
+            | IsohedralType::IH28
+            | IsohedralType::IH41
+            | IsohedralType::IH44
+            | IsohedralType::IH45
+            | IsohedralType::IH47
+            | IsohedralType::IH53
+            | IsohedralType::IH57
+            | IsohedralType::IH66
+            | IsohedralType::IH67
+            | IsohedralType::IH84
+            | IsohedralType::IH86 => &[4, 5],
+            IsohedralType::IH15
+            | IsohedralType::IH17
+            | IsohedralType::IH18
+            | IsohedralType::IH20
+            | IsohedralType::IH31
+            | IsohedralType::IH32
+            | IsohedralType::IH33
+            | IsohedralType::IH34
+            | IsohedralType::IH36
+            | IsohedralType::IH37
+            | IsohedralType::IH38
+            | IsohedralType::IH39
+            | IsohedralType::IH40
+            | IsohedralType::IH55
+            | IsohedralType::IH64
+            | IsohedralType::IH69
+            | IsohedralType::IH71
+            | IsohedralType::IH73
+            | IsohedralType::IH76
+            | IsohedralType::IH77
+            | IsohedralType::IH79
+            | IsohedralType::IH81
+            | IsohedralType::IH82
+            | IsohedralType::IH88
+            | IsohedralType::IH91
+            | IsohedralType::IH93 => &[],
+            IsohedralType::IH23 => &[4, 5, 8, 9],
+            IsohedralType::IH24 => &[3, 5, 8],
+            IsohedralType::IH29
+            | IsohedralType::IH62
+            | IsohedralType::IH68
+            | IsohedralType::IH74
+            | IsohedralType::IH90 => &[3],
+            IsohedralType::IH42 => &[3, 7],
+            IsohedralType::IH52 => &[4, 5, 6],
+            IsohedralType::IH54 => &[5, 6],
+        }
+    }
+
+    /// The corners of the prototile.
+    ///
+    /// Every edge pairing and symmetry keeps the linear part it has on
+    /// the Laves tile, which makes the corners a linear function of the
+    /// parameters.
+    fn corners(self, parameters: &[f64]) -> Vec<DPoint> {
+        let laves = laves(self.valences());
+        let edges = self.edges();
+        let (order, mirror) = self.symmetry();
+        let n = laves.len();
+        let unknowns = 2 * n;
+        let mut rows = Vec::new();
+
+        for i in 0..n {
+            let (j, reflected) = partner(edges[i], i);
+            let ends = if reflected {
+                [i, (i + 1) % n]
+            } else {
+                [(i + 1) % n, i]
+            };
+            congruent(
+                &mut rows,
+                unknowns,
+                &neighbor(&laves, edges, i),
+                [j, (j + 1) % n],
+                ends,
+            );
+        }
+        if order > 1 {
+            let step = n / order;
+            let rotation = affine::match_two(laves[0], laves[1], laves[step], laves[step + 1]);
+            for k in 0..n {
+                congruent(
+                    &mut rows,
+                    unknowns,
+                    &rotation,
+                    [0, k],
+                    [step, (k + step) % n],
+                );
+            }
+        }
+        if let Some(m) = mirror {
+            let reflection =
+                affine::match_two_reflected(laves[0], laves[1], laves[m], laves[(m + n - 1) % n]);
+            for k in 0..n {
+                congruent(
+                    &mut rows,
+                    unknowns,
+                    &reflection,
+                    [0, k],
+                    [m, (m + n - k) % n],
+                );
+            }
+        }
+
+        // Reflections fix the direction of their axes, so in tilings with
+        // mirror images edge 0 may turn instead.
+        let reflective = mirror.is_some() || edges.iter().any(|&e| partner(e, 0).1);
+        let mut fixed = vec![(0, 0.0), (1, 0.0), (2, 1.0)];
+        if !reflective {
+            fixed.push((3, 0.0));
+        }
+        fixed.extend(
+            self.parameter_coordinates()
+                .iter()
+                .copied()
+                .zip(parameters.iter().copied()),
+        );
+        for (coordinate, value) in fixed {
+            let mut row = vec![0.0; unknowns + 1];
+            row[coordinate] = 1.0;
+            row[unknowns] = value;
+            rows.push(row);
+        }
+
+        let solution = solve(rows, unknowns);
+        (0..n)
+            .map(|k| [solution[2 * k], solution[2 * k + 1]])
+            .collect()
+    }
+
+    /// For every edge, the index of the edge curve it carries and the
+    /// combination of [`REVERSE`] and [`FLIP`] that maps the curve onto
+    /// it, followed by the shape of every edge curve.
+    fn edge_classes(self) -> (Vec<(usize, u8)>, Vec<EdgeShape>) {
+        let edges = self.edges();
+        let (order, mirror) = self.symmetry();
+        let n = edges.len();
+
+        // Edges whose curves are images of each other, and how.
+        let mut links = vec![Vec::new(); n];
+        let mut link = |a: usize, b: usize, transform: u8| {
+            links[a].push((b, transform));
+            links[b].push((a, transform));
+        };
+        for (i, &pairing) in edges.iter().enumerate() {
+            // The neighbour sees the shared edge from its other side, and
+            // unless it is mirrored, also in the opposite direction.
+            let (j, reflected) = partner(pairing, i);
+            link(i, j, if reflected { FLIP } else { REVERSE | FLIP });
+        }
+        for i in 0..n {
+            link(i, (i + n / order) % n, 0);
+            if let Some(m) = mirror {
+                link(i, (m + 2 * n - i - 1) % n, REVERSE);
+            }
+        }
+
+        let mut classes: Vec<Option<(usize, u8)>> = vec![None; n];
+        let mut shapes = Vec::new();
+        for start in 0..n {
+            if classes[start].is_some() {
+                continue;
+            }
+            let class = shapes.len();
+            classes[start] = Some((class, 0));
+
+            // The transforms the curve must be symmetric under, as bits.
+            let mut symmetries = 1u8;
+            let mut stack = vec![start];
+            while let Some(i) = stack.pop() {
+                let transform = classes[i].map_or(0, |(_, t)| t);
+                for &(j, step) in &links[i] {
+                    match classes[j] {
+                        Some((_, other)) => symmetries |= 1 << (transform ^ step ^ other),
+                        None => {
+                            classes[j] = Some((class, transform ^ step));
+                            stack.push(j);
+                        }
+                    }
+                }
+            }
+
+            let symmetric = |t: u8| symmetries & 1 << t != 0;
+            shapes.push(
+                if symmetric(FLIP) || symmetric(REVERSE) && symmetric(REVERSE | FLIP) {
+                    EdgeShape::I
+                } else if symmetric(REVERSE | FLIP) {
+                    EdgeShape::S
+                } else if symmetric(REVERSE) {
+                    EdgeShape::U
+                } else {
+                    EdgeShape::J
+                },
+            );
+        }
+
+        (classes.into_iter().flatten().collect(), shapes)
+    }
+
+    /// The number of distinct edge curves.
+    pub fn edge_curve_count(self) -> usize {
+        self.edge_classes().1.len()
+    }
+
+    /// The shape each edge curve must have.
+    pub fn edge_shapes(self) -> Vec<EdgeShape> {
+        self.edge_classes().1
+    }
+
+    fn name(self) -> String {
+        format!("ISOHEDRAL-{:?}", self)
+    }
+}
+
+/// The edge that edge `i` is paired with and whether the pairing
+/// mirrors.
+fn partner(pairing: EdgePairing, i: usize) -> (usize, bool) {
+    match pairing {
+        EdgePairing::Translation(j) | EdgePairing::Rotation(j) => (j, false),
+        EdgePairing::HalfTurn => (i, false),
+        EdgePairing::Glide(j) => (j, true),
+        EdgePairing::Mirror => (i, true),
+    }
+}
+
+/// The transform taking a tile to its neighbour across edge `i`.
+pub(crate) fn neighbor(corners: &[DPoint], edges: &[EdgePairing], i: usize) -> Affine {
+    let n = corners.len();
+    let (j, reflected) = partner(edges[i], i);
+    let (p1, q1) = (corners[j], corners[(j + 1) % n]);
+    let (p2, q2) = (corners[i], corners[(i + 1) % n]);
+    // Mirroring flips sides, so mirrored edges keep their direction.
+    if reflected {
+        affine::match_two_reflected(p1, q1, p2, q2)
+    } else {
+        affine::match_two(p1, q1, q2, p2)
+    }
+}
+
+/// Corners of the deformed prototile: every edge contributes its start
+/// corner followed by the interior points of its curve.
+fn outline(
+    corners: &[DPoint],
+    classes: &[(usize, u8)],
+    shapes: &[EdgeShape],
+    curves: &[Polyline],
+) -> Vec<DPoint> {
+    let n = corners.len();
+
+    // Complete the first half of symmetric curves and straighten `I`
+    // edges.
+    let curves: Vec<Vec<DPoint>> = shapes
+        .iter()
+        .enumerate()
+        .map(|(class, shape)| {
+            let curve: Vec<DPoint> = curves
+                .get(class)
+                .map(|curve| curve.iter().map(|p| [p.x as f64, p.y as f64]).collect())
+                .unwrap_or_default();
+            let half = || curve.iter().copied().filter(|p| p[0] < 0.5);
+            match shape {
+                EdgeShape::J => curve,
+                EdgeShape::S => half()
+                    .chain(iter::once([0.5, 0.0]))
+                    .chain(half().rev().map(|p| [1.0 - p[0], -p[1]]))
+                    .collect(),
+                EdgeShape::U => half()
+                    .chain(half().rev().map(|p| [1.0 - p[0], p[1]]))
+                    .collect(),
+                EdgeShape::I => Vec::new(),
+            }
+        })
+        .collect();
+
+    (0..n)
+        .flat_map(|i| {
+            let (class, transform) = classes[i];
+            let frame = affine::match_two([0.0, 0.0], [1.0, 0.0], corners[i], corners[(i + 1) % n]);
+            let mut curve: Vec<DPoint> = curves[class]
+                .iter()
+                .map(|&[x, y]| {
+                    let x = if transform & REVERSE != 0 { 1.0 - x } else { x };
+                    let y = if transform & FLIP != 0 { -y } else { y };
+                    affine::apply(&frame, [x, y])
+                })
+                .collect();
+            if transform & REVERSE != 0 {
+                curve.reverse();
+            }
+            iter::once(corners[i]).chain(curve)
+        })
+        .collect()
+}
+
+/// A patch of an isohedral tiling: congruent copies of one prototile
+/// whose edges may be replaced by arbitrary curves.
+pub struct IsohedralTiling {
+    face_index: FaceIndex,
+    points: Points,
+    reflected: Vec<bool>,
+    name: String,
+    topology: crate::topology::TopologyCache,
+    classes: crate::class::ClassCache,
+}
+
+impl IsohedralTiling {
+    default_methods! {}
+
+    /// Creates a patch of tiles of type `kind` covering the rectangle
+    /// from the origin to `(width, height)`.
+    ///
+    /// The prototile's corners are placed by `parameters`, see
+    /// [`IsohedralType::default_parameters()`].
+    ///
+    /// `edge_curves` holds one polyline per edge curve, see
+    /// [`IsohedralType::edge_shapes()`], given in a frame where the edge
+    /// runs from `(0, 0)` to `(1, 0)` and without these two end points.
+    /// Pass an empty slice to keep all edges straight. Only the part with
+    /// `x < 0.5` of `S` and `U` curves is used; the rest is completed to
+    /// the symmetric shape. `I` edges stay straight.
+    ///
+    /// # Errors
+    /// Returns [`Error::ParameterCount`] if the number of `parameters`
+    /// or `edge_curves` does not match `kind` and
+    /// [`Error::DegenerateTile`] if the resulting tile is not a simple,
+    /// counter-clockwise polygon.
+    pub fn new(
+        kind: IsohedralType,
+        parameters: &[f32],
+        edge_curves: &[Polyline],
+        width: f32,
+        height: f32,
+    ) -> Result<Self, Error> {
+        if parameters.len() != kind.parameter_count() {
+            return Err(Error::ParameterCount {
+                expected: kind.parameter_count(),
+                found: parameters.len(),
+            });
+        }
+        let (classes, shapes) = kind.edge_classes();
+        if !edge_curves.is_empty() && edge_curves.len() != shapes.len() {
+            return Err(Error::ParameterCount {
+                expected: shapes.len(),
+                found: edge_curves.len(),
+            });
+        }
+
+        let parameters: Vec<f64> = parameters.iter().map(|&p| p as f64).collect();
+        let corners = kind.corners(&parameters);
+        let tile = outline(&corners, &classes, &shapes, edge_curves);
+
+        if !polygon::is_simple(&corners) || !polygon::is_simple(&tile) {
+            return Err(Error::DegenerateTile);
+        }
+
+        let edges = kind.edges();
+        let neighbors: Vec<Affine> = (0..corners.len())
+            .map(|i| neighbor(&corners, edges, i))
+            .collect();
+        let (builder, mut reflected) = builder::grow_patch(
+            &tile,
+            polygon::centroid(&corners),
+            &neighbors,
+            width as _,
+            height as _,
+        );
+        // The mirror image of a mirror symmetric tile is the tile itself.
+        if kind.symmetry().1.is_some() {
+            reflected.iter_mut().for_each(|r| *r = false);
+        }
+
+        let (points, face_index) = builder.build();
+
+        Ok(Self {
+            face_index,
+            points,
+            classes: crate::class::mirrored(&reflected),
+            reflected,
+            name: kind.name(),
+            topology: Default::default(),
+        })
+    }
+
+    /// Per-face flags telling which tiles are mirror images of the
+    /// prototile.
+    pub fn reflected(&self) -> &[bool] {
+        &self.reflected
+    }
+}
+
+#[test]
+fn isohedral() {
+    use crate::Point;
+
+    for &kind in &IsohedralType::ALL {
+        let curves: Vec<Polyline> = (0..kind.edge_curve_count())
+            .map(|_| vec![Point::new(0.25, 0.1), Point::new(0.6, -0.15)])
+            .collect();
+        // The Laves tile and a less regular one.
+        let skewed: Vec<f32> = kind
+            .default_parameters()
+            .iter()
+            .enumerate()
+            .map(|(i, p)| p + if i % 2 == 0 { 0.06 } else { -0.04 })
+            .collect();
+
+        for parameters in &[kind.default_parameters(), &skewed] {
+            let corners = kind.corners(&parameters.iter().map(|&p| p as f64).collect::<Vec<_>>());
+            if !kind.parameter_coordinates().contains(&3) {
+                assert!(corners[1][1].abs() < 1.0e-9, "{:?}", kind);
+            }
+
+            let tiling = IsohedralTiling::new(kind, parameters, &curves, 8.0, 8.0)
+                .unwrap_or_else(|e| panic!("{:?} {:?}: {:?}", kind, parameters, e));
+
+            // Deformed edges still line up: every directed edge occurs
+            // once and the patch is a disk.
+            let boundary = polygon::boundary_edges(tiling.faces()).len();
+            let directed: usize = tiling.faces().iter().map(|face| face.len()).sum();
+            assert_eq!(
+                tiling.points().len() + tiling.faces().len(),
+                (directed + boundary) / 2 + 1,
+                "{:?}",
+                kind
+            );
+
+            // Every point well inside the patch is covered exactly once.
+            let polygons = polygon::face_polygons(tiling.points(), tiling.faces());
+            polygon::covered_once(&polygons, [3.0, 3.0], 2.0, 10)
+                .unwrap_or_else(|p| panic!("{:?} {:?} at {:?}", kind, parameters, p));
+
+            let mirrors = kind
+                .edges()
+                .iter()
+                .any(|&e| matches!(e, EdgePairing::Glide(_) | EdgePairing::Mirror));
+            assert_eq!(
+                tiling.reflected().iter().any(|&r| r),
+                mirrors && kind.symmetry().1.is_none(),
+                "{:?}",
+                kind
+            );
+        }
+    }
+
+    use EdgeShape::*;
+    assert_eq!(IsohedralType::IH1.edge_shapes(), [J, J, J]);
+    assert_eq!(IsohedralType::IH13.edge_shapes(), [U, S, S]);
+    assert_eq!(IsohedralType::IH18.edge_shapes(), [U]);
+    assert_eq!(IsohedralType::IH20.edge_shapes(), [I]);
+    assert_eq!(IsohedralType::IH45.edge_shapes(), [I, J, I]);
+
+    assert_eq!(
+        IsohedralTiling::new(IsohedralType::IH84, &[0.5, -0.5], &[], 4.0, 4.0).err(),
+        Some(Error::DegenerateTile)
+    );
+}
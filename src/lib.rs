@@ -12,9 +12,51 @@
 //! may contain more than one type of polygon, but each vertex will
 //! look identical (up to rotation).
 //!
+//! The [`AperiodicTiling`] constructors create patches of the *hat* and
+//! *spectre* monotile tilings, which never repeat periodically.
+//!
+//! [`HyperbolicTiling`] covers the regular tilings `{p, q}` of the
+//! hyperbolic plane, i.e. those with `1/p + 1/q < 1/2`.
+//! [`SphericalTiling`] covers their spherical counterparts, the
+//! Platonic and Archimedean solids.
+//!
+//! [`IsohedralTiling`] creates Escher-style tilings of any of the 81
+//! [`IsohedralType`]s from a prototile whose edges can be replaced by
+//! arbitrary curves and
+//! [`PentagonalTiling`] the tilings of all 15 [`PentagonalType`]s of
+//! convex pentagons, e.g. the Cairo tiling.
+//!
+//! [`PaverTiling`] lays out running bond, herringbone, basketweave and
+//! other paving patterns that are not edge-to-edge.
+//!
+//! A [`Mesh`] holds any other tiling, e.g. one read back from an OBJ
+//! file, and offers the same methods.
+//!
+//! Any tiling can be wrapped onto a [`Surface`] like a cylinder, cone,
+//! torus or sphere, or onto a heightfield given by a closure.
+//!
+//! [`HankinPattern`]s are Islamic star patterns drawn over a tiling,
+//! optionally interlaced, and [`Decoration`]s place Truchet tiles and
+//! similar motifs on every face. A [`Weave`] runs strands along the
+//! medial graph of a tiling, passing over and under each other.
+//!
+//! Conway operators like [`truncate()`](RegularTiling::truncate),
+//! [`ambo()`](RegularTiling::ambo) or [`snub()`](RegularTiling::snub)
+//! derive new tilings from existing ones, e.g. the truncated square
+//! tiling from squares. [`Subdivision`] schemes refine tilings into
+//! smooth meshes.
+//!
+//! An [`Automaton`] runs Game of Life-like rules with a cell per face,
+//! optionally wrapped around a torus. Shortest paths and distance
+//! fields over faces and vertices help with game maps, see
+//! [`face_path()`](RegularTiling::face_path). A [`Maze`] has a cell per
+//! face of any tiling.
+//!
+//! [`Mesh::delaunay()`] and [`Mesh::voronoi()`] build tilings from
+//! arbitrary point sets, e.g. a jittered lattice.
+//!
+#![allow(clippy::identity_op)]
 use core::f64::consts::SQRT_2;
-#[cfg(feature = "obj")]
-use std::{error::Error, io::Write};
 use ultraviolet as uv;
 
 type VertexKey = u32;
@@ -22,14 +64,49 @@ pub type Face = Vec<VertexKey>;
 pub type FaceIndex = Vec<Face>;
 pub type Point = uv::Vec2;
 pub type Points = Vec<Point>;
+pub type Point3 = uv::Vec3;
+pub type Points3 = Vec<Point3>;
+pub type Polyline = Vec<Point>;
+
+/// A vertex position that can be written to 3D file formats.
+pub trait Position: Copy {
+    /// The position in 3D. Planar points lie in the `z = 0` plane.
+    fn xyz(&self) -> [f32; 3];
+
+    /// The point at `xyz`. Planar points drop `z`.
+    fn from_xyz(xyz: [f32; 3]) -> Self;
+}
+
+impl Position for Point {
+    fn xyz(&self) -> [f32; 3] {
+        [self.x, self.y, 0.0]
+    }
+
+    fn from_xyz([x, y, _]: [f32; 3]) -> Self {
+        Point::new(x, y)
+    }
+}
+
+impl Position for Point3 {
+    fn xyz(&self) -> [f32; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    fn from_xyz(xyz: [f32; 3]) -> Self {
+        Point3::from(xyz)
+    }
+}
 
 macro_rules! default_methods {
     () => {
+        default_methods! { $crate::Point }
+    };
+    ($point:ty) => {
         pub fn faces(&self) -> &FaceIndex {
             &self.face_index
         }
 
-        pub fn points(&self) -> &Points {
+        pub fn points(&self) -> &Vec<$point> {
             &self.points
         }
 
@@ -37,45 +114,676 @@ macro_rules! default_methods {
             self.name.as_str()
         }
 
+        /// Returns every edge once, with the face(s) it borders.
+        ///
+        /// Only the first two faces of an edge are kept. A third or
+        /// further face on a non-manifold edge, as a mesh read by
+        /// [`Mesh::from_obj()`]($crate::Mesh::from_obj) can have, is
+        /// left out.
+        pub fn edges(&self) -> &[$crate::Edge] {
+            self.topology().edges()
+        }
+
+        /// The class of every face, e.g. the kind of tile it is. Writers
+        /// color and group faces by class.
+        ///
+        /// Tilings by regular polygons class faces by polygon, the one
+        /// with the fewest vertices first; other tilings by their
+        /// prototiles or whether a tile is mirrored. Meshes derived face
+        /// by face, e.g. by [`inset()`](Self::inset), keep the classes.
+        pub fn classes(&self) -> &[u32] {
+            self.classes.get(&self.face_index)
+        }
+
+        /// Returns a tiling of the faces moved inwards by `distance`, each
+        /// with its own vertices.
+        ///
+        /// If `radius` is positive, corners are rounded with arcs of that
+        /// radius made of `segments` pieces.
+        ///
+        /// # Errors
+        /// Returns [`Error::Constraint`](crate::Error::Constraint) unless
+        /// `distance ≥ 0` and `radius ≥ 0`, and
+        /// [`Error::DegenerateTile`](crate::Error::DegenerateTile) if
+        /// `distance` reaches past the middle of a face.
+        pub fn inset(
+            &self,
+            distance: f32,
+            radius: f32,
+            segments: u32,
+        ) -> Result<$crate::Mesh<$point>, $crate::Error> {
+            $crate::offset::inset(
+                &self.name,
+                &self.points,
+                &self.face_index,
+                distance,
+                radius,
+                segments,
+            )
+            .map(|mesh| mesh.with_classes(self.classes()))
+        }
+
+        /// Wraps the tiling onto `surface`, stretching its bounding box
+        /// over the surface's parameter domain.
+        ///
+        /// Every edge is split into `segments` pieces first, so large
+        /// faces follow the curvature. Only `x` and `y` coordinates are
+        /// used.
+        ///
+        /// Points that meet on a seam or at a pole of the surface are
+        /// welded. The mesh is closed where sides of the tiling's bounding
+        /// box that meet have matching vertices, e.g. for a
+        /// [`RegularTiling::square()`](crate::RegularTiling::square) on a
+        /// torus, and open elsewhere.
+        pub fn map_onto(
+            &self,
+            surface: $crate::Surface,
+            segments: u32,
+        ) -> $crate::Mesh<$crate::Point3> {
+            $crate::surface::map(
+                &self.name,
+                &self.points,
+                &self.face_index,
+                $crate::surface::parametrization(&self.points, surface),
+                segments,
+                true,
+            )
+            .with_classes(self.classes())
+        }
+
+        /// Like [`map_onto()`](Self::map_onto), but with positions given
+        /// by `f` of the planar points, e.g. a heightfield.
+        pub fn map_with(
+            &self,
+            f: impl Fn($crate::Point) -> $crate::Point3,
+            segments: u32,
+        ) -> $crate::Mesh<$crate::Point3> {
+            $crate::surface::map(
+                &self.name,
+                &self.points,
+                &self.face_index,
+                f,
+                segments,
+                false,
+            )
+            .with_classes(self.classes())
+        }
+
+        /// Creates an Islamic star pattern by Hankin's
+        /// polygons-in-contact method, using the faces as the polygons.
+        ///
+        /// Two rays leave every edge at `contact_angle` (in radians),
+        /// from points `offset` to either side of its midpoint. If
+        /// `interlaced` is set, strands alternate between passing over
+        /// and under each other. Only `x` and `y` coordinates are used.
+        ///
+        /// # Errors
+        /// Returns [`Error::Constraint`](crate::Error::Constraint) unless
+        /// `0 < contact_angle < π / 2` and `offset ≥ 0`.
+        pub fn hankin(
+            &self,
+            contact_angle: f32,
+            offset: f32,
+            interlaced: bool,
+        ) -> Result<$crate::HankinPattern, $crate::Error> {
+            $crate::hankin::hankin(
+                &self.name,
+                &self.points,
+                &self.face_index,
+                contact_angle,
+                offset,
+                interlaced,
+            )
+        }
+
+        /// Draws `motif` on every face, e.g. Truchet tiles.
+        ///
+        /// If a `seed` is given, every face's motif is rotated by a
+        /// random number of edges; the same seed yields the same
+        /// decoration. Only `x` and `y` coordinates are used.
+        pub fn decorate(&self, motif: $crate::Motif, seed: Option<u64>) -> $crate::Decoration {
+            $crate::decoration::decorate(&self.name, &self.points, &self.face_index, motif, seed)
+        }
+
+        /// Returns the medial graph: a vertex at the midpoint of every
+        /// edge and, per face, a face connecting the midpoints of its
+        /// edges.
+        pub fn medial(&self) -> $crate::Mesh<$point> {
+            $crate::weave::medial(&self.name, &self.points, &self.face_index)
+        }
+
+        /// Weaves strands along the medial graph, alternating over and
+        /// under where they cross. Only `x` and `y` coordinates are
+        /// used.
+        pub fn weave(&self) -> $crate::Weave {
+            $crate::weave::weave(&self.points, &self.face_index)
+        }
+
+        /// Returns the dual tiling: a vertex at the centroid of every face and
+        /// a face around every interior vertex.
+        pub fn dual(&self) -> $crate::Mesh<$point> {
+            $crate::conway::apply(
+                &self.name,
+                &self.points,
+                &self.face_index,
+                $crate::conway::Operator::Dual,
+            )
+        }
+
+        /// Returns the tiling with a vertex at the midpoint of every edge, a
+        /// face inside every face and one around every interior vertex, e.g.
+        /// the trihexagonal tiling from hexagons.
+        pub fn ambo(&self) -> $crate::Mesh<$point> {
+            $crate::conway::apply(
+                &self.name,
+                &self.points,
+                &self.face_index,
+                $crate::conway::Operator::Ambo,
+            )
+        }
+
+        /// Cuts off every corner, e.g. the truncated square tiling from
+        /// squares. Corners are cut so that regular faces stay regular.
+        pub fn truncate(&self) -> $crate::Mesh<$point> {
+            $crate::conway::apply(
+                &self.name,
+                &self.points,
+                &self.face_index,
+                $crate::conway::Operator::Truncate,
+            )
+        }
+
+        /// Raises a pyramid on every face: a triangle per edge of a face,
+        /// meeting at its centroid.
+        pub fn kis(&self) -> $crate::Mesh<$point> {
+            $crate::conway::apply(
+                &self.name,
+                &self.points,
+                &self.face_index,
+                $crate::conway::Operator::Kis,
+            )
+        }
+
+        /// Returns the snub tiling, the dual of [`gyro()`](Self::gyro), e.g.
+        /// the snub square tiling from squares.
+        pub fn snub(&self) -> $crate::Mesh<$point> {
+            $crate::conway::apply(
+                &self.name,
+                &self.points,
+                &self.face_index,
+                $crate::conway::Operator::Snub,
+            )
+        }
+
+        /// Pulls the faces apart, with a quadrilateral in every interior edge
+        /// and a face around every interior vertex, e.g. the
+        /// rhombitrihexagonal tiling from hexagons.
+        pub fn expand(&self) -> $crate::Mesh<$point> {
+            $crate::conway::apply(
+                &self.name,
+                &self.points,
+                &self.face_index,
+                $crate::conway::Operator::Expand,
+            )
+        }
+
+        /// Splits every face with `n` corners into `n` pentagons around its
+        /// centroid, turning like a pinwheel.
+        pub fn gyro(&self) -> $crate::Mesh<$point> {
+            $crate::conway::apply(
+                &self.name,
+                &self.points,
+                &self.face_index,
+                $crate::conway::Operator::Gyro,
+            )
+        }
+
+        /// Returns the tiling with a quadrilateral for every interior edge,
+        /// joining its end points and the centroids of its faces.
+        pub fn join(&self) -> $crate::Mesh<$point> {
+            $crate::conway::apply(
+                &self.name,
+                &self.points,
+                &self.face_index,
+                $crate::conway::Operator::Join,
+            )
+        }
+
+        /// Splits every face into quadrilaterals, one per corner, through
+        /// the centroid and the edge midpoints.
+        pub fn ortho(&self) -> $crate::Mesh<$point> {
+            $crate::conway::apply(
+                &self.name,
+                &self.points,
+                &self.face_index,
+                $crate::conway::Operator::Ortho,
+            )
+        }
+
+        /// Refines the faces with a subdivision scheme.
+        ///
+        /// Boundary vertices are smoothed along the boundary unless
+        /// `fixed_boundary` is set.
+        pub fn subdivide(
+            &self,
+            scheme: $crate::Subdivision,
+            fixed_boundary: bool,
+        ) -> $crate::Mesh<$point> {
+            $crate::subdivision::subdivide(
+                &self.name,
+                &self.points,
+                &self.face_index,
+                scheme,
+                fixed_boundary,
+            )
+        }
+
+        fn topology(&self) -> &$crate::topology::Topology {
+            self.topology.get(self.points.len(), &self.face_index)
+        }
+
+        /// The vertices sharing an edge with vertex `v`, in ascending
+        /// order.
+        ///
+        /// This and the other neighbourhood queries derive the adjacency
+        /// of the tiling on first use and keep it.
+        pub fn vertex_neighbours(&self, v: u32) -> &[u32] {
+            self.topology().vertex_neighbours(v)
+        }
+
+        /// The number of edges at vertex `v`.
+        pub fn vertex_degree(&self, v: u32) -> usize {
+            self.topology().vertex_neighbours(v).len()
+        }
+
+        /// The faces around vertex `v`.
+        pub fn vertex_faces(&self, v: u32) -> &[usize] {
+            self.topology().vertex_faces(v)
+        }
+
+        /// The faces next to face `f`, in ascending order.
+        pub fn face_neighbours(&self, f: usize, adjacency: $crate::Adjacency) -> &[usize] {
+            self.topology().face_neighbours(f, adjacency)
+        }
+
+        /// Vertex `v` and all vertices at most `k` edges away from it,
+        /// ordered by distance.
+        pub fn vertex_ring(&self, v: u32, k: u32) -> Vec<u32> {
+            let topology = self.topology();
+            $crate::topology::Topology::ring(v, k, |w| topology.vertex_neighbours(w))
+        }
+
+        /// Face `f` and all faces at most `k` steps from neighbour to
+        /// neighbour away from it, ordered by distance.
+        pub fn face_ring(&self, f: usize, k: u32, adjacency: $crate::Adjacency) -> Vec<usize> {
+            let topology = self.topology();
+            $crate::topology::Topology::ring(f, k, |g| topology.face_neighbours(g, adjacency))
+        }
+
+        /// The faces on a shortest path from face `from` to face `to`,
+        /// both included, or `None` if `to` cannot be reached or either
+        /// face does not exist.
+        ///
+        /// With [`Metric::Euclidean`](crate::Metric::Euclidean), steps are
+        /// as long as the distance between face centroids.
+        pub fn face_path(
+            &self,
+            from: usize,
+            to: usize,
+            adjacency: $crate::Adjacency,
+            metric: $crate::Metric,
+        ) -> Option<Vec<usize>> {
+            $crate::path::face_path(
+                &self.points,
+                &self.face_index,
+                self.topology(),
+                from,
+                to,
+                adjacency,
+                metric,
+            )
+        }
+
+        /// The vertices on a shortest path along edges from vertex
+        /// `from` to vertex `to`, both included, or `None` if `to`
+        /// cannot be reached or either vertex does not exist.
+        pub fn vertex_path(&self, from: u32, to: u32, metric: $crate::Metric) -> Option<Vec<u32>> {
+            $crate::path::vertex_path(&self.points, self.topology(), from, to, metric)
+        }
+
+        /// The distance of every face from the nearest of the `sources`
+        /// faces, `None` for faces that cannot be reached.
+        pub fn face_distances(
+            &self,
+            sources: &[usize],
+            adjacency: $crate::Adjacency,
+            metric: $crate::Metric,
+        ) -> Vec<Option<f32>> {
+            $crate::path::face_distances(
+                &self.points,
+                &self.face_index,
+                self.topology(),
+                sources,
+                adjacency,
+                metric,
+            )
+        }
+
+        /// The distance of every vertex along edges from the nearest
+        /// corner of the `sources` faces, `None` for vertices that
+        /// cannot be reached.
+        pub fn vertex_distances(
+            &self,
+            sources: &[usize],
+            metric: $crate::Metric,
+        ) -> Vec<Option<f32>> {
+            $crate::path::vertex_distances(
+                &self.points,
+                &self.face_index,
+                self.topology(),
+                sources,
+                metric,
+            )
+        }
+
+        /// Creates a maze with a cell per face, passing between faces
+        /// through shared edges. The same `seed` yields the same maze.
+        pub fn maze(&self, algorithm: $crate::MazeAlgorithm, seed: u64) -> $crate::Maze<$point> {
+            $crate::maze::maze(
+                &self.points,
+                self.face_index.len(),
+                self.topology(),
+                algorithm,
+                seed,
+            )
+        }
+
+        /// Returns the Delaunay triangulation of the face centroids, see
+        /// [`Mesh::delaunay()`](crate::Mesh::delaunay). For regular
+        /// tilings, merging `cocircular` triangles yields the dual. Only
+        /// `x` and `y` coordinates are used.
+        pub fn centroid_delaunay(&self, cocircular: bool) -> $crate::Mesh {
+            $crate::Mesh::delaunay(
+                &$crate::voronoi::centroids(&self.points, &self.face_index),
+                cocircular,
+            )
+        }
+
+        /// Returns the Voronoi diagram of the face centroids, clipped to
+        /// the bounding box of the tiling, see
+        /// [`Mesh::voronoi()`](crate::Mesh::voronoi). For regular
+        /// tilings, inner cells are the faces themselves. Only `x` and
+        /// `y` coordinates are used.
+        pub fn centroid_voronoi(&self) -> $crate::Mesh {
+            let (min, max) = $crate::voronoi::bounds(&self.points);
+            $crate::Mesh::voronoi(
+                &$crate::voronoi::centroids(&self.points, &self.face_index),
+                min,
+                max,
+            )
+        }
+
+        /// Creates a cellular automaton with a cell per face, all dead.
+        /// Cells have the tile class of their face, see
+        /// [`classes()`](Self::classes).
+        ///
+        /// With `wrap`, vertices that coincide when translated by the
+        /// two periods are identified, so cells on opposite sides of the
+        /// patch become neighbours. The periods must be lattice vectors
+        /// of the tiling, e.g. `(cols - 1, 0)` and `(0, rows - 1)` for
+        /// [`RegularTiling::square()`](crate::RegularTiling::square).
+        pub fn automaton(
+            &self,
+            adjacency: $crate::Adjacency,
+            wrap: Option<[$crate::Point; 2]>,
+        ) -> $crate::Automaton {
+            $crate::automaton::Automaton::new(
+                &self.points,
+                &self.face_index,
+                self.classes(),
+                self.topology(),
+                adjacency,
+                wrap,
+            )
+        }
+
+        /// Turns every face into a solid tile in one closed, manifold
+        /// mesh.
+        ///
+        /// Faces are inset by half the `grout` width, so neighbouring
+        /// tiles are `grout` apart, and extruded along their normal by
+        /// `thickness`. A positive `bevel` chamfers the top edges.
+        ///
+        /// # Errors
+        /// Returns [`Error::Constraint`](crate::Error::Constraint) unless
+        /// `grout ≥ 0`, `thickness > 0` and `0 ≤ bevel < thickness`, and
+        /// [`Error::DegenerateTile`](crate::Error::DegenerateTile) if the
+        /// grout or the bevel shrink a tile to nothing.
+        pub fn extrude(
+            &self,
+            grout: f32,
+            thickness: f32,
+            bevel: f32,
+        ) -> Result<$crate::Mesh<$crate::Point3>, $crate::Error> {
+            $crate::extrude::extrude(
+                &self.name,
+                &self.points,
+                &self.face_index,
+                grout,
+                thickness,
+                bevel,
+            )
+        }
+
+        /// Like [`extrude()`](Self::extrude) but returns a separate mesh
+        /// per tile.
+        ///
+        /// # Errors
+        /// Fails like [`extrude()`](Self::extrude).
+        pub fn extrude_tiles(
+            &self,
+            grout: f32,
+            thickness: f32,
+            bevel: f32,
+        ) -> Result<Vec<$crate::Mesh<$crate::Point3>>, $crate::Error> {
+            $crate::extrude::extrude_tiles(
+                &self.name,
+                &self.points,
+                &self.face_index,
+                grout,
+                thickness,
+                bevel,
+            )
+        }
+
+        #[cfg(feature = "obj")]
+        pub fn to_obj(
+            &self,
+            reverse_face_winding: bool,
+        ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+            self.to_obj_with(&$crate::ObjOptions {
+                reverse_face_winding,
+                ..Default::default()
+            })
+        }
+
+        /// Writes the tiling as an OBJ file with optional texture
+        /// coordinates, normals and groups.
+        #[cfg(feature = "obj")]
+        pub fn to_obj_with(
+            &self,
+            options: &$crate::ObjOptions,
+        ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+            $crate::obj::write(
+                &self.name,
+                &self.points,
+                &self.face_index,
+                self.classes(),
+                options,
+            )
+        }
+
+        /// Writes the material library for an OBJ file written with
+        /// [`ObjOptions::groups`](crate::ObjOptions::groups) set, with
+        /// one material per tile class.
         #[cfg(feature = "obj")]
-        pub fn to_obj(&self, reverse_face_winding: bool) -> Result<Vec<u8>, Box<dyn Error>> {
-            let mut file = Vec::new();
-
-            writeln!(file, "o {}-tiling", self.name)?;
-
-            for vertex in &self.points {
-                writeln!(file, "v {} {} 0", vertex.x, vertex.y)?;
-            }
-
-            if reverse_face_winding {
-                for face in &self.face_index {
-                    write!(file, "f")?;
-                    for vertex_index in face.iter().rev() {
-                        write!(file, " {}", vertex_index + 1)?;
-                    }
-                    writeln!(file)?;
-                }
-            } else {
-                for face in &self.face_index {
-                    write!(file, "f")?;
-                    for vertex_index in face {
-                        write!(file, " {}", vertex_index + 1)?;
-                    }
-                    writeln!(file)?;
-                }
-            }
-
-            Ok(file)
+        pub fn to_mtl(&self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+            $crate::obj::write_mtl(self.classes())
+        }
+
+        /// Writes the tiling as a PLY file with a `class` and color per
+        /// face, see [`classes()`](Self::classes).
+        #[cfg(feature = "ply")]
+        pub fn to_ply(
+            &self,
+            encoding: $crate::Encoding,
+            reverse_face_winding: bool,
+        ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+            $crate::ply::write(
+                &self.name,
+                &self.points,
+                &self.face_index,
+                self.classes(),
+                encoding,
+                reverse_face_winding,
+            )
+        }
+
+        /// Writes the tiling as an STL file. Faces are triangulated.
+        #[cfg(feature = "stl")]
+        pub fn to_stl(
+            &self,
+            encoding: $crate::Encoding,
+            reverse_face_winding: bool,
+        ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+            $crate::stl::write(
+                &self.name,
+                &self.points,
+                &self.face_index,
+                encoding,
+                reverse_face_winding,
+            )
+        }
+
+        /// Writes the tiling as a glTF 2.0 file with one primitive and
+        /// material per tile class, see [`classes()`](Self::classes).
+        /// Faces are triangulated.
+        ///
+        /// # Errors
+        /// Fails for a tiling without faces, which glTF cannot hold.
+        #[cfg(feature = "gltf")]
+        pub fn to_gltf(
+            &self,
+            encoding: $crate::Encoding,
+            options: &$crate::GltfOptions,
+        ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+            $crate::gltf::write(
+                &self.name,
+                &self.points,
+                &self.face_index,
+                self.classes(),
+                encoding,
+                options,
+            )
+        }
+
+        /// Writes the tiling as an AutoCAD 2000 (R2000) DXF file for CAD
+        /// and CNC software.
+        ///
+        /// Faces become closed polylines on one layer per tile class,
+        /// e.g. `CLASS-1`, see [`classes()`](Self::classes). With
+        /// `unique_edges` every edge is instead written once as a line
+        /// so shared edges are not cut twice.
+        ///
+        /// # Errors
+        /// Polylines keep the elevation of their corners, so without
+        /// `unique_edges` faces whose corners differ in `z` fail.
+        #[cfg(feature = "dxf")]
+        pub fn to_dxf(&self, unique_edges: bool) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+            $crate::dxf::write(&self.points, &self.face_index, self.classes(), unique_edges)
         }
     };
 }
 
-const SQRT_3: f64 = 1.732_050_807_568_877_293;
+/// Whether a writer emits a text or a binary file.
+#[cfg(any(feature = "ply", feature = "stl", feature = "gltf"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    Ascii,
+    Binary,
+}
+
+const SQRT_3: f64 = 1.732_050_807_568_877_2;
+
+mod affine;
+mod aperiodic;
+mod automaton;
+mod builder;
+mod class;
+mod conway;
+mod decoration;
+#[cfg(feature = "dxf")]
+mod dxf;
+mod error;
+mod extrude;
+#[cfg(feature = "gltf")]
+mod gltf;
+mod hankin;
+mod hyperbolic;
+mod isohedral;
+mod maze;
+mod mesh;
+#[cfg(feature = "obj")]
+mod obj;
+mod offset;
+#[cfg(any(feature = "obj", feature = "ply", feature = "gltf"))]
+mod palette;
+mod path;
+mod paver;
+mod pentagonal;
+#[cfg(feature = "ply")]
+mod ply;
+mod polygon;
+mod random;
+mod spherical;
+#[cfg(feature = "stl")]
+mod stl;
+mod strand;
+mod subdivision;
+mod surface;
+mod topology;
+mod voronoi;
+mod weave;
+
+pub use aperiodic::AperiodicTiling;
+pub use automaton::{Automaton, Cell, LifeRule};
+pub use decoration::{Decoration, Motif};
+pub use error::Error;
+#[cfg(feature = "gltf")]
+pub use gltf::GltfOptions;
+pub use hankin::HankinPattern;
+pub use hyperbolic::{HyperbolicModel, HyperbolicTiling};
+pub use isohedral::{EdgePairing, EdgeShape, IsohedralType, IsohedralTiling};
+pub use maze::{Maze, MazeAlgorithm};
+pub use mesh::Mesh;
+#[cfg(feature = "obj")]
+pub use obj::{ObjOptions, UvMapping};
+pub use path::Metric;
+pub use paver::{PaverPattern, PaverTiling};
+pub use pentagonal::{PentagonalTiling, PentagonalType};
+pub use spherical::{Archimedean, SphericalTiling};
+pub use strand::{Crossing, Strand};
+pub use subdivision::Subdivision;
+pub use surface::Surface;
+pub use topology::{Adjacency, Edge};
+pub use weave::Weave;
 
 pub struct SemiRegularTiling {
     face_index: FaceIndex,
     points: Points,
     name: String,
+    topology: crate::topology::TopologyCache,
+    classes: crate::class::ClassCache,
 }
 
 impl SemiRegularTiling {
@@ -137,6 +845,8 @@ impl SemiRegularTiling {
                         .flatten()
                 })
                 .collect(),
+            topology: Default::default(),
+            classes: Default::default(),
         }
     }
 
@@ -194,6 +904,8 @@ impl SemiRegularTiling {
                     })
                 }))
                 .collect(),
+            topology: Default::default(),
+            classes: Default::default(),
         }
     }
 
@@ -241,6 +953,8 @@ impl SemiRegularTiling {
                     })
                 })
                 .collect(),
+            topology: Default::default(),
+            classes: Default::default(),
         }
     }
 
@@ -301,6 +1015,8 @@ impl SemiRegularTiling {
                         .flatten()
                 })
                 .collect(),
+            topology: Default::default(),
+            classes: Default::default(),
         }
     }
 
@@ -380,6 +1096,8 @@ impl SemiRegularTiling {
                         .flatten()
                 })
                 .collect(),
+            topology: Default::default(),
+            classes: Default::default(),
         }
     }
 
@@ -461,6 +1179,8 @@ impl SemiRegularTiling {
                         .flatten()
                 })
                 .collect(),
+            topology: Default::default(),
+            classes: Default::default(),
         }
     }
 
@@ -557,6 +1277,8 @@ impl SemiRegularTiling {
                         .flatten()
                 })
                 .collect(),
+            topology: Default::default(),
+            classes: Default::default(),
         }
     }
 
@@ -667,6 +1389,8 @@ impl SemiRegularTiling {
                         .flatten()
                 })
                 .collect(),
+            topology: Default::default(),
+            classes: Default::default(),
         }
     }
 }
@@ -675,6 +1399,8 @@ pub struct RegularTiling {
     face_index: FaceIndex,
     points: Points,
     name: String,
+    topology: crate::topology::TopologyCache,
+    classes: crate::class::ClassCache,
 }
 
 impl RegularTiling {
@@ -715,6 +1441,8 @@ impl RegularTiling {
                     })
                 })
                 .collect(),
+            topology: Default::default(),
+            classes: Default::default(),
         }
     }
 
@@ -740,6 +1468,8 @@ impl RegularTiling {
                     })
                 })
                 .collect(),
+            topology: Default::default(),
+            classes: Default::default(),
         }
     }
 
@@ -778,14 +1508,16 @@ impl RegularTiling {
                     })
                 })
                 .collect(),
+            topology: Default::default(),
+            classes: Default::default(),
         }
     }
 }
 
 #[test]
 #[cfg(feature = "obj")]
-pub fn obj() -> Result<(), Box<dyn Error>> {
-    use std::fs::File;
+pub fn obj() -> Result<(), Box<dyn std::error::Error>> {
+    use std::{fs::File, io::Write};
 
     let tiling = RegularTiling::triangle(100, 100);
     let mut file = File::create(format!("./{}.obj", tiling.name()))?;
@@ -0,0 +1,275 @@
+//! Mazes whose cells are the faces of a tiling.
+use crate::{random::Random, topology::Topology, Edge, Point, Position};
+use std::collections::HashMap;
+
+/// How a maze picks its passages. Every algorithm yields a perfect
+/// maze, i.e. exactly one way between any two faces that share edges
+/// with each other, directly or through other faces.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MazeAlgorithm {
+    /// A random depth-first search. Makes long, winding corridors with
+    /// few dead ends.
+    RecursiveBacktracker,
+    /// Opens edges in random order unless they would close a loop.
+    /// Makes many short dead ends.
+    Kruskal,
+    /// Loop-erased random walks, picking uniformly among all perfect
+    /// mazes.
+    Wilson,
+}
+
+/// A maze over the faces of a tiling.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Maze<P = Point> {
+    /// The shared edges one can pass through.
+    pub passages: Vec<Edge>,
+    /// The other edges, boundary edges included, joined into as few
+    /// polylines as possible. Closed walls repeat their first point.
+    pub walls: Vec<Vec<P>>,
+}
+
+/// Faces sharing an edge with each face and the index of that edge.
+fn neighbours(face_count: usize, edges: &[Edge]) -> Vec<Vec<(usize, usize)>> {
+    let mut neighbours = vec![Vec::new(); face_count];
+    for (e, edge) in edges.iter().enumerate() {
+        if let (f, Some(g)) = edge.faces {
+            neighbours[f].push((g, e));
+            neighbours[g].push((f, e));
+        }
+    }
+    neighbours
+}
+
+fn recursive_backtracker(
+    neighbours: &[Vec<(usize, usize)>],
+    random: &mut Random,
+    open: &mut [bool],
+) {
+    let mut visited = vec![false; neighbours.len()];
+    for start in 0..neighbours.len() {
+        if visited[start] {
+            continue;
+        }
+        visited[start] = true;
+        let mut stack = vec![start];
+        while let Some(&f) = stack.last() {
+            let unvisited: Vec<_> = neighbours[f]
+                .iter()
+                .filter(|&&(g, _)| !visited[g])
+                .collect();
+            if unvisited.is_empty() {
+                stack.pop();
+            } else {
+                let &(g, e) = unvisited[random.below(unvisited.len())];
+                open[e] = true;
+                visited[g] = true;
+                stack.push(g);
+            }
+        }
+    }
+}
+
+fn root(parent: &mut [usize], mut f: usize) -> usize {
+    while parent[f] != f {
+        parent[f] = parent[parent[f]];
+        f = parent[f];
+    }
+    f
+}
+
+fn kruskal(edges: &[Edge], face_count: usize, random: &mut Random, open: &mut [bool]) {
+    let mut order: Vec<usize> = (0..edges.len())
+        .filter(|&e| !edges[e].is_boundary())
+        .collect();
+    // Fisher-Yates shuffle.
+    for i in (1..order.len()).rev() {
+        order.swap(i, random.below(i + 1));
+    }
+
+    let mut parent: Vec<usize> = (0..face_count).collect();
+    for e in order {
+        if let (f, Some(g)) = edges[e].faces {
+            let (rf, rg) = (root(&mut parent, f), root(&mut parent, g));
+            if rf != rg {
+                parent[rf] = rg;
+                open[e] = true;
+            }
+        }
+    }
+}
+
+fn wilson(neighbours: &[Vec<(usize, usize)>], random: &mut Random, open: &mut [bool]) {
+    let mut in_maze = vec![false; neighbours.len()];
+    let mut seen = vec![false; neighbours.len()];
+    // The step each face took last on the current walk.
+    let mut next: Vec<Option<(usize, usize)>> = vec![None; neighbours.len()];
+
+    for start in 0..neighbours.len() {
+        if in_maze[start] {
+            continue;
+        }
+        // Seed every group of connected faces with a random one.
+        let mut component = vec![start];
+        seen[start] = true;
+        let mut i = 0;
+        while i < component.len() {
+            for &(g, _) in &neighbours[component[i]] {
+                if !seen[g] {
+                    seen[g] = true;
+                    component.push(g);
+                }
+            }
+            i += 1;
+        }
+        in_maze[component[random.below(component.len())]] = true;
+
+        for &walk_start in &component {
+            // Walk until the maze is hit; revisiting a face overwrites
+            // its step, which erases the loop.
+            let mut f = walk_start;
+            while !in_maze[f] {
+                let step = neighbours[f][random.below(neighbours[f].len())];
+                next[f] = Some(step);
+                f = step.0;
+            }
+            let mut f = walk_start;
+            while !in_maze[f] {
+                let (g, e) = next[f].unwrap();
+                in_maze[f] = true;
+                open[e] = true;
+                f = g;
+            }
+        }
+    }
+}
+
+/// Joins edges meeting at vertices of degree two into polylines.
+fn chain<P: Position>(points: &[P], edges: &[Edge]) -> Vec<Vec<P>> {
+    let mut incident: HashMap<u32, Vec<usize>> = HashMap::new();
+    for (e, edge) in edges.iter().enumerate() {
+        for &v in &edge.vertices {
+            incident.entry(v).or_default().push(e);
+        }
+    }
+
+    let mut used = vec![false; edges.len()];
+    let walk = |start: u32, used: &mut Vec<bool>| -> Vec<Vec<P>> {
+        let mut polylines = Vec::new();
+        for &first in &incident[&start] {
+            if used[first] {
+                continue;
+            }
+            let mut polyline = vec![points[start as usize]];
+            let (mut v, mut e) = (start, first);
+            loop {
+                used[e] = true;
+                let [a, b] = edges[e].vertices;
+                v = if a == v { b } else { a };
+                polyline.push(points[v as usize]);
+                let around = &incident[&v];
+                match around.iter().find(|&&e| !used[e]) {
+                    Some(&f) if around.len() == 2 => e = f,
+                    _ => break,
+                }
+            }
+            polylines.push(polyline);
+        }
+        polylines
+    };
+
+    // Open chains run between vertices where walls branch or end,
+    // closed ones are left over.
+    let mut vertices: Vec<u32> = incident.keys().copied().collect();
+    vertices.sort_unstable();
+    let mut polylines = Vec::new();
+    for &v in &vertices {
+        if incident[&v].len() != 2 {
+            polylines.extend(walk(v, &mut used));
+        }
+    }
+    for &v in &vertices {
+        polylines.extend(walk(v, &mut used));
+    }
+    polylines
+}
+
+pub(crate) fn maze<P: Position>(
+    points: &[P],
+    face_count: usize,
+    topology: &Topology,
+    algorithm: MazeAlgorithm,
+    seed: u64,
+) -> Maze<P> {
+    let edges = topology.edges();
+    let mut random = Random::new(seed);
+    let mut open = vec![false; edges.len()];
+
+    match algorithm {
+        MazeAlgorithm::RecursiveBacktracker => {
+            recursive_backtracker(&neighbours(face_count, edges), &mut random, &mut open)
+        }
+        MazeAlgorithm::Kruskal => kruskal(edges, face_count, &mut random, &mut open),
+        MazeAlgorithm::Wilson => wilson(&neighbours(face_count, edges), &mut random, &mut open),
+    }
+
+    let (passages, walls): (Vec<_>, Vec<_>) =
+        edges.iter().copied().zip(open).partition(|&(_, open)| open);
+    Maze {
+        passages: passages.into_iter().map(|(edge, _)| edge).collect(),
+        walls: chain(
+            points,
+            &walls.into_iter().map(|(edge, _)| edge).collect::<Vec<_>>(),
+        ),
+    }
+}
+
+#[test]
+fn mazes_are_spanning_trees() {
+    use crate::{topology, FaceIndex, RegularTiling, SemiRegularTiling, SphericalTiling};
+
+    fn check<P: Position>(maze: &Maze<P>, face_index: &FaceIndex) {
+        let edges = topology::edges(face_index);
+        // A tree has one passage fewer than cells and connects them all.
+        assert_eq!(maze.passages.len(), face_index.len() - 1);
+        let mut parent: Vec<usize> = (0..face_index.len()).collect();
+        for passage in &maze.passages {
+            let (f, g) = (passage.faces.0, passage.faces.1.unwrap());
+            let (rf, rg) = (root(&mut parent, f), root(&mut parent, g));
+            assert_ne!(rf, rg);
+            parent[rf] = rg;
+        }
+        // Every other edge is a wall segment.
+        let segments: usize = maze.walls.iter().map(|wall| wall.len() - 1).sum();
+        assert_eq!(segments, edges.len() - maze.passages.len());
+    }
+
+    let squares = RegularTiling::square(9, 9);
+    let hexagons = RegularTiling::hexagon(12, 12);
+    let octagons = SemiRegularTiling::two(12, 12);
+    let cube = SphericalTiling::regular(4, 3).unwrap();
+    for &algorithm in &[
+        MazeAlgorithm::RecursiveBacktracker,
+        MazeAlgorithm::Kruskal,
+        MazeAlgorithm::Wilson,
+    ] {
+        let maze = squares.maze(algorithm, 3);
+        check(&maze, squares.faces());
+        assert_eq!(maze, squares.maze(algorithm, 3));
+        assert_ne!(maze, squares.maze(algorithm, 4));
+
+        check(&hexagons.maze(algorithm, 5), hexagons.faces());
+        check(&cube.maze(algorithm, 1), cube.faces());
+
+        // Faces that share no edges form separate mazes.
+        let maze = octagons.maze(algorithm, 2);
+        let groups = (0..octagons.faces().len())
+            .filter(|&f| {
+                octagons
+                    .face_ring(f, octagons.faces().len() as u32, crate::Adjacency::Edge)
+                    .iter()
+                    .all(|&g| g >= f)
+            })
+            .count();
+        assert_eq!(maze.passages.len(), octagons.faces().len() - groups);
+    }
+}
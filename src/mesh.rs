@@ -0,0 +1,97 @@
+//! A tiling given by arbitrary points and faces.
+use crate::{FaceIndex, Point, Position};
+
+/// A tiling or mesh that is not generated by one of the constructors
+/// of this crate, e.g. one read from a file or the result of an
+/// operation on another tiling.
+///
+/// Points are planar by default but can be any [`Position`], e.g.
+/// [`Point3`](crate::Point3) for meshes in 3D.
+#[derive(Clone, Debug)]
+pub struct Mesh<P = Point> {
+    face_index: FaceIndex,
+    points: Vec<P>,
+    name: String,
+    topology: crate::topology::TopologyCache,
+    classes: crate::class::ClassCache,
+}
+
+impl<P: Position> Mesh<P> {
+    default_methods! { P }
+
+    /// Creates a mesh from its parts. Faces index into `points`.
+    pub fn new(name: &str, points: Vec<P>, face_index: FaceIndex) -> Self {
+        Self {
+            face_index,
+            points,
+            name: name.to_uppercase(),
+            topology: Default::default(),
+            classes: Default::default(),
+        }
+    }
+
+    /// Gives the faces the classes of the faces they were made from, one
+    /// to one.
+    pub(crate) fn with_classes(mut self, classes: &[u32]) -> Self {
+        self.classes = classes.to_vec().into();
+        self
+    }
+}
+
+// The topology follows from the faces, but classes can differ between
+// meshes with the same faces, e.g. an inset of mirrored and unmirrored
+// hats and a mesh read from a file.
+impl<P: PartialEq> PartialEq for Mesh<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.points == other.points
+            && self.face_index == other.face_index
+            && self.classes.get(&self.face_index) == other.classes.get(&other.face_index)
+    }
+}
+
+impl Mesh {
+    /// Creates the Delaunay triangulation of `points`, i.e. the one
+    /// where no point lies inside the circumcircle of a triangle.
+    ///
+    /// Duplicate points are dropped. If `cocircular` is set, triangles
+    /// sharing a circumcircle are merged into one convex polygon, making
+    /// the result unique, e.g. squares for points on a square grid.
+    pub fn delaunay(points: &[Point], cocircular: bool) -> Self {
+        let (points, face_index) =
+            crate::voronoi::delaunay(points.iter().map(|p| [p.x as f64, p.y as f64]), cocircular);
+        Self::new("delaunay", points, face_index)
+    }
+
+    /// Creates the Voronoi diagram of `sites` clipped to the rectangle
+    /// from `min` to `max`: a face per site, covering the part of the
+    /// rectangle closer to it than to any other site.
+    ///
+    /// Duplicate sites and sites whose cell misses the rectangle get no
+    /// face.
+    pub fn voronoi(sites: &[Point], min: Point, max: Point) -> Self {
+        let (points, face_index) = crate::voronoi::voronoi(
+            sites.iter().map(|p| [p.x as f64, p.y as f64]),
+            [min.x as f64, min.y as f64],
+            [max.x as f64, max.y as f64],
+        );
+        Self::new("voronoi", points, face_index)
+    }
+
+    /// Reads a planar mesh from an OBJ file.
+    ///
+    /// Only vertex positions and faces are read; texture coordinates,
+    /// normals, groups and materials are ignored. The mesh is named
+    /// after the first object (`o`), minus a `-tiling` suffix as written
+    /// by [`to_obj()`](Self::to_obj).
+    ///
+    /// # Errors
+    /// Returns [`Error::Parse`](crate::Error::Parse) for malformed
+    /// lines and [`Error::NotPlanar`](crate::Error::NotPlanar) if the
+    /// vertices do not share one `z` coordinate.
+    #[cfg(feature = "obj")]
+    pub fn from_obj(obj: &[u8]) -> Result<Self, crate::Error> {
+        let (name, points, face_index) = crate::obj::read(obj)?;
+        Ok(Self::new(&name, points, face_index))
+    }
+}
@@ -0,0 +1,388 @@
+//! [OBJ](https://paulbourke.net/dataformats/obj/) and
+//! [MTL](https://paulbourke.net/dataformats/mtl/) writers.
+use crate::{palette::class_color, polygon, Error, Face, FaceIndex, Point, Points, Position};
+use std::{collections::BTreeSet, io::Write};
+
+/// How texture coordinates are derived from positions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UvMapping {
+    /// The bounding box of the `x`, `y` coordinates is mapped to the
+    /// unit square, keeping its aspect ratio.
+    Planar,
+    /// The `x`, `y` coordinates are used as is, so a texture repeats
+    /// once per unit of the tiling.
+    Lattice,
+    /// The bounding box of each face is mapped to the unit square, so
+    /// every tile shows the whole texture.
+    PerTile,
+}
+
+/// Optional contents of an OBJ file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ObjOptions {
+    /// Write the corners of every face in clockwise order, flipping the
+    /// side it faces.
+    pub reverse_face_winding: bool,
+    /// Write `vt` texture coordinates.
+    pub uvs: Option<UvMapping>,
+    /// Write `vn` vertex normals averaged from the adjacent faces.
+    pub normals: bool,
+    /// Put faces into one `g` group per tile class, with a `usemtl`
+    /// material each. Faces are written ordered by class.
+    pub groups: bool,
+    /// The material library referenced with `mtllib`, e.g. the name the
+    /// output of `to_mtl()` is saved under.
+    pub material_library: Option<String>,
+}
+
+fn material_name(class: u32) -> String {
+    format!("class-{}", class)
+}
+
+pub(crate) fn write<P: Position>(
+    name: &str,
+    points: &[P],
+    face_index: &FaceIndex,
+    classes: &[u32],
+    options: &ObjOptions,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut file = Vec::new();
+
+    if let Some(material_library) = &options.material_library {
+        writeln!(file, "mtllib {}", material_library)?;
+    }
+    writeln!(file, "o {}-tiling", name)?;
+
+    let positions: Vec<[f32; 3]> = points.iter().map(Position::xyz).collect();
+    for [x, y, z] in &positions {
+        writeln!(file, "v {} {} {}", x, y, z)?;
+    }
+
+    match options.uvs {
+        Some(UvMapping::Planar) => {
+            let (min, extent) = bounds(positions.iter());
+            for [x, y, _] in &positions {
+                writeln!(
+                    file,
+                    "vt {} {}",
+                    (x - min[0]) / extent,
+                    (y - min[1]) / extent
+                )?;
+            }
+        }
+        Some(UvMapping::Lattice) => {
+            for [x, y, _] in &positions {
+                writeln!(file, "vt {} {}", x, y)?;
+            }
+        }
+        Some(UvMapping::PerTile) => {
+            for face in face_index {
+                let (min, extent) = bounds(face.iter().map(|&v| &positions[v as usize]));
+                for &vertex in face {
+                    let [x, y, _] = positions[vertex as usize];
+                    writeln!(
+                        file,
+                        "vt {} {}",
+                        (x - min[0]) / extent,
+                        (y - min[1]) / extent
+                    )?;
+                }
+            }
+        }
+        None => (),
+    }
+
+    if options.normals {
+        let sign = if options.reverse_face_winding {
+            -1.0
+        } else {
+            1.0
+        };
+        for [x, y, z] in polygon::vertex_normals(&positions, face_index) {
+            writeln!(file, "vn {} {} {}", sign * x, sign * y, sign * z)?;
+        }
+    }
+
+    // Texture coordinates of a tile are stored in face order.
+    let first_corners: Vec<usize> = face_index
+        .iter()
+        .scan(0, |corner, face| {
+            *corner += face.len();
+            Some(*corner - face.len())
+        })
+        .collect();
+
+    let mut order: Vec<usize> = (0..face_index.len()).collect();
+    if options.groups {
+        order.sort_by_key(|&f| classes[f]);
+    }
+
+    let mut class = None;
+    for f in order {
+        let face = &face_index[f];
+        if options.groups && class != Some(classes[f]) {
+            class = Some(classes[f]);
+            writeln!(file, "g {}", material_name(classes[f]))?;
+            writeln!(file, "usemtl {}", material_name(classes[f]))?;
+        }
+
+        let mut corners: Vec<(u32, usize)> = face
+            .iter()
+            .enumerate()
+            .map(|(i, &vertex)| (vertex, first_corners[f] + i))
+            .collect();
+        if options.reverse_face_winding {
+            corners.reverse();
+        }
+
+        write!(file, "f")?;
+        for (vertex, corner) in corners {
+            let vertex = vertex as usize + 1;
+            match (options.uvs, options.normals) {
+                (None, false) => write!(file, " {}", vertex)?,
+                (None, true) => write!(file, " {}//{}", vertex, vertex)?,
+                (Some(uvs), normals) => {
+                    let uv = if uvs == UvMapping::PerTile {
+                        corner + 1
+                    } else {
+                        vertex
+                    };
+                    write!(file, " {}/{}", vertex, uv)?;
+                    if normals {
+                        write!(file, "/{}", vertex)?;
+                    }
+                }
+            }
+        }
+        writeln!(file)?;
+    }
+
+    Ok(file)
+}
+
+/// The lower left corner and the larger side of the bounding box of
+/// the `x`, `y` coordinates.
+fn bounds<'a>(positions: impl Iterator<Item = &'a [f32; 3]>) -> ([f32; 2], f32) {
+    let mut min = [f32::MAX; 2];
+    let mut max = [f32::MIN; 2];
+    for p in positions {
+        for k in 0..2 {
+            min[k] = min[k].min(p[k]);
+            max[k] = max[k].max(p[k]);
+        }
+    }
+    let extent = (max[0] - min[0]).max(max[1] - min[1]);
+    (min, if extent > 0.0 { extent } else { 1.0 })
+}
+
+/// Writes a material per tile class present in `classes`, for use with
+/// [`ObjOptions::groups`].
+pub(crate) fn write_mtl(classes: &[u32]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut file = Vec::new();
+
+    for &class in classes.iter().collect::<BTreeSet<_>>() {
+        let [r, g, b] = class_color(class).map(|c| c as f32 / 255.0);
+        writeln!(file, "newmtl {}", material_name(class))?;
+        writeln!(file, "Kd {} {} {}", r, g, b)?;
+        writeln!(file, "Ka 0 0 0")?;
+        writeln!(file, "illum 1")?;
+        writeln!(file)?;
+    }
+
+    Ok(file)
+}
+
+#[test]
+fn obj_options() -> Result<(), Box<dyn std::error::Error>> {
+    let tiling = crate::SemiRegularTiling::seven(20, 20);
+    let corners: usize = tiling.faces().iter().map(|face| face.len()).sum();
+
+    let obj = String::from_utf8(tiling.to_obj_with(&ObjOptions {
+        uvs: Some(UvMapping::PerTile),
+        normals: true,
+        groups: true,
+        material_library: Some("tiling.mtl".to_string()),
+        ..Default::default()
+    })?)?;
+    assert!(obj.starts_with("mtllib tiling.mtl\n"));
+    assert_eq!(
+        obj.lines().filter(|l| l.starts_with("vt ")).count(),
+        corners
+    );
+    assert_eq!(
+        obj.lines().filter(|l| l.starts_with("vn ")).count(),
+        tiling.points().len()
+    );
+    assert!(obj.contains("\nvn 0 0 1\n"));
+
+    // Faces are grouped by class, each group written once, and keep
+    // their texture coordinates.
+    let classes = tiling.classes();
+    let mut groups = Vec::new();
+    let mut faces = Vec::new();
+    for line in obj.lines() {
+        if let Some(group) = line.strip_prefix("g ") {
+            groups.push(group);
+        } else if line.starts_with("f ") {
+            faces.push(line);
+        }
+    }
+    let mut order: Vec<usize> = (0..classes.len()).collect();
+    order.sort_by_key(|&f| classes[f]);
+    assert_eq!(groups, ["class-0", "class-1", "class-2"]);
+    let last = *order.last().unwrap();
+    let first_corner: usize = tiling.faces()[..last].iter().map(|face| face.len()).sum();
+    assert_eq!(
+        faces.last().unwrap().split(' ').nth(1),
+        Some(
+            format!(
+                "{}/{}/{}",
+                tiling.faces()[last][0] + 1,
+                first_corner + 1,
+                tiling.faces()[last][0] + 1
+            )
+            .as_str()
+        )
+    );
+
+    let mtl = String::from_utf8(tiling.to_mtl()?)?;
+    for &class in classes {
+        let material = format!("newmtl class-{}", class);
+        assert!(mtl.contains(&material));
+        assert!(obj.contains(&format!("usemtl class-{}", class)));
+    }
+
+    // The defaults yield what `to_obj()` writes.
+    assert_eq!(
+        tiling.to_obj_with(&ObjOptions::default())?,
+        tiling.to_obj(false)?
+    );
+
+    Ok(())
+}
+
+/// Positions closer to the mean `z` than this, relative to the size of
+/// the mesh, count as planar.
+const PLANARITY_TOLERANCE: f32 = 1.0e-4;
+
+/// Reads the name, planar vertex positions and faces of an OBJ file.
+pub(crate) fn read(obj: &[u8]) -> Result<(String, Points, FaceIndex), Error> {
+    let text = std::str::from_utf8(obj).map_err(|_| Error::Parse {
+        line: 0,
+        message: "not UTF-8",
+    })?;
+
+    let mut name = None;
+    let mut positions: Vec<[f32; 3]> = Vec::new();
+    let mut face_index = FaceIndex::new();
+
+    for (number, line) in text.lines().enumerate() {
+        let error = |message| Error::Parse {
+            line: number + 1,
+            message,
+        };
+        let mut tokens = line.split_whitespace();
+
+        match tokens.next() {
+            Some("o") if name.is_none() => {
+                name = Some(tokens.collect::<Vec<_>>().join(" "));
+            }
+            Some("v") => {
+                let coordinates = tokens
+                    .take(3)
+                    .map(|token| token.parse::<f32>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|_| error("invalid vertex coordinate"))?;
+                match coordinates[..] {
+                    [x, y] => positions.push([x, y, 0.0]),
+                    [x, y, z] => positions.push([x, y, z]),
+                    _ => return Err(error("vertex with less than two coordinates")),
+                }
+            }
+            Some("f") => {
+                let face = tokens
+                    .map(|token| {
+                        // The vertex index precedes any texture
+                        // coordinate and normal index.
+                        let index: i64 = token
+                            .split('/')
+                            .next()
+                            .unwrap()
+                            .parse()
+                            .map_err(|_| error("invalid vertex index"))?;
+                        // Negative indices count back from the last
+                        // vertex read so far.
+                        let index = if index < 0 {
+                            positions.len() as i64 + index
+                        } else {
+                            index - 1
+                        };
+                        if 0 <= index && (index as usize) < positions.len() {
+                            Ok(index as u32)
+                        } else {
+                            Err(error("vertex index out of range"))
+                        }
+                    })
+                    .collect::<Result<Face, _>>()?;
+                if face.len() < 3 {
+                    return Err(error("face with less than three vertices"));
+                }
+                face_index.push(face);
+            }
+            _ => (),
+        }
+    }
+
+    if !positions.is_empty() {
+        let (_, extent) = bounds(positions.iter());
+        let z = positions.iter().map(|p| p[2]).sum::<f32>() / positions.len() as f32;
+        if positions
+            .iter()
+            .any(|p| (p[2] - z).abs() > PLANARITY_TOLERANCE * extent.max(1.0))
+        {
+            return Err(Error::NotPlanar);
+        }
+    }
+
+    let name = name.unwrap_or_else(|| "MESH".to_string());
+    let name = name.strip_suffix("-tiling").unwrap_or(&name).to_string();
+
+    Ok((
+        name,
+        positions.iter().map(|p| Point::new(p[0], p[1])).collect(),
+        face_index,
+    ))
+}
+
+#[test]
+fn obj_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let tiling = crate::SemiRegularTiling::seven(20, 20);
+    let mesh = crate::Mesh::from_obj(&tiling.to_obj_with(&ObjOptions {
+        uvs: Some(UvMapping::Planar),
+        normals: true,
+        groups: true,
+        ..Default::default()
+    })?)?;
+
+    // Grouped faces are written ordered by class.
+    let mut faces: Vec<_> = tiling.classes().iter().zip(tiling.faces()).collect();
+    faces.sort_by_key(|&(class, _)| class);
+    let faces: Vec<_> = faces.into_iter().map(|(_, face)| face.clone()).collect();
+    assert_eq!(mesh.name(), tiling.name());
+    assert_eq!(mesh.faces(), &faces);
+    assert_eq!(mesh.points(), tiling.points());
+
+    assert_eq!(
+        crate::Mesh::from_obj(b"v 0 0 0\nv 1 0 0\nv 0 1 1\nf 1 2 3\n"),
+        Err(Error::NotPlanar)
+    );
+    assert_eq!(
+        crate::Mesh::from_obj(b"v 0 0\nv 1 0\nv 0 1\nf 1 2 -4\n"),
+        Err(Error::Parse {
+            line: 4,
+            message: "vertex index out of range"
+        })
+    );
+
+    Ok(())
+}
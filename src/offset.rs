@@ -0,0 +1,95 @@
+//! Shrunk, optionally rounded copies of the faces of a tiling.
+use crate::{polygon, Error, FaceIndex, Mesh, Position};
+
+pub(crate) fn inset<P: Position>(
+    name: &str,
+    points: &[P],
+    face_index: &FaceIndex,
+    distance: f32,
+    radius: f32,
+    segments: u32,
+) -> Result<Mesh<P>, Error> {
+    if !(distance >= 0.0 && distance.is_finite()) {
+        return Err(Error::Constraint("distance ≥ 0"));
+    }
+    if !(radius >= 0.0 && radius.is_finite()) {
+        return Err(Error::Constraint("radius ≥ 0"));
+    }
+
+    let mut inset_points = Vec::new();
+    let mut inset_faces = FaceIndex::with_capacity(face_index.len());
+
+    for face in face_index {
+        let corners: Vec<[f32; 3]> = face.iter().map(|&v| points[v as usize].xyz()).collect();
+        let inset = polygon::inset(&corners, distance);
+        if polygon::collapsed(&corners, &inset) {
+            return Err(Error::DegenerateTile);
+        }
+        let corners = polygon::fillet(&inset, radius, segments);
+
+        let offset = inset_points.len() as u32;
+        inset_faces.push((offset..offset + corners.len() as u32).collect());
+        inset_points.extend(corners.into_iter().map(P::from_xyz));
+    }
+
+    Ok(Mesh::new(
+        &format!("{}-inset", name),
+        inset_points,
+        inset_faces,
+    ))
+}
+
+#[test]
+fn inset_squares() {
+    use crate::Point;
+
+    let tiling = crate::RegularTiling::square(4, 4);
+    let inset = tiling.inset(0.1, 0.0, 0).unwrap();
+
+    // Every face has its own vertices.
+    assert_eq!(inset.faces().len(), tiling.faces().len());
+    assert_eq!(inset.points().len(), 4 * tiling.faces().len());
+    let face = &inset.faces()[0];
+    let corners: Vec<Point> = face.iter().map(|&v| inset.points()[v as usize]).collect();
+    let side = (corners[1] - corners[0]).mag();
+    assert!((side - 0.8).abs() < 1.0e-6);
+
+    // Quarter circles of radius 0.2 with 4 segments each.
+    let rounded = tiling.inset(0.1, 0.2, 4).unwrap();
+    assert_eq!(rounded.faces()[0].len(), 4 * 5);
+    let corners: Vec<Point> = rounded.faces()[0]
+        .iter()
+        .map(|&v| rounded.points()[v as usize])
+        .collect();
+    let center = corners.iter().fold(Point::zero(), |c, &p| c + p) / corners.len() as f32;
+    // Arc points are 0.2 away from the arc centers, which sit 0.2 inside
+    // the inset square's sides.
+    let arc_center = center - Point::new(0.2, 0.2);
+    for corner in &corners[0..5] {
+        assert!(((*corner - arc_center).mag() - 0.2).abs() < 1.0e-5);
+    }
+}
+
+#[test]
+fn inset_errors() {
+    use crate::Error;
+
+    let tiling = crate::RegularTiling::hexagon(4, 4);
+    assert_eq!(
+        tiling.inset(-0.1, 0.0, 0).err(),
+        Some(Error::Constraint("distance ≥ 0"))
+    );
+    assert_eq!(
+        tiling.inset(f32::NAN, 0.0, 0).err(),
+        Some(Error::Constraint("distance ≥ 0"))
+    );
+    assert_eq!(
+        tiling.inset(0.1, f32::INFINITY, 4).err(),
+        Some(Error::Constraint("radius ≥ 0"))
+    );
+
+    // Unit hexagons have an inradius of √3/2; insetting further would
+    // turn them inside out.
+    assert!(tiling.inset(0.85, 0.0, 0).is_ok());
+    assert_eq!(tiling.inset(0.9, 0.0, 0).err(), Some(Error::DegenerateTile));
+}
@@ -0,0 +1,18 @@
+//! Colors for writers that style faces by tile class.
+
+/// Distinct colors for the first few tile classes.
+const PALETTE: [[u8; 3]; 8] = [
+    [78, 121, 167],
+    [242, 142, 43],
+    [225, 87, 89],
+    [118, 183, 178],
+    [89, 161, 79],
+    [237, 201, 72],
+    [176, 122, 161],
+    [156, 117, 95],
+];
+
+/// The color faces of the given class are written with.
+pub(crate) fn class_color(class: u32) -> [u8; 3] {
+    PALETTE[class as usize % PALETTE.len()]
+}
@@ -0,0 +1,338 @@
+//! Shortest paths and distance fields over faces and vertices.
+use crate::{topology::Topology, Adjacency, FaceIndex, Position};
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, VecDeque},
+};
+
+/// How far apart two neighbouring faces or vertices are.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Metric {
+    /// Every step counts one, i.e. a breadth-first search.
+    Steps,
+    /// Steps are as long as the distance between the vertices or face
+    /// centroids they connect, i.e. Dijkstra's algorithm or, between
+    /// two faces or vertices, A*.
+    Euclidean,
+}
+
+/// A node waiting in the priority queue, ordered so the heap pops the
+/// lowest estimate first.
+#[derive(PartialEq)]
+struct Queued {
+    estimate: f64,
+    node: usize,
+}
+
+impl Eq for Queued {}
+
+impl Ord for Queued {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .total_cmp(&self.estimate)
+            .then(other.node.cmp(&self.node))
+    }
+}
+
+impl PartialOrd for Queued {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn distance(p: [f32; 3], q: [f32; 3]) -> f64 {
+    p.iter()
+        .zip(&q)
+        .map(|(&a, &b)| (a as f64 - b as f64).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+/// The distance of every node from the nearest of `sources` and the
+/// node it is reached from. With a `target`, the search stops once it
+/// is reached, and other distances may be missing.
+fn search<I: IntoIterator<Item = usize>>(
+    count: usize,
+    sources: &[usize],
+    target: Option<usize>,
+    metric: Metric,
+    neighbours: impl Fn(usize) -> I,
+    position: impl Fn(usize) -> [f32; 3],
+) -> (Vec<Option<f64>>, Vec<Option<usize>>) {
+    let mut distances = vec![None; count];
+    let mut previous = vec![None; count];
+
+    match metric {
+        Metric::Steps => {
+            let mut queue = VecDeque::new();
+            for &s in sources {
+                distances[s] = Some(0.0);
+                queue.push_back(s);
+            }
+            while let Some(node) = queue.pop_front() {
+                if Some(node) == target {
+                    break;
+                }
+                let d = distances[node].unwrap();
+                for next in neighbours(node) {
+                    if distances[next].is_none() {
+                        distances[next] = Some(d + 1.0);
+                        previous[next] = Some(node);
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+        Metric::Euclidean => {
+            // Straight-line distances never overestimate, so A* finds
+            // the shortest path.
+            let heuristic = |node: usize| match target {
+                Some(target) => distance(position(node), position(target)),
+                None => 0.0,
+            };
+            let mut settled = vec![false; count];
+            let mut queue = BinaryHeap::new();
+            for &s in sources {
+                distances[s] = Some(0.0);
+                queue.push(Queued {
+                    estimate: heuristic(s),
+                    node: s,
+                });
+            }
+            while let Some(Queued { node, .. }) = queue.pop() {
+                if settled[node] {
+                    continue;
+                }
+                settled[node] = true;
+                if Some(node) == target {
+                    break;
+                }
+                let d = distances[node].unwrap();
+                for next in neighbours(node) {
+                    let d = d + distance(position(node), position(next));
+                    if !matches!(distances[next], Some(current) if current <= d) {
+                        distances[next] = Some(d);
+                        previous[next] = Some(node);
+                        queue.push(Queued {
+                            estimate: d + heuristic(next),
+                            node: next,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    (distances, previous)
+}
+
+/// Follows `previous` back from `to`, if it was reached.
+fn trace(previous: &[Option<usize>], distances: &[Option<f64>], to: usize) -> Option<Vec<usize>> {
+    distances[to]?;
+    let mut path = vec![to];
+    while let Some(node) = previous[*path.last().unwrap()] {
+        path.push(node);
+    }
+    path.reverse();
+    Some(path)
+}
+
+fn centroids<P: Position>(points: &[P], face_index: &FaceIndex) -> Vec<[f32; 3]> {
+    face_index
+        .iter()
+        .map(|face| {
+            let sum = face.iter().fold([0.0; 3], |c, &v| {
+                let p = points[v as usize].xyz();
+                [c[0] + p[0], c[1] + p[1], c[2] + p[2]]
+            });
+            sum.map(|c| c / face.len() as f32)
+        })
+        .collect()
+}
+
+fn to_f32(distances: Vec<Option<f64>>) -> Vec<Option<f32>> {
+    distances.into_iter().map(|d| d.map(|d| d as f32)).collect()
+}
+
+pub(crate) fn face_path<P: Position>(
+    points: &[P],
+    face_index: &FaceIndex,
+    topology: &Topology,
+    from: usize,
+    to: usize,
+    adjacency: Adjacency,
+    metric: Metric,
+) -> Option<Vec<usize>> {
+    if face_index.len() <= from.max(to) {
+        return None;
+    }
+    let centroids = centroids(points, face_index);
+    let (distances, previous) = search(
+        face_index.len(),
+        &[from],
+        Some(to),
+        metric,
+        |f| topology.face_neighbours(f, adjacency).iter().copied(),
+        |f| centroids[f],
+    );
+    trace(&previous, &distances, to)
+}
+
+pub(crate) fn vertex_path<P: Position>(
+    points: &[P],
+    topology: &Topology,
+    from: u32,
+    to: u32,
+    metric: Metric,
+) -> Option<Vec<u32>> {
+    if points.len() <= from.max(to) as usize {
+        return None;
+    }
+    let (distances, previous) = search(
+        points.len(),
+        &[from as usize],
+        Some(to as usize),
+        metric,
+        |v| {
+            topology
+                .vertex_neighbours(v as u32)
+                .iter()
+                .map(|&w| w as usize)
+        },
+        |v| points[v].xyz(),
+    );
+    trace(&previous, &distances, to as usize)
+        .map(|path| path.into_iter().map(|v| v as u32).collect())
+}
+
+pub(crate) fn face_distances<P: Position>(
+    points: &[P],
+    face_index: &FaceIndex,
+    topology: &Topology,
+    sources: &[usize],
+    adjacency: Adjacency,
+    metric: Metric,
+) -> Vec<Option<f32>> {
+    let centroids = centroids(points, face_index);
+    let (distances, _) = search(
+        face_index.len(),
+        sources,
+        None,
+        metric,
+        |f| topology.face_neighbours(f, adjacency).iter().copied(),
+        |f| centroids[f],
+    );
+    to_f32(distances)
+}
+
+pub(crate) fn vertex_distances<P: Position>(
+    points: &[P],
+    face_index: &FaceIndex,
+    topology: &Topology,
+    sources: &[usize],
+    metric: Metric,
+) -> Vec<Option<f32>> {
+    let mut corners: Vec<usize> = sources
+        .iter()
+        .flat_map(|&f| face_index[f].iter().map(|&v| v as usize))
+        .collect();
+    corners.sort_unstable();
+    corners.dedup();
+
+    let (distances, _) = search(
+        points.len(),
+        &corners,
+        None,
+        metric,
+        |v| {
+            topology
+                .vertex_neighbours(v as u32)
+                .iter()
+                .map(|&w| w as usize)
+        },
+        |v| points[v].xyz(),
+    );
+    to_f32(distances)
+}
+
+#[test]
+fn paths_and_distances() {
+    use crate::{RegularTiling, SemiRegularTiling};
+
+    // 4 x 4 unit squares, face `x + 4 y` at `(x, y)`.
+    let squares = RegularTiling::square(5, 5);
+    let path = squares
+        .face_path(0, 15, Adjacency::Edge, Metric::Steps)
+        .unwrap();
+    assert_eq!((path[0], path.len(), path[6]), (0, 7, 15));
+    for step in path.windows(2) {
+        assert!(squares
+            .face_neighbours(step[0], Adjacency::Edge)
+            .contains(&step[1]));
+    }
+    let path = squares
+        .face_path(0, 15, Adjacency::Vertex, Metric::Euclidean)
+        .unwrap();
+    assert_eq!(path, [0, 5, 10, 15]);
+
+    let distances = squares.face_distances(&[0], Adjacency::Vertex, Metric::Euclidean);
+    assert!((distances[15].unwrap() - 3.0 * 2f32.sqrt()).abs() < 1.0e-5);
+    let steps = squares.face_distances(&[0, 15], Adjacency::Edge, Metric::Steps);
+    assert_eq!(steps[3], Some(3.0));
+    assert_eq!(steps[5], Some(2.0));
+
+    // The source square's corners are at zero, the opposite corner of
+    // the grid six edges away.
+    let distances = squares.vertex_distances(&[0], Metric::Steps);
+    assert_eq!(distances.iter().filter(|&&d| d == Some(0.0)).count(), 4);
+    assert_eq!(distances[24], Some(6.0));
+    assert_eq!(
+        squares.vertex_path(0, 24, Metric::Euclidean).unwrap().len(),
+        9
+    );
+    assert_eq!(
+        squares.face_path(0, 1000, Adjacency::Edge, Metric::Steps),
+        None
+    );
+    assert_eq!(squares.vertex_path(1000, 0, Metric::Euclidean), None);
+
+    // A* agrees with Dijkstra on the truncated square tiling.
+    let tiling = SemiRegularTiling::two(12, 12);
+    let distances = tiling.face_distances(&[0], Adjacency::Edge, Metric::Euclidean);
+    let last = (0..distances.len())
+        .max_by(|&f, &g| distances[f].partial_cmp(&distances[g]).unwrap())
+        .unwrap();
+    assert!(last > 0 && distances[last].is_some());
+    let path = tiling
+        .face_path(0, last, Adjacency::Edge, Metric::Euclidean)
+        .unwrap();
+    let centroids = centroids(tiling.points(), tiling.faces());
+    let length: f64 = path
+        .windows(2)
+        .map(|step| distance(centroids[step[0]], centroids[step[1]]))
+        .sum();
+    assert!((length as f32 - distances[last].unwrap()).abs() < 1.0e-4);
+
+    // Hexagons have six neighbours at the same distance.
+    let hexagons = RegularTiling::hexagon(12, 12);
+    let center = (0..hexagons.faces().len())
+        .find(|&f| hexagons.face_neighbours(f, Adjacency::Edge).len() == 6)
+        .unwrap();
+    let distances = hexagons.face_distances(&[center], Adjacency::Edge, Metric::Euclidean);
+    for &f in hexagons.face_neighbours(center, Adjacency::Edge) {
+        assert!((distances[f].unwrap() - 3f32.sqrt()).abs() < 1.0e-5);
+    }
+
+    // Faces that are not connected have no path.
+    let mesh = crate::Mesh::new(
+        "two",
+        squares.points().clone(),
+        vec![squares.faces()[0].clone(), squares.faces()[15].clone()],
+    );
+    assert_eq!(mesh.face_path(0, 1, Adjacency::Vertex, Metric::Steps), None);
+    assert_eq!(
+        mesh.face_distances(&[0], Adjacency::Edge, Metric::Euclidean),
+        [Some(0.0), None]
+    );
+}
@@ -0,0 +1,342 @@
+//! Paving patterns made of rectangular tiles.
+//!
+//! Lengths are in units of a brick's width or, for
+//! [`PaverPattern::Hopscotch`], the small square's side.
+use crate::{affine::DPoint, builder::MeshBuilder, Error, FaceIndex, Points};
+
+/// A paving pattern and its proportions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PaverPattern {
+    /// Courses of bricks of length `aspect`, each course shifted by
+    /// `offset` brick lengths against the one below. An `offset` of
+    /// `0.5` is the classic half bond, `0.0` a stack bond.
+    RunningBond { aspect: f32, offset: f32 },
+    /// Bricks of length `aspect` laid in a zigzag of alternating
+    /// horizontal and vertical bricks.
+    Herringbone { aspect: f32 },
+    /// Blocks of `bricks` parallel bricks of length `aspect`,
+    /// alternating between horizontal and vertical. Unless `aspect`
+    /// equals `bricks`, the blocks are not square and turn around
+    /// squares of side `|aspect - bricks|`, as in
+    /// [`Pinwheel`](Self::Pinwheel).
+    Basketweave { bricks: u32, aspect: f32 },
+    /// Four bricks of length `aspect` around a square whose side is
+    /// `aspect - 1`. Requires `aspect > 1`.
+    Pinwheel { aspect: f32 },
+    /// Squares of side `ratio` and unit squares, i.e. the Pythagorean
+    /// tiling.
+    Hopscotch { ratio: f32 },
+    /// The French or Versailles pattern made of squares and rectangles
+    /// of sizes `1 × 1`, `1 × 2`, `2 × 2` and `2 × 3` repeating in a
+    /// `6 × 6` block.
+    Versailles,
+}
+
+/// A rectangle given by its lower left and upper right corner.
+type Rectangle = [f64; 4];
+
+/// Four `a × b` rectangles, every other one turned by a right angle,
+/// around a square of side `|a - b|`, which is left out if empty.
+/// Repeats every `a + b` in both directions.
+fn pinwheel(a: f64, b: f64) -> Vec<Rectangle> {
+    let mut rectangles = vec![
+        [0.0, 0.0, a, b],
+        [a, 0.0, a + b, a],
+        [b, a, a + b, a + b],
+        [0.0, b, b, a + b],
+    ];
+    let (short, long) = (a.min(b), a.max(b));
+    if short < long {
+        rectangles.push([short, short, long, long]);
+    }
+    rectangles
+}
+
+impl PaverPattern {
+    /// The tiles of one repeat and the two vectors the repeat is
+    /// translated by.
+    fn motif(self) -> Result<(Vec<Rectangle>, [DPoint; 2]), Error> {
+        let positive = |x: f32| x.is_finite() && x > 0.0;
+
+        Ok(match self {
+            PaverPattern::RunningBond { aspect, offset } => {
+                if !positive(aspect) {
+                    return Err(Error::Constraint("aspect > 0"));
+                }
+                if !offset.is_finite() {
+                    return Err(Error::Constraint("offset is finite"));
+                }
+                let (l, o) = (aspect as f64, offset as f64);
+                (vec![[0.0, 0.0, l, 1.0]], [[l, 0.0], [o * l, 1.0]])
+            }
+            PaverPattern::Herringbone { aspect } => {
+                if !(positive(aspect) && aspect >= 1.0) {
+                    return Err(Error::Constraint("aspect ≥ 1"));
+                }
+                let l = aspect as f64;
+                (
+                    vec![[0.0, 0.0, l, 1.0], [0.0, 1.0, 1.0, 1.0 + l]],
+                    [[1.0, 1.0], [l, -l]],
+                )
+            }
+            PaverPattern::Basketweave { bricks, aspect } => {
+                if bricks == 0 {
+                    return Err(Error::Constraint("bricks > 0"));
+                }
+                if !positive(aspect) {
+                    return Err(Error::Constraint("aspect > 0"));
+                }
+                let (l, n) = (aspect as f64, bricks as f64);
+                let mut tiles = Vec::new();
+                for (k, [x0, y0, x1, y1]) in pinwheel(l, n).into_iter().enumerate() {
+                    match k {
+                        // Horizontal and vertical blocks alternate.
+                        0 | 2 => tiles.extend((0..bricks).map(|i| {
+                            let y = y0 + i as f64;
+                            [x0, y, x1, y + 1.0]
+                        })),
+                        1 | 3 => tiles.extend((0..bricks).map(|i| {
+                            let x = x0 + i as f64;
+                            [x, y0, x + 1.0, y1]
+                        })),
+                        _ => tiles.push([x0, y0, x1, y1]),
+                    }
+                }
+                (tiles, [[l + n, 0.0], [0.0, l + n]])
+            }
+            PaverPattern::Pinwheel { aspect } => {
+                if !(positive(aspect) && aspect > 1.0) {
+                    return Err(Error::Constraint("aspect > 1"));
+                }
+                let l = aspect as f64;
+                (pinwheel(l, 1.0), [[l + 1.0, 0.0], [0.0, l + 1.0]])
+            }
+            PaverPattern::Hopscotch { ratio } => {
+                if !positive(ratio) {
+                    return Err(Error::Constraint("ratio > 0"));
+                }
+                let a = ratio as f64;
+                (
+                    vec![[0.0, 0.0, a, a], [a, 0.0, a + 1.0, 1.0]],
+                    [[a, 1.0], [-1.0, a]],
+                )
+            }
+            PaverPattern::Versailles => (
+                vec![
+                    [0.0, 0.0, 3.0, 2.0],
+                    [3.0, 0.0, 5.0, 2.0],
+                    [5.0, 0.0, 6.0, 2.0],
+                    [0.0, 2.0, 2.0, 4.0],
+                    [2.0, 2.0, 4.0, 5.0],
+                    [4.0, 2.0, 6.0, 4.0],
+                    [0.0, 4.0, 1.0, 6.0],
+                    [1.0, 4.0, 2.0, 5.0],
+                    [1.0, 5.0, 2.0, 6.0],
+                    [2.0, 5.0, 4.0, 6.0],
+                    [4.0, 4.0, 5.0, 6.0],
+                    [5.0, 4.0, 6.0, 5.0],
+                    [5.0, 5.0, 6.0, 6.0],
+                ],
+                [[6.0, 0.0], [0.0, 6.0]],
+            ),
+        })
+    }
+}
+
+/// A patch of a paving pattern.
+///
+/// Paving patterns are not edge-to-edge: a tile's corner usually lies
+/// on the side of a neighbouring tile. Such corners are inserted into
+/// the neighbour's face, so faces may have more than four vertices but
+/// every interior edge is shared by exactly two faces.
+///
+/// With a grout joint, tiles no longer touch and every face is a
+/// rectangle of its own.
+pub struct PaverTiling {
+    face_index: FaceIndex,
+    points: Points,
+    name: String,
+    topology: crate::topology::TopologyCache,
+    classes: crate::class::ClassCache,
+}
+
+impl PaverTiling {
+    default_methods! {}
+
+    /// Creates a patch of `pattern` with every tile whose center lies
+    /// in the rectangle from the origin to `(width, height)`.
+    ///
+    /// Tile sizes are nominal, i.e. include the grout: every tile
+    /// shrinks by half the `joint` width on each side, leaving joints
+    /// of that width between tiles. A `joint` of `0.0` yields tiles
+    /// that share their sides.
+    ///
+    /// # Errors
+    /// Returns [`Error::Constraint`] if the pattern's proportions are
+    /// out of range or the `joint` is negative or not narrower than
+    /// every tile.
+    pub fn new(pattern: PaverPattern, joint: f32, width: f32, height: f32) -> Result<Self, Error> {
+        let (tiles, [u, v]) = pattern.motif()?;
+        if !(joint.is_finite() && joint >= 0.0) {
+            return Err(Error::Constraint("joint ≥ 0"));
+        }
+        let joint = joint as f64;
+        if tiles
+            .iter()
+            .any(|&[x0, y0, x1, y1]| joint >= (x1 - x0).min(y1 - y0))
+        {
+            return Err(Error::Constraint("joint < tile width"));
+        }
+        let (width, height) = (width as f64, height as f64);
+
+        // Lattice coordinates of the rectangle's corners bound the
+        // repeats that can contribute tiles.
+        let det = u[0] * v[1] - u[1] * v[0];
+        let (mut min, mut max) = ([f64::MAX; 2], [f64::MIN; 2]);
+        for &[x, y] in &[[0.0, 0.0], [width, 0.0], [0.0, height], [width, height]] {
+            let coordinates = [(x * v[1] - y * v[0]) / det, (u[0] * y - u[1] * x) / det];
+            for k in 0..2 {
+                min[k] = min[k].min(coordinates[k]);
+                max[k] = max[k].max(coordinates[k]);
+            }
+        }
+
+        // Tiles of the same size and orientation share a class.
+        let shapes: Vec<[i64; 2]> = tiles
+            .iter()
+            .map(|&[x0, y0, x1, y1]| [x1 - x0, y1 - y0].map(|d| (d * 1e6).round() as i64))
+            .collect();
+        let kinds = crate::class::rank(&shapes);
+
+        let inside = |p: DPoint| p[0] >= 0.0 && p[1] >= 0.0 && p[0] <= width && p[1] <= height;
+
+        let mut builder = MeshBuilder::new();
+        let mut classes = Vec::new();
+        for i in min[0].floor() as i64 - 1..=max[0].ceil() as i64 + 1 {
+            for j in min[1].floor() as i64 - 1..=max[1].ceil() as i64 + 1 {
+                let offset = [
+                    i as f64 * u[0] + j as f64 * v[0],
+                    i as f64 * u[1] + j as f64 * v[1],
+                ];
+                for (&[x0, y0, x1, y1], &kind) in tiles.iter().zip(&kinds) {
+                    let (x0, y0) = (x0 + offset[0], y0 + offset[1]);
+                    let (x1, y1) = (x1 + offset[0], y1 + offset[1]);
+                    if inside([(x0 + x1) / 2.0, (y0 + y1) / 2.0]) {
+                        let (x0, y0) = (x0 + joint / 2.0, y0 + joint / 2.0);
+                        let (x1, y1) = (x1 - joint / 2.0, y1 - joint / 2.0);
+                        builder.polygon(&[[x0, y0], [x1, y0], [x1, y1], [x0, y1]]);
+                        classes.push(kind);
+                    }
+                }
+            }
+        }
+        builder.split_t_junctions();
+        let (points, face_index) = builder.build();
+
+        Ok(Self {
+            face_index,
+            points,
+            name: match pattern {
+                PaverPattern::RunningBond { .. } => "RUNNING-BOND",
+                PaverPattern::Herringbone { .. } => "HERRINGBONE",
+                PaverPattern::Basketweave { .. } => "BASKETWEAVE",
+                PaverPattern::Pinwheel { .. } => "PINWHEEL",
+                PaverPattern::Hopscotch { .. } => "HOPSCOTCH",
+                PaverPattern::Versailles => "VERSAILLES",
+            }
+            .to_string(),
+            topology: Default::default(),
+            classes: classes.into(),
+        })
+    }
+}
+
+#[test]
+fn paver() {
+    use crate::{polygon, Point};
+    use std::collections::HashSet;
+
+    for &pattern in &[
+        PaverPattern::RunningBond {
+            aspect: 2.0,
+            offset: 0.5,
+        },
+        PaverPattern::RunningBond {
+            aspect: 3.0,
+            offset: 1.0 / 3.0,
+        },
+        PaverPattern::Herringbone { aspect: 2.0 },
+        PaverPattern::Herringbone { aspect: 3.5 },
+        PaverPattern::Basketweave {
+            bricks: 3,
+            aspect: 3.0,
+        },
+        PaverPattern::Basketweave {
+            bricks: 2,
+            aspect: 3.0,
+        },
+        PaverPattern::Pinwheel { aspect: 2.5 },
+        PaverPattern::Hopscotch { ratio: 2.0 },
+        PaverPattern::Versailles,
+    ] {
+        let tiling = PaverTiling::new(pattern, 0.0, 30.0, 30.0).unwrap();
+        let polygons = polygon::face_polygons(tiling.points(), tiling.faces());
+
+        // Every point well inside the patch is covered exactly once.
+        polygon::covered_once(&polygons, [10.0, 10.0], 10.0, 40)
+            .unwrap_or_else(|p| panic!("{:?} at {:?}", pattern, p));
+
+        // No T-junctions are left: an edge without a twin lies on the
+        // patch boundary.
+        for (a, b) in polygon::boundary_edges(tiling.faces()) {
+            let (pa, pb) = (tiling.points()[a as usize], tiling.points()[b as usize]);
+            let outside = (pa + pb) * 0.5 + Point::new(pb.y - pa.y, pa.x - pb.x) * 0.01;
+            let outside = [outside.x as f64, outside.y as f64];
+            assert!(
+                polygons
+                    .iter()
+                    .all(|face| !polygon::contains(face, outside)),
+                "{:?}",
+                pattern
+            );
+        }
+
+        // A class per tile size and orientation.
+        let classes: HashSet<_> = tiling.classes().iter().collect();
+        let expected = match pattern {
+            PaverPattern::RunningBond { .. } => 1,
+            PaverPattern::Pinwheel { .. } => 3,
+            PaverPattern::Basketweave { bricks, aspect } if bricks as f32 != aspect => 3,
+            PaverPattern::Versailles => 6,
+            _ => 2,
+        };
+        assert_eq!(classes.len(), expected, "{:?}", pattern);
+    }
+
+    // Joints leave the same tiles, shrunk and apart.
+    let pattern = PaverPattern::Herringbone { aspect: 2.0 };
+    let flush = PaverTiling::new(pattern, 0.0, 20.0, 20.0).unwrap();
+    let grouted = PaverTiling::new(pattern, 0.1, 20.0, 20.0).unwrap();
+    assert_eq!(grouted.faces().len(), flush.faces().len());
+    assert_eq!(grouted.classes(), flush.classes());
+    assert!(grouted.faces().iter().all(|face| face.len() == 4));
+    assert!(grouted.edges().iter().all(|edge| edge.is_boundary()));
+    for face in grouted.faces() {
+        let [a, _, c, _] = [0, 1, 2, 3].map(|i| grouted.points()[face[i] as usize]);
+        let (w, h) = ((c.x - a.x).abs(), (c.y - a.y).abs());
+        assert!((w.min(h) - 0.9).abs() < 1e-5 && (w.max(h) - 1.9).abs() < 1e-5);
+    }
+
+    assert_eq!(
+        PaverTiling::new(PaverPattern::Pinwheel { aspect: 1.0 }, 0.0, 4.0, 4.0).err(),
+        Some(Error::Constraint("aspect > 1"))
+    );
+    assert_eq!(
+        PaverTiling::new(pattern, -0.1, 4.0, 4.0).err(),
+        Some(Error::Constraint("joint ≥ 0"))
+    );
+    assert_eq!(
+        PaverTiling::new(pattern, 1.0, 4.0, 4.0).err(),
+        Some(Error::Constraint("joint < tile width"))
+    );
+}
@@ -0,0 +1,938 @@
+//! Monohedral tilings by convex pentagons.
+//!
+//! Corners are labelled `A` to `E` counter-clockwise and sides follow
+//! the usual convention: `a = EA`, `b = AB`, `c = BC`, `d = CD` and
+//! `e = DE`.
+use crate::{
+    affine::{self, Affine, DPoint},
+    builder,
+    isohedral::{self, EdgePairing},
+    polygon, Error, FaceIndex, Point, Points,
+};
+use std::{f32::consts::FRAC_PI_2, f64::consts::PI};
+
+/// Angles and lengths may be off by this much and still satisfy a
+/// constraint.
+const TOLERANCE: f64 = 1.0e-3;
+
+/// A tile of a cluster, placed by mapping corners `corner` and
+/// `corner + 1` of the pentagon, mirrored if `reflected`, onto corners
+/// of tiles placed before it, given as `(tile, corner)`.
+type Placement = (usize, bool, [(usize, usize); 2]);
+
+/// A period of a cluster, as the vector from one `(tile, corner)` to
+/// another.
+type Period = [(usize, usize); 2];
+
+/// The 15 families of convex pentagons that tile the plane, named by
+/// their number in the
+/// [classification](https://en.wikipedia.org/wiki/Pentagonal_tiling#Monohedral_convex_pentagonal_tilings).
+///
+/// Types 1 to 5 tile isohedrally (type 1 in general 2-isohedrally).
+/// Types 6 to 15 only tile `k`-isohedrally, with `k` of 3 to 12, and
+/// are laid out as a cluster of tiles repeated by translation.
+///
+/// [`pentagon()`](Self::pentagon) constructs a pentagon of each type
+/// from its free angles, in radians, and side lengths. Side `c` of
+/// types 1 and 2, `b` of types 3 to 5 and 7 to 10, `d` of types 11 and
+/// 12 and `a` of the others has unit length; types 6 to 9 and 14 have
+/// one angle less free than their angle conditions suggest, which is
+/// solved for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PentagonalType {
+    /// `B + C = 180°`. Parameters: `A`, `B`, `D`, `b` and `d`.
+    One,
+    /// `c = e`, `B + D = 180°`. Parameters: `A`, `B`, `C` and `d`.
+    Two,
+    /// `a = b`, `d = c + e`, `A = C = D = 120°`. Parameters: `B`.
+    Three,
+    /// `b = c`, `d = e`, `B = D = 90°`. Parameters: `A` and `C`.
+    Four,
+    /// `a = b`, `d = e`, `A = 60°`, `D = 120°`. Parameters: `B` and
+    /// `C`.
+    Five,
+    /// `a = d = e`, `b = c`, `B + D = 180°`, `2B = E`. Parameters: `B`.
+    Six,
+    /// `b = c = d = e`, `B + 2E = 2C + D = 360°`. Parameters: `C`.
+    Seven,
+    /// `b = c = d = e`, `2B + C = D + 2E = 360°`. Parameters: `B`.
+    Eight,
+    /// `b = c = d = e`, `2A + C = D + 2E = 360°`. Parameters: `A`.
+    Nine,
+    /// `a = b = c + e`, `A = 90°`, `B + E = 180°`, `B + 2C = 360°`.
+    /// Parameters: `B`.
+    Ten,
+    /// `2a + c = d = e`, `A = 90°`, `2B + C = 360°`, `C + E = 180°`.
+    /// Parameters: `B`.
+    Eleven,
+    /// `2a = d = c + e`, `A = 90°`, `2B + C = 360°`, `C + E = 180°`.
+    /// Parameters: `B`.
+    Twelve,
+    /// `d = 2a = 2e`, `B = E = 90°`, `2A + D = 360°`. Parameters: `A`.
+    Thirteen,
+    /// `2a = 2c = d = e`, `A = 90°`, `2B + C = 360°`, `C + E = 180°`.
+    /// The pentagon is unique up to similarity.
+    Fourteen,
+    /// `a = c = e`, `b = 2a`, `d = a√2 / (√3 - 1)`, `A = 150°`,
+    /// `B = 60°`, `C = 135°`, `D = 105°`, `E = 90°`. The pentagon is
+    /// unique up to similarity.
+    Fifteen,
+}
+
+impl PentagonalType {
+    /// All types.
+    pub const ALL: [PentagonalType; 15] = [
+        PentagonalType::One,
+        PentagonalType::Two,
+        PentagonalType::Three,
+        PentagonalType::Four,
+        PentagonalType::Five,
+        PentagonalType::Six,
+        PentagonalType::Seven,
+        PentagonalType::Eight,
+        PentagonalType::Nine,
+        PentagonalType::Ten,
+        PentagonalType::Eleven,
+        PentagonalType::Twelve,
+        PentagonalType::Thirteen,
+        PentagonalType::Fourteen,
+        PentagonalType::Fifteen,
+    ];
+
+    /// A pentagon satisfying the type's constraints, made from the
+    /// [default parameters](Self::default_parameters). For type 4 it
+    /// is the Cairo pentagon.
+    pub fn example(self) -> [Point; 5] {
+        self.pentagon(self.default_parameters()).unwrap()
+    }
+
+    /// The number of free parameters of the pentagon.
+    pub fn parameter_count(self) -> usize {
+        self.default_parameters().len()
+    }
+
+    /// Parameters that give a nice looking pentagon.
+    pub fn default_parameters(self) -> &'static [f32] {
+        match self {
+            PentagonalType::One => &[2.2455, FRAC_PI_2, 2.4469, 1.0, 0.8],
+            PentagonalType::Two => &[2.2539, 1.6581, 1.7359, 1.2166],
+            PentagonalType::Three | PentagonalType::Ten => &[1.7453],
+            PentagonalType::Four => &[2.0944, 2.0944],
+            PentagonalType::Five => &[2.4052, 1.3384],
+            PentagonalType::Six => &[1.2217],
+            PentagonalType::Seven => &[2.4435],
+            PentagonalType::Eight => &[2.2689],
+            PentagonalType::Nine | PentagonalType::Thirteen => &[1.9199],
+            PentagonalType::Eleven | PentagonalType::Twelve => &[2.5656],
+            PentagonalType::Fourteen | PentagonalType::Fifteen => &[],
+        }
+    }
+
+    /// Constructs the pentagon of this type with the given parameters,
+    /// with `B` at the origin and `C` on the positive x-axis.
+    ///
+    /// # Errors
+    /// Returns [`Error::ParameterCount`] if `parameters` does not have
+    /// [`parameter_count()`](Self::parameter_count) entries and
+    /// [`Error::DegenerateTile`] if no convex pentagon of this type has
+    /// them.
+    pub fn pentagon(self, parameters: &[f32]) -> Result<[Point; 5], Error> {
+        if parameters.len() != self.parameter_count() {
+            return Err(Error::ParameterCount {
+                expected: self.parameter_count(),
+                found: parameters.len(),
+            });
+        }
+
+        let parameters: Vec<f64> = parameters.iter().map(|&p| p as f64).collect();
+        let corners = self.corners(&parameters).ok_or(Error::DegenerateTile)?;
+        self.validate(&corners)?;
+        Ok(corners.map(|[x, y]| Point::new(x as _, y as _)))
+    }
+
+    /// The interior angles `A` to `E` for the parameters `p`, and linear
+    /// equations `(coefficients, value)` on the side lengths
+    /// `[a, b, c, d, e]` that fix the pentagon together with the
+    /// condition that its sides close up.
+    ///
+    /// Where one more angle is fixed than the angle conditions say, `x`
+    /// is that angle and there is a fourth equation, which only holds
+    /// for the right `x`.
+    fn shape(self, p: &[f64], x: f64) -> ([f64; 5], Vec<([f64; 5], f64)>) {
+        let (a, b, c, d, e) = (0, 1, 2, 3, 4);
+        let side = |i: usize, length: f64| {
+            let mut row = [0.0; 5];
+            row[i] = 1.0;
+            (row, length)
+        };
+        let equal = |i: usize, j: usize| {
+            let mut row = [0.0; 5];
+            row[i] = 1.0;
+            row[j] = -1.0;
+            (row, 0.0)
+        };
+        let unit_sides = vec![side(b, 1.0), side(c, 1.0), side(d, 1.0), side(e, 1.0)];
+        // The angles of types 11, 12 and 14.
+        let right_at_a = |b: f64| [PI / 2.0, b, 2.0 * PI - 2.0 * b, 1.5 * PI - b, 2.0 * b - PI];
+
+        match self {
+            PentagonalType::One => (
+                [p[0], p[1], PI - p[1], p[2], 2.0 * PI - p[0] - p[2]],
+                vec![side(c, 1.0), side(b, p[3]), side(d, p[4])],
+            ),
+            PentagonalType::Two => (
+                [p[0], p[1], p[2], PI - p[1], 2.0 * PI - p[0] - p[2]],
+                vec![side(c, 1.0), equal(c, e), side(d, p[3])],
+            ),
+            PentagonalType::Three => (
+                [
+                    2.0 * PI / 3.0,
+                    p[0],
+                    2.0 * PI / 3.0,
+                    2.0 * PI / 3.0,
+                    PI - p[0],
+                ],
+                vec![
+                    side(b, 1.0),
+                    equal(a, b),
+                    ([0.0, 0.0, -1.0, 1.0, -1.0], 0.0),
+                ],
+            ),
+            PentagonalType::Four => (
+                [p[0], PI / 2.0, p[1], PI / 2.0, 2.0 * PI - p[0] - p[1]],
+                vec![side(b, 1.0), equal(b, c), equal(d, e)],
+            ),
+            PentagonalType::Five => (
+                [PI / 3.0, p[0], p[1], 2.0 * PI / 3.0, 2.0 * PI - p[0] - p[1]],
+                vec![side(b, 1.0), equal(a, b), equal(d, e)],
+            ),
+            PentagonalType::Six => (
+                [2.0 * PI - 2.0 * p[0] - x, p[0], x, PI - p[0], 2.0 * p[0]],
+                vec![side(a, 1.0), equal(a, d), equal(a, e), equal(b, c)],
+            ),
+            PentagonalType::Seven => (
+                [p[0] - x / 2.0, x, p[0], 2.0 * PI - 2.0 * p[0], PI - x / 2.0],
+                unit_sides,
+            ),
+            PentagonalType::Eight => (
+                [
+                    p[0] + x - PI,
+                    p[0],
+                    2.0 * PI - 2.0 * p[0],
+                    2.0 * PI - 2.0 * x,
+                    x,
+                ],
+                unit_sides,
+            ),
+            PentagonalType::Nine => (
+                [
+                    p[0],
+                    p[0] + x - PI,
+                    2.0 * PI - 2.0 * p[0],
+                    2.0 * PI - 2.0 * x,
+                    x,
+                ],
+                unit_sides,
+            ),
+            PentagonalType::Ten => (
+                [
+                    PI / 2.0,
+                    p[0],
+                    PI - p[0] / 2.0,
+                    (PI + p[0]) / 2.0,
+                    PI - p[0],
+                ],
+                vec![
+                    side(b, 1.0),
+                    equal(a, b),
+                    ([0.0, 1.0, -1.0, 0.0, -1.0], 0.0),
+                ],
+            ),
+            PentagonalType::Eleven => (
+                right_at_a(p[0]),
+                vec![side(d, 1.0), equal(d, e), ([2.0, 0.0, 1.0, -1.0, 0.0], 0.0)],
+            ),
+            PentagonalType::Twelve => (
+                right_at_a(p[0]),
+                vec![
+                    side(d, 1.0),
+                    ([2.0, 0.0, 0.0, -1.0, 0.0], 0.0),
+                    ([0.0, 0.0, -1.0, 1.0, -1.0], 0.0),
+                ],
+            ),
+            PentagonalType::Thirteen => (
+                [p[0], PI / 2.0, p[0], 2.0 * PI - 2.0 * p[0], PI / 2.0],
+                vec![side(a, 1.0), equal(a, e), ([2.0, 0.0, 0.0, -1.0, 0.0], 0.0)],
+            ),
+            PentagonalType::Fourteen => (
+                right_at_a(x),
+                vec![
+                    side(a, 1.0),
+                    equal(a, c),
+                    ([2.0, 0.0, 0.0, -1.0, 0.0], 0.0),
+                    equal(d, e),
+                ],
+            ),
+            // `b` and `d` follow from the angles.
+            PentagonalType::Fifteen => (
+                [150.0, 60.0, 135.0, 105.0, 90.0].map(|angle: f64| angle.to_radians()),
+                vec![side(a, 1.0), equal(a, c), equal(a, e)],
+            ),
+        }
+    }
+
+    /// The corners of the pentagon with the parameters `p`, if it is
+    /// convex.
+    fn corners(self, p: &[f64]) -> Option<[DPoint; 5]> {
+        let solve = |x: f64| {
+            let (angles, equations) = self.shape(p, x);
+            close(&angles, &equations)
+        };
+        if self.shape(p, 0.0).1.len() < 4 {
+            return solve(0.0).map(|(corners, _)| corners);
+        }
+
+        // Bisect the first sign change of the fourth equation among the
+        // angles that give a convex pentagon.
+        const STEPS: usize = 720;
+        let mut previous: Option<(f64, f64)> = None;
+        for i in 1..STEPS {
+            let x = PI * i as f64 / STEPS as f64;
+            let residual = solve(x).map(|(_, residual)| residual);
+            if let (Some((mut low, low_residual)), Some(residual)) = (previous, residual) {
+                if (low_residual < 0.0) != (residual < 0.0) {
+                    let mut high = x;
+                    for _ in 0..60 {
+                        let middle = (low + high) / 2.0;
+                        match solve(middle) {
+                            Some((_, r)) if (r < 0.0) == (low_residual < 0.0) => low = middle,
+                            _ => high = middle,
+                        }
+                    }
+                    return solve(low).map(|(corners, _)| corners);
+                }
+            }
+            previous = residual.map(|residual| (x, residual));
+        }
+        None
+    }
+
+    /// Checks the type's side and angle constraints.
+    fn validate(self, corners: &[DPoint; 5]) -> Result<(), Error> {
+        let side = |i: usize| {
+            // Side `a` ends at `A`, i.e. side `i` runs from corner
+            // `i - 1` to corner `i`.
+            let p = corners[(i + 4) % 5];
+            let q = corners[i];
+            ((q[0] - p[0]).powi(2) + (q[1] - p[1]).powi(2)).sqrt()
+        };
+        let angle = |i: usize| interior_angle(corners, i);
+        let equal = |x: f64, y: f64| (x - y).abs() < TOLERANCE * x.abs().max(y.abs()).max(1.0);
+
+        let (a, b, c, d, e) = (0, 1, 2, 3, 4);
+        let (ca, cb, cc, cd, ce) = (0, 1, 2, 3, 4);
+        let degrees = |angle: f64| angle.to_radians();
+
+        let constraints: &[(bool, &'static str)] = match self {
+            PentagonalType::One => &[(equal(angle(cb) + angle(cc), PI), "B + C = 180°")],
+            PentagonalType::Two => &[
+                (equal(side(c), side(e)), "c = e"),
+                (equal(angle(cb) + angle(cd), PI), "B + D = 180°"),
+            ],
+            PentagonalType::Three => &[
+                (equal(side(a), side(b)), "a = b"),
+                (equal(side(d), side(c) + side(e)), "d = c + e"),
+                (equal(angle(ca), degrees(120.0)), "A = 120°"),
+                (equal(angle(cc), degrees(120.0)), "C = 120°"),
+                (equal(angle(cd), degrees(120.0)), "D = 120°"),
+            ],
+            PentagonalType::Four => &[
+                (equal(side(b), side(c)), "b = c"),
+                (equal(side(d), side(e)), "d = e"),
+                (equal(angle(cb), PI / 2.0), "B = 90°"),
+                (equal(angle(cd), PI / 2.0), "D = 90°"),
+            ],
+            PentagonalType::Five => &[
+                (equal(side(a), side(b)), "a = b"),
+                (equal(side(d), side(e)), "d = e"),
+                (equal(angle(ca), PI / 3.0), "A = 60°"),
+                (equal(angle(cd), 2.0 * PI / 3.0), "D = 120°"),
+            ],
+            PentagonalType::Six => &[
+                (equal(side(a), side(d)), "a = d"),
+                (equal(side(a), side(e)), "a = e"),
+                (equal(side(b), side(c)), "b = c"),
+                (equal(angle(cb) + angle(cd), PI), "B + D = 180°"),
+                (equal(2.0 * angle(cb), angle(ce)), "2B = E"),
+            ],
+            PentagonalType::Seven | PentagonalType::Eight | PentagonalType::Nine => {
+                let sides = [
+                    (equal(side(b), side(c)), "b = c"),
+                    (equal(side(b), side(d)), "b = d"),
+                    (equal(side(b), side(e)), "b = e"),
+                ];
+                let angles = match self {
+                    PentagonalType::Seven => [
+                        (
+                            equal(angle(cb) + 2.0 * angle(ce), 2.0 * PI),
+                            "B + 2E = 360°",
+                        ),
+                        (
+                            equal(2.0 * angle(cc) + angle(cd), 2.0 * PI),
+                            "2C + D = 360°",
+                        ),
+                    ],
+                    PentagonalType::Eight => [
+                        (
+                            equal(2.0 * angle(cb) + angle(cc), 2.0 * PI),
+                            "2B + C = 360°",
+                        ),
+                        (
+                            equal(angle(cd) + 2.0 * angle(ce), 2.0 * PI),
+                            "D + 2E = 360°",
+                        ),
+                    ],
+                    _ => [
+                        (
+                            equal(2.0 * angle(ca) + angle(cc), 2.0 * PI),
+                            "2A + C = 360°",
+                        ),
+                        (
+                            equal(angle(cd) + 2.0 * angle(ce), 2.0 * PI),
+                            "D + 2E = 360°",
+                        ),
+                    ],
+                };
+                return check(sides.iter().chain(&angles));
+            }
+            PentagonalType::Ten => &[
+                (equal(side(a), side(b)), "a = b"),
+                (equal(side(b), side(c) + side(e)), "b = c + e"),
+                (equal(angle(ca), PI / 2.0), "A = 90°"),
+                (equal(angle(cb) + angle(ce), PI), "B + E = 180°"),
+                (
+                    equal(angle(cb) + 2.0 * angle(cc), 2.0 * PI),
+                    "B + 2C = 360°",
+                ),
+            ],
+            PentagonalType::Eleven | PentagonalType::Twelve | PentagonalType::Fourteen => {
+                let sides: &[(bool, &'static str)] = match self {
+                    PentagonalType::Eleven => &[
+                        (equal(2.0 * side(a) + side(c), side(d)), "2a + c = d"),
+                        (equal(side(d), side(e)), "d = e"),
+                    ],
+                    PentagonalType::Twelve => &[
+                        (equal(2.0 * side(a), side(d)), "2a = d"),
+                        (equal(side(d), side(c) + side(e)), "d = c + e"),
+                    ],
+                    _ => &[
+                        (equal(side(a), side(c)), "2a = 2c"),
+                        (equal(2.0 * side(a), side(d)), "2a = d"),
+                        (equal(side(d), side(e)), "d = e"),
+                    ],
+                };
+                let angles = [
+                    (equal(angle(ca), PI / 2.0), "A = 90°"),
+                    (
+                        equal(2.0 * angle(cb) + angle(cc), 2.0 * PI),
+                        "2B + C = 360°",
+                    ),
+                    (equal(angle(cc) + angle(ce), PI), "C + E = 180°"),
+                ];
+                return check(sides.iter().chain(&angles));
+            }
+            PentagonalType::Thirteen => &[
+                (equal(side(d), 2.0 * side(a)), "d = 2a"),
+                (equal(side(a), side(e)), "a = e"),
+                (equal(angle(cb), PI / 2.0), "B = 90°"),
+                (equal(angle(ce), PI / 2.0), "E = 90°"),
+                (
+                    equal(2.0 * angle(ca) + angle(cd), 2.0 * PI),
+                    "2A + D = 360°",
+                ),
+            ],
+            PentagonalType::Fifteen => &[
+                (equal(side(a), side(c)), "a = c"),
+                (equal(side(a), side(e)), "a = e"),
+                (equal(side(b), 2.0 * side(a)), "b = 2a"),
+                (
+                    equal(side(d), side(a) * 2f64.sqrt() / (3f64.sqrt() - 1.0)),
+                    "d = a√2 / (√3 - 1)",
+                ),
+                (equal(angle(ca), degrees(150.0)), "A = 150°"),
+                (equal(angle(cb), degrees(60.0)), "B = 60°"),
+                (equal(angle(cc), degrees(135.0)), "C = 135°"),
+                (equal(angle(cd), degrees(105.0)), "D = 105°"),
+            ],
+        };
+
+        check(constraints)
+    }
+
+    /// Transforms, in the frame of a tile, that generate the symmetry
+    /// group of an isohedral tiling.
+    fn generators(self, corners: &[DPoint; 5]) -> Vec<Affine> {
+        use EdgePairing::*;
+
+        // Edge `i` runs from corner `i` to corner `i + 1`, so edge `0`
+        // is side `b`.
+        let pairings: &[EdgePairing] = match self {
+            PentagonalType::One => {
+                // The tile and its half-turn about the midpoint of `BC`
+                // form a centrally symmetric hexagon that tiles by
+                // translation.
+                let m = [corners[1][0] + corners[2][0], corners[1][1] + corners[2][1]];
+                let turn = |p: DPoint| [m[0] - p[0], m[1] - p[1]];
+                let hexagon = [
+                    corners[0],
+                    turn(corners[3]),
+                    turn(corners[4]),
+                    turn(corners[0]),
+                    corners[3],
+                    corners[4],
+                ];
+                let translation =
+                    |p: DPoint, q: DPoint| affine::translation(p[0] - q[0], p[1] - q[1]);
+
+                // Both directions of each translation are needed, as
+                // the patch only grows through tiles inside it.
+                return vec![
+                    [-1.0, 0.0, m[0], 0.0, -1.0, m[1]],
+                    translation(hexagon[1], hexagon[3]),
+                    translation(hexagon[3], hexagon[1]),
+                    translation(hexagon[2], hexagon[4]),
+                    translation(hexagon[4], hexagon[2]),
+                ];
+            }
+            PentagonalType::Two => {
+                // Sides `c` and `e` are glued by a glide reflection and
+                // side `a` by a half-turn. Sides `b` and `d` of a glued
+                // pair form one straight side as `B + D = 180°`, so they
+                // do not meet other tiles edge-to-edge in general. Their
+                // entries below are never used.
+                let pairings = [HalfTurn, Glide(3), HalfTurn, Glide(1), HalfTurn];
+                return [1, 3, 4]
+                    .iter()
+                    .map(|&i| isohedral::neighbor(corners, &pairings, i))
+                    .collect();
+            }
+            // Three tiles meet at each of `A`, `C` and `D`, and turning
+            // about two of them generates the turns about the third.
+            PentagonalType::Three => {
+                return [0, 3]
+                    .iter()
+                    .map(|&i| affine::rotation_about(corners[i], 2.0 * PI / 3.0))
+                    .collect();
+            }
+            PentagonalType::Four => &[Rotation(1), Rotation(0), Rotation(3), Rotation(2), HalfTurn],
+            PentagonalType::Five => &[Rotation(4), HalfTurn, Rotation(3), Rotation(2), Rotation(0)],
+            _ => unreachable!("{:?} tilings are grown from a cluster", self),
+        };
+
+        (0..5)
+            .map(|i| isohedral::neighbor(corners, pairings, i))
+            .collect()
+    }
+
+    /// For types that only tile `k`-isohedrally, how to place the
+    /// other tiles of a cluster of `k` tiles around the given one, and
+    /// the periods by which the cluster tiles the plane.
+    fn cluster(self) -> Option<(&'static [Placement], [Period; 2])> {
+        Some(match self {
+            PentagonalType::One
+            | PentagonalType::Two
+            | PentagonalType::Three
+            | PentagonalType::Four
+            | PentagonalType::Five => return None,
+            PentagonalType::Six => (
+                &[
+                    (2, false, [(0, 4), (0, 3)]),
+                    (3, false, [(1, 4), (0, 3)]),
+                    (0, false, [(0, 4), (1, 1)]),
+                ],
+                [[(0, 1), (3, 2)], [(1, 0), (3, 2)]],
+            ),
+            PentagonalType::Seven => (
+                &[
+                    (0, false, [(0, 1), (0, 0)]),
+                    (4, true, [(1, 4), (0, 1)]),
+                    (4, true, [(0, 4), (0, 0)]),
+                    (3, true, [(2, 2), (2, 1)]),
+                    (2, false, [(0, 2), (0, 1)]),
+                    (0, false, [(3, 3), (0, 4)]),
+                    (4, true, [(6, 4), (3, 3)]),
+                ],
+                [[(6, 3), (0, 0)], [(4, 2), (3, 2)]],
+            ),
+            PentagonalType::Eight => (
+                &[
+                    (3, false, [(0, 4), (0, 3)]),
+                    (3, true, [(1, 2), (0, 4)]),
+                    (3, true, [(0, 2), (0, 3)]),
+                    (0, false, [(0, 2), (0, 1)]),
+                    (1, false, [(4, 2), (0, 1)]),
+                    (1, true, [(2, 1), (0, 0)]),
+                    (3, true, [(6, 4), (5, 4)]),
+                ],
+                [[(2, 1), (3, 2)], [(4, 3), (3, 0)]],
+            ),
+            PentagonalType::Nine => (
+                &[
+                    (1, false, [(0, 1), (0, 0)]),
+                    (4, true, [(1, 4), (1, 0)]),
+                    (0, true, [(0, 0), (1, 3)]),
+                    (1, false, [(0, 2), (0, 1)]),
+                    (4, true, [(4, 4), (4, 0)]),
+                    (1, false, [(0, 2), (4, 0)]),
+                    (4, true, [(6, 4), (6, 0)]),
+                ],
+                [[(5, 3), (0, 2)], [(7, 2), (1, 3)]],
+            ),
+            PentagonalType::Ten => (
+                &[
+                    (4, false, [(0, 1), (0, 0)]),
+                    (4, true, [(1, 3), (0, 2)]),
+                    (0, false, [(0, 0), (0, 4)]),
+                    (0, true, [(0, 3), (3, 2)]),
+                    (0, false, [(0, 0), (3, 4)]),
+                ],
+                [[(2, 1), (1, 2)], [(4, 2), (1, 2)]],
+            ),
+            PentagonalType::Eleven => (
+                &[
+                    (3, false, [(0, 3), (0, 2)]),
+                    (0, true, [(1, 0), (1, 1)]),
+                    (3, false, [(0, 4), (0, 3)]),
+                    (0, true, [(3, 0), (3, 1)]),
+                    (0, true, [(0, 0), (0, 1)]),
+                    (3, true, [(2, 4), (2, 3)]),
+                    (0, false, [(6, 0), (6, 1)]),
+                ],
+                [[(2, 3), (5, 3)], [(4, 2), (0, 1)]],
+            ),
+            PentagonalType::Twelve => (
+                &[
+                    (0, true, [(0, 0), (0, 1)]),
+                    (2, false, [(1, 4), (0, 4)]),
+                    (3, false, [(0, 4), (0, 3)]),
+                    (0, true, [(2, 0), (2, 1)]),
+                    (1, true, [(4, 2), (2, 1)]),
+                    (0, false, [(5, 0), (4, 2)]),
+                    (3, true, [(4, 3), (6, 2)]),
+                ],
+                [[(7, 2), (6, 4)], [(3, 0), (7, 0)]],
+            ),
+            PentagonalType::Thirteen => (
+                &[
+                    (2, false, [(0, 3), (0, 2)]),
+                    (0, true, [(1, 0), (1, 1)]),
+                    (3, false, [(1, 0), (1, 4)]),
+                    (0, true, [(3, 0), (3, 1)]),
+                    (0, true, [(0, 0), (0, 1)]),
+                    (4, true, [(5, 4), (5, 3)]),
+                    (0, false, [(5, 3), (6, 1)]),
+                ],
+                [[(1, 1), (0, 1)], [(4, 3), (6, 2)]],
+            ),
+            PentagonalType::Fourteen => (
+                &[
+                    (0, true, [(0, 0), (0, 1)]),
+                    (3, false, [(0, 3), (0, 2)]),
+                    (3, false, [(0, 4), (0, 3)]),
+                    (2, false, [(1, 4), (0, 4)]),
+                    (0, true, [(3, 0), (3, 1)]),
+                ],
+                [[(4, 1), (5, 3)], [(1, 3), (4, 0)]],
+            ),
+            PentagonalType::Fifteen => (
+                &[
+                    (0, true, [(0, 0), (0, 1)]),
+                    (2, false, [(1, 2), (1, 3)]),
+                    (4, true, [(1, 4), (1, 3)]),
+                    (2, false, [(3, 2), (3, 3)]),
+                    (3, true, [(4, 1), (3, 2)]),
+                    (2, true, [(4, 1), (5, 2)]),
+                    (0, false, [(6, 0), (6, 1)]),
+                    (2, true, [(7, 2), (7, 3)]),
+                    (2, false, [(0, 3), (0, 2)]),
+                    (0, true, [(9, 0), (9, 1)]),
+                    (1, false, [(9, 0), (9, 4)]),
+                ],
+                [[(1, 2), (0, 4)], [(8, 0), (10, 3)]],
+            ),
+        })
+    }
+}
+
+fn check<'a>(constraints: impl IntoIterator<Item = &'a (bool, &'static str)>) -> Result<(), Error> {
+    match constraints.into_iter().find(|(satisfied, _)| !satisfied) {
+        Some((_, constraint)) => Err(Error::Constraint(constraint)),
+        None => Ok(()),
+    }
+}
+
+/// The corners of the convex pentagon with the given interior angles
+/// whose side lengths satisfy the first three `equations`, with `B` at
+/// the origin and `C` on the positive x-axis, and by how much the
+/// fourth equation, if any, misses.
+fn close(angles: &[f64; 5], equations: &[([f64; 5], f64)]) -> Option<([DPoint; 5], f64)> {
+    if angles.iter().any(|&angle| angle <= 0.0 || angle >= PI) {
+        return None;
+    }
+
+    // The direction of each side, starting with `c` along the x-axis.
+    // Side `i` turns from side `i - 1` by the exterior angle at the
+    // corner between them.
+    let mut directions = [0.0; 5];
+    for i in [3, 4, 0, 1] {
+        directions[i] = directions[(i + 4) % 5] + PI - angles[(i + 4) % 5];
+    }
+
+    // Solve the closing condition and the equations by Gaussian
+    // elimination.
+    let mut rows: Vec<([f64; 5], f64)> = vec![
+        (directions.map(f64::cos), 0.0),
+        (directions.map(f64::sin), 0.0),
+    ];
+    rows.extend_from_slice(&equations[..3]);
+    for column in 0..5 {
+        let pivot = (column..5).max_by(|&i, &j| {
+            rows[i].0[column]
+                .abs()
+                .partial_cmp(&rows[j].0[column].abs())
+                .unwrap()
+        })?;
+        if rows[pivot].0[column].abs() < 1.0e-12 {
+            return None;
+        }
+        rows.swap(column, pivot);
+        let (coefficients, value) = rows[column];
+        for (i, row) in rows.iter_mut().enumerate() {
+            let factor = row.0[column] / coefficients[column];
+            if i != column {
+                for (x, y) in row.0.iter_mut().zip(&coefficients) {
+                    *x -= factor * y;
+                }
+                row.1 -= factor * value;
+            }
+        }
+    }
+    let sides: [f64; 5] = [0, 1, 2, 3, 4].map(|i| rows[i].1 / rows[i].0[i]);
+    if sides.iter().any(|&length| length <= 0.0) {
+        return None;
+    }
+
+    let mut corners = [[0.0; 2]; 5];
+    for i in [2, 3, 4, 0] {
+        let previous = corners[(i + 4) % 5];
+        corners[i] = [
+            previous[0] + sides[i] * directions[i].cos(),
+            previous[1] + sides[i] * directions[i].sin(),
+        ];
+    }
+    let residual = equations.get(3).map_or(0.0, |(coefficients, value)| {
+        (0..5).map(|i| coefficients[i] * sides[i]).sum::<f64>() - value
+    });
+    Some((corners, residual))
+}
+
+/// The interior angle at corner `i` of a counter-clockwise polygon.
+fn interior_angle(corners: &[DPoint], i: usize) -> f64 {
+    let n = corners.len();
+    let (p, c, q) = (corners[(i + n - 1) % n], corners[i], corners[(i + 1) % n]);
+    let u = [p[0] - c[0], p[1] - c[1]];
+    let v = [q[0] - c[0], q[1] - c[1]];
+    let angle = (-(u[0] * v[1] - u[1] * v[0])).atan2(u[0] * v[0] + u[1] * v[1]);
+    if angle < 0.0 {
+        angle + 2.0 * PI
+    } else {
+        angle
+    }
+}
+
+/// A patch of a monohedral tiling by convex pentagons.
+///
+/// Most types' tilings are not edge-to-edge. Corners of neighbouring
+/// tiles that lie on a tile's side are inserted into its face, which
+/// then has more than five vertices.
+pub struct PentagonalTiling {
+    face_index: FaceIndex,
+    points: Points,
+    reflected: Vec<bool>,
+    name: String,
+    topology: crate::topology::TopologyCache,
+    classes: crate::class::ClassCache,
+}
+
+impl PentagonalTiling {
+    default_methods! {}
+
+    /// Creates a patch of pentagons of type `kind` covering the
+    /// rectangle from the origin to `(width, height)`.
+    ///
+    /// `corners` are the pentagon's corners `A` to `E` in
+    /// counter-clockwise order; see [`PentagonalType::pentagon()`].
+    ///
+    /// # Errors
+    /// Returns [`Error::DegenerateTile`] if the pentagon is not convex
+    /// and [`Error::Constraint`] if it violates a side or angle
+    /// condition of `kind`.
+    pub fn new(
+        kind: PentagonalType,
+        corners: [Point; 5],
+        width: f32,
+        height: f32,
+    ) -> Result<Self, Error> {
+        let corners = corners.map(|p| [p.x as f64, p.y as f64]);
+
+        if !polygon::is_simple(&corners) || (0..5).any(|i| interior_angle(&corners, i) >= PI) {
+            return Err(Error::DegenerateTile);
+        }
+        kind.validate(&corners)?;
+
+        let (mut builder, reflected, classes) = match kind.cluster() {
+            Some((placements, periods)) => {
+                let mut tiles = vec![corners.to_vec()];
+                let mut reflected = vec![false];
+                for &(corner, mirrored, [(i, m), (j, n)]) in placements {
+                    let (p, q) = (corners[corner], corners[(corner + 1) % 5]);
+                    let transform = if mirrored {
+                        affine::match_two_reflected(p, q, tiles[i][m], tiles[j][n])
+                    } else {
+                        affine::match_two(p, q, tiles[i][m], tiles[j][n])
+                    };
+                    tiles.push(
+                        corners
+                            .iter()
+                            .map(|&c| affine::apply(&transform, c))
+                            .collect(),
+                    );
+                    reflected.push(mirrored);
+                }
+
+                let period = |[(i, m), (j, n)]: Period| {
+                    [
+                        tiles[j][n][0] - tiles[i][m][0],
+                        tiles[j][n][1] - tiles[i][m][1],
+                    ]
+                };
+                let (builder, copies) = builder::grow_lattice(
+                    &tiles,
+                    &reflected,
+                    periods.map(period),
+                    width as _,
+                    height as _,
+                );
+                // Each tile of the cluster is a class of its own.
+                (
+                    builder,
+                    copies.iter().map(|&k| reflected[k]).collect(),
+                    copies.iter().map(|&k| k as u32).collect::<Vec<_>>().into(),
+                )
+            }
+            None => {
+                let (builder, reflected) = builder::grow_patch(
+                    &corners,
+                    polygon::centroid(&corners),
+                    &kind.generators(&corners),
+                    width as _,
+                    height as _,
+                );
+                let classes = crate::class::mirrored(&reflected);
+                (builder, reflected, classes)
+            }
+        };
+        builder.split_t_junctions();
+        let (points, face_index) = builder.build();
+
+        Ok(Self {
+            face_index,
+            points,
+            reflected,
+            name: format!("PENTAGONAL-{:?}", kind).to_uppercase(),
+            topology: Default::default(),
+            classes,
+        })
+    }
+
+    /// Creates a patch of the Cairo pentagonal tiling.
+    ///
+    /// ![](https://upload.wikimedia.org/wikipedia/commons/thumb/e/e3/Cairo_pentagonal_tiling_4.svg/1920px-Cairo_pentagonal_tiling_4.svg.png)
+    pub fn cairo(width: f32, height: f32) -> Self {
+        let mut tiling = Self::new(
+            PentagonalType::Four,
+            PentagonalType::Four.example(),
+            width,
+            height,
+        )
+        .unwrap();
+        tiling.name = "CAIRO".to_string();
+        tiling
+    }
+
+    /// Per-face flags telling which tiles are mirror images of the
+    /// given pentagon.
+    pub fn reflected(&self) -> &[bool] {
+        &self.reflected
+    }
+}
+
+#[test]
+fn pentagonal() {
+    // Types whose tilings change shape with the parameters are also
+    // checked near both ends of their range.
+    let degrees = |angle: f32| vec![angle.to_radians()];
+    let mut pentagons: Vec<(PentagonalType, Vec<f32>)> = PentagonalType::ALL
+        .iter()
+        .map(|&kind| (kind, kind.default_parameters().to_vec()))
+        .collect();
+    pentagons.extend(vec![
+        (PentagonalType::Three, degrees(70.0)),
+        (PentagonalType::Six, degrees(45.0)),
+        (PentagonalType::Six, degrees(82.0)),
+        (PentagonalType::Seven, degrees(135.0)),
+        (PentagonalType::Seven, degrees(155.0)),
+        (PentagonalType::Eight, degrees(105.0)),
+        (PentagonalType::Eight, degrees(160.0)),
+        (PentagonalType::Nine, degrees(102.0)),
+        (PentagonalType::Nine, degrees(122.0)),
+        (PentagonalType::Ten, degrees(85.0)),
+        (PentagonalType::Ten, degrees(120.0)),
+        (PentagonalType::Eleven, degrees(140.0)),
+        (PentagonalType::Eleven, degrees(155.0)),
+        (PentagonalType::Twelve, degrees(140.0)),
+        (PentagonalType::Twelve, degrees(165.0)),
+        (PentagonalType::Thirteen, degrees(95.0)),
+        (PentagonalType::Thirteen, degrees(125.0)),
+    ]);
+
+    for (kind, parameters) in pentagons {
+        let corners = kind.pentagon(&parameters).unwrap();
+        let tiling = PentagonalTiling::new(kind, corners, 12.0, 12.0).unwrap();
+
+        // Every point well inside the patch is covered exactly once.
+        let polygons = polygon::face_polygons(tiling.points(), tiling.faces());
+        polygon::covered_once(&polygons, [4.0, 4.0], 4.0, 20)
+            .unwrap_or_else(|p| panic!("{:?} {:?} at {:?}", kind, parameters, p));
+    }
+
+    assert_eq!(
+        PentagonalType::Seven.pentagon(&[]).err(),
+        Some(Error::ParameterCount {
+            expected: 1,
+            found: 0
+        })
+    );
+    // Sides `c` and `d` would have to be negative.
+    assert_eq!(
+        PentagonalType::Ten.pentagon(&degrees(170.0)).err(),
+        Some(Error::DegenerateTile)
+    );
+
+    let mut corners = PentagonalType::Four.example();
+    corners[4].x += 0.1;
+    assert_eq!(
+        PentagonalTiling::new(PentagonalType::Four, corners, 4.0, 4.0).err(),
+        Some(Error::Constraint("d = e"))
+    );
+}
@@ -0,0 +1,120 @@
+//! [PLY](http://paulbourke.net/dataformats/ply/) writer.
+use crate::{palette::class_color, Encoding, FaceIndex, Position};
+use std::io::Write;
+
+/// Writes the mesh with per-face `class` and `red`, `green`, `blue`
+/// properties.
+pub(crate) fn write<P: Position>(
+    name: &str,
+    points: &[P],
+    face_index: &FaceIndex,
+    classes: &[u32],
+    encoding: Encoding,
+    reverse_face_winding: bool,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut file = Vec::new();
+
+    writeln!(file, "ply")?;
+    match encoding {
+        Encoding::Ascii => writeln!(file, "format ascii 1.0")?,
+        Encoding::Binary => writeln!(file, "format binary_little_endian 1.0")?,
+    }
+    writeln!(file, "comment {}-tiling", name)?;
+    writeln!(file, "element vertex {}", points.len())?;
+    for axis in &["x", "y", "z"] {
+        writeln!(file, "property float {}", axis)?;
+    }
+    writeln!(file, "element face {}", face_index.len())?;
+    writeln!(file, "property list uchar uint vertex_indices")?;
+    writeln!(file, "property uint class")?;
+    for channel in &["red", "green", "blue"] {
+        writeln!(file, "property uchar {}", channel)?;
+    }
+    writeln!(file, "end_header")?;
+
+    for point in points {
+        let [x, y, z] = point.xyz();
+        match encoding {
+            Encoding::Ascii => writeln!(file, "{} {} {}", x, y, z)?,
+            Encoding::Binary => {
+                for coordinate in &[x, y, z] {
+                    file.write_all(&coordinate.to_le_bytes())?;
+                }
+            }
+        }
+    }
+
+    for (face, &class) in face_index.iter().zip(classes) {
+        if face.len() > u8::MAX as usize {
+            return Err(format!(
+                "PLY faces can have at most 255 vertices, got {}",
+                face.len()
+            )
+            .into());
+        }
+
+        let color = class_color(class);
+        let vertices: Vec<u32> = if reverse_face_winding {
+            face.iter().rev().copied().collect()
+        } else {
+            face.clone()
+        };
+
+        match encoding {
+            Encoding::Ascii => {
+                write!(file, "{}", face.len())?;
+                for vertex_index in &vertices {
+                    write!(file, " {}", vertex_index)?;
+                }
+                writeln!(file, " {} {} {} {}", class, color[0], color[1], color[2])?;
+            }
+            Encoding::Binary => {
+                file.push(face.len() as u8);
+                for vertex_index in &vertices {
+                    file.write_all(&vertex_index.to_le_bytes())?;
+                }
+                file.write_all(&class.to_le_bytes())?;
+                file.write_all(&color)?;
+            }
+        }
+    }
+
+    Ok(file)
+}
+
+#[test]
+fn ply() -> Result<(), Box<dyn std::error::Error>> {
+    let tiling = crate::SemiRegularTiling::two(4, 4);
+
+    let ascii = String::from_utf8(tiling.to_ply(Encoding::Ascii, false)?)?;
+    let body = ascii.split("end_header\n").nth(1).unwrap();
+    assert_eq!(
+        body.lines().count(),
+        tiling.points().len() + tiling.faces().len()
+    );
+    assert!(ascii.contains(&format!("element face {}", tiling.faces().len())));
+    let classes: Vec<u32> = body
+        .lines()
+        .skip(tiling.points().len())
+        .map(|line| {
+            let values: Vec<u32> = line.split(' ').map(|v| v.parse().unwrap()).collect();
+            values[values[0] as usize + 1]
+        })
+        .collect();
+    assert_eq!(classes, tiling.classes());
+
+    let binary = tiling.to_ply(Encoding::Binary, false)?;
+    let header = binary
+        .windows(11)
+        .position(|window| window == b"end_header\n")
+        .unwrap()
+        + 11;
+    let faces: usize = tiling
+        .faces()
+        .iter()
+        .map(|face| 1 + 4 * face.len() + 4 + 3)
+        .sum();
+    assert_eq!(binary.len(), header + 12 * tiling.points().len() + faces);
+
+    Ok(())
+}
@@ -0,0 +1,511 @@
+//! Helpers for simple polygons given by their corners.
+use crate::affine::DPoint;
+
+/// Twice the signed area; positive for counter-clockwise polygons.
+pub(crate) fn signed_area(polygon: &[DPoint]) -> f64 {
+    (0..polygon.len())
+        .map(|i| {
+            let a = polygon[i];
+            let b = polygon[(i + 1) % polygon.len()];
+            a[0] * b[1] - b[0] * a[1]
+        })
+        .sum()
+}
+
+pub(crate) fn centroid(polygon: &[DPoint]) -> DPoint {
+    let n = polygon.len() as f64;
+    polygon
+        .iter()
+        .fold([0.0, 0.0], |c, p| [c[0] + p[0] / n, c[1] + p[1] / n])
+}
+
+fn cross(o: DPoint, a: DPoint, b: DPoint) -> f64 {
+    (a[0] - o[0]) * (b[1] - o[1]) - (a[1] - o[1]) * (b[0] - o[0])
+}
+
+/// Whether the closed segments `a`–`b` and `c`–`d` properly cross or
+/// touch.
+pub(crate) fn segments_intersect(a: DPoint, b: DPoint, c: DPoint, d: DPoint) -> bool {
+    let d1 = cross(c, d, a);
+    let d2 = cross(c, d, b);
+    let d3 = cross(a, b, c);
+    let d4 = cross(a, b, d);
+
+    if ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0))
+        && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0))
+    {
+        return true;
+    }
+
+    let on_segment = |p: DPoint, q: DPoint, r: DPoint| {
+        r[0] >= p[0].min(q[0])
+            && r[0] <= p[0].max(q[0])
+            && r[1] >= p[1].min(q[1])
+            && r[1] <= p[1].max(q[1])
+    };
+
+    (d1 == 0.0 && on_segment(c, d, a))
+        || (d2 == 0.0 && on_segment(c, d, b))
+        || (d3 == 0.0 && on_segment(a, b, c))
+        || (d4 == 0.0 && on_segment(a, b, d))
+}
+
+/// The parameters `(s, t)` where the lines `p + s u` and `q + t w`
+/// meet, unless they are parallel.
+pub(crate) fn line_intersection(p: DPoint, u: DPoint, q: DPoint, w: DPoint) -> Option<(f64, f64)> {
+    let denominator = u[0] * w[1] - u[1] * w[0];
+    if denominator.abs() < 1.0e-12 {
+        return None;
+    }
+    let pq = [q[0] - p[0], q[1] - p[1]];
+    Some((
+        (pq[0] * w[1] - pq[1] * w[0]) / denominator,
+        (pq[0] * u[1] - pq[1] * u[0]) / denominator,
+    ))
+}
+
+/// Whether the polygon has at least three corners, winds
+/// counter-clockwise and no two of its non-adjacent edges touch.
+pub(crate) fn is_simple(polygon: &[DPoint]) -> bool {
+    let n = polygon.len();
+    if n < 3 || signed_area(polygon) <= 0.0 {
+        return false;
+    }
+
+    for i in 0..n {
+        for j in i + 1..n {
+            if j == i + 1 || (i == 0 && j == n - 1) {
+                continue;
+            }
+            if segments_intersect(
+                polygon[i],
+                polygon[(i + 1) % n],
+                polygon[j],
+                polygon[(j + 1) % n],
+            ) {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+/// Whether `p` lies inside the polygon, by ray casting.
+#[cfg(test)]
+pub(crate) fn contains(polygon: &[DPoint], p: DPoint) -> bool {
+    let mut inside = false;
+    for i in 0..polygon.len() {
+        let (a, b) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+        if (a[1] > p[1]) != (b[1] > p[1])
+            && p[0] < a[0] + (p[1] - a[1]) / (b[1] - a[1]) * (b[0] - a[0])
+        {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// The faces of a tiling as polygons.
+#[cfg(test)]
+pub(crate) fn face_polygons(
+    points: &[crate::Point],
+    face_index: &crate::FaceIndex,
+) -> Vec<Vec<DPoint>> {
+    face_index
+        .iter()
+        .map(|face| {
+            face.iter()
+                .map(|&v| [points[v as usize].x as f64, points[v as usize].y as f64])
+                .collect()
+        })
+        .collect()
+}
+
+/// The area of all faces of a mesh.
+#[cfg(test)]
+pub(crate) fn mesh_area(mesh: &crate::Mesh) -> f64 {
+    face_polygons(mesh.points(), mesh.faces())
+        .iter()
+        .map(|face| signed_area(face) / 2.0)
+        .sum()
+}
+
+/// Checks that every point of a `samples` by `samples` grid in the
+/// square of side `size` at `corner` lies in exactly one of `polygons`.
+/// Returns the first point that does not.
+///
+/// The grid is slightly skewed so its points stay off the axis-parallel
+/// edges of regular tiles.
+#[cfg(test)]
+pub(crate) fn covered_once(
+    polygons: &[Vec<DPoint>],
+    corner: DPoint,
+    size: f64,
+    samples: usize,
+) -> Result<(), DPoint> {
+    let step = size / samples as f64;
+    for i in 0..samples {
+        for j in 0..samples {
+            let p = [
+                corner[0] + 1.0065 * step * i as f64,
+                corner[1] + 0.9935 * step * j as f64,
+            ];
+            if polygons
+                .iter()
+                .filter(|polygon| contains(polygon, p))
+                .count()
+                != 1
+            {
+                return Err(p);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The directed edges of `face_index` without a twin running the other
+/// way, i.e. those on the boundary of the patch.
+///
+/// # Panics
+/// If a directed edge occurs twice, i.e. two faces overlap or one is
+/// turned over.
+#[cfg(test)]
+pub(crate) fn boundary_edges(
+    face_index: &crate::FaceIndex,
+) -> Vec<(crate::VertexKey, crate::VertexKey)> {
+    let mut directed = std::collections::HashSet::new();
+    for face in face_index {
+        for i in 0..face.len() {
+            let edge = (face[i], face[(i + 1) % face.len()]);
+            assert!(directed.insert(edge), "edge {:?} occurs twice", edge);
+        }
+    }
+    directed
+        .iter()
+        .filter(|&&(a, b)| !directed.contains(&(b, a)))
+        .copied()
+        .collect()
+}
+
+/// The normal of a polygon in 3D by Newell's method. Its length is
+/// twice the polygon's area.
+pub(crate) fn newell_normal(polygon: &[[f32; 3]]) -> [f64; 3] {
+    let n = polygon.len();
+    let mut normal = [0.0f64; 3];
+    for i in 0..n {
+        let (p, q) = (polygon[i], polygon[(i + 1) % n]);
+        for (k, n) in normal.iter_mut().enumerate() {
+            let (a, b) = ((k + 1) % 3, (k + 2) % 3);
+            *n += (p[a] as f64 - q[a] as f64) * (p[b] as f64 + q[b] as f64);
+        }
+    }
+    normal
+}
+
+/// Moves every side of a planar polygon in 3D, given counter-clockwise
+/// around its normal, inwards by `distance`. Corners are mitered.
+///
+/// Sides of non-convex polygons or of polygons with sides shorter than
+/// `distance` may cross each other in the result.
+pub(crate) fn inset(polygon: &[[f32; 3]], distance: f32) -> Vec<[f32; 3]> {
+    let n = polygon.len();
+    let normal = normalize(newell_normal(polygon));
+    let distance = distance as f64;
+
+    (0..n)
+        .map(|i| {
+            let [p, q, r] = [polygon[(i + n - 1) % n], polygon[i], polygon[(i + 1) % n]]
+                .map(|p| p.map(|c| c as f64));
+            let inward = |a: [f64; 3], b: [f64; 3]| {
+                let d = normalize([b[0] - a[0], b[1] - a[1], b[2] - a[2]]);
+                [
+                    normal[1] * d[2] - normal[2] * d[1],
+                    normal[2] * d[0] - normal[0] * d[2],
+                    normal[0] * d[1] - normal[1] * d[0],
+                ]
+            };
+            let (u, v) = (inward(p, q), inward(q, r));
+            let cosine = u[0] * v[0] + u[1] * v[1] + u[2] * v[2];
+            // Sides folding back onto each other have no miter.
+            let miter = if 1.0 + cosine > 1.0e-6 {
+                [0, 1, 2].map(|k| (u[k] + v[k]) / (1.0 + cosine))
+            } else {
+                u
+            };
+            [0, 1, 2].map(|k| (q[k] + distance * miter[k]) as f32)
+        })
+        .collect()
+}
+
+/// Whether some side of `inset` vanished or turned around, i.e. the
+/// [`inset()`] of `polygon` went past its middle.
+pub(crate) fn collapsed(polygon: &[[f32; 3]], inset: &[[f32; 3]]) -> bool {
+    let n = polygon.len();
+    let side = |ring: &[[f32; 3]], i: usize| [0, 1, 2].map(|k| ring[(i + 1) % n][k] - ring[i][k]);
+    (0..n).any(|i| {
+        let (a, b) = (side(polygon, i), side(inset, i));
+        a[0] * b[0] + a[1] * b[1] + a[2] * b[2] <= 0.0
+    })
+}
+
+/// Rounds the corners of a planar polygon in 3D with circular arcs of
+/// `radius`, each made of `segments` straight pieces.
+///
+/// The radius shrinks at corners whose sides are too short for it.
+/// Straight corners are kept as they are.
+pub(crate) fn fillet(polygon: &[[f32; 3]], radius: f32, segments: u32) -> Vec<[f32; 3]> {
+    let n = polygon.len();
+    if radius <= 0.0 || segments == 0 {
+        return polygon.to_vec();
+    }
+
+    let mut rounded = Vec::with_capacity(n * (segments as usize + 1));
+    for i in 0..n {
+        let [p, q, r] = [polygon[(i + n - 1) % n], polygon[i], polygon[(i + 1) % n]]
+            .map(|p| p.map(|c| c as f64));
+        let (u, v) = (
+            [p[0] - q[0], p[1] - q[1], p[2] - q[2]],
+            [r[0] - q[0], r[1] - q[1], r[2] - q[2]],
+        );
+        let (u_length, v_length) = (length(u), length(v));
+        let (u, v) = (normalize(u), normalize(v));
+        let angle = (u[0] * v[0] + u[1] * v[1] + u[2] * v[2])
+            .clamp(-1.0, 1.0)
+            .acos();
+
+        // Distance from the corner to where the arc touches the sides.
+        let tangent = (radius as f64 / (angle / 2.0).tan()).min(u_length.min(v_length) / 2.0);
+        if !(1.0e-6..std::f64::consts::PI - 1.0e-6).contains(&angle) || tangent <= 0.0 {
+            rounded.push(polygon[i]);
+            continue;
+        }
+        let radius = tangent * (angle / 2.0).tan();
+
+        let bisector = normalize([u[0] + v[0], u[1] + v[1], u[2] + v[2]]);
+        let center = [0, 1, 2].map(|k| q[k] + bisector[k] * radius / (angle / 2.0).sin());
+        let [a, b] = [u, v].map(|w| [0, 1, 2].map(|k| q[k] + w[k] * tangent - center[k]));
+        // The arc spans the corner's exterior angle.
+        let sweep = std::f64::consts::PI - angle;
+
+        for s in 0..=segments {
+            let t = s as f64 / segments as f64;
+            let (wa, wb) = (
+                ((1.0 - t) * sweep).sin() / sweep.sin(),
+                (t * sweep).sin() / sweep.sin(),
+            );
+            rounded.push([0, 1, 2].map(|k| (center[k] + wa * a[k] + wb * b[k]) as f32));
+        }
+    }
+
+    rounded
+}
+
+fn length(v: [f64; 3]) -> f64 {
+    (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt()
+}
+
+fn normalize(v: [f64; 3]) -> [f64; 3] {
+    let length = length(v);
+    if length > 0.0 {
+        v.map(|c| c / length)
+    } else {
+        v
+    }
+}
+
+/// Unit vertex normals, averaged from the normals of the adjacent faces
+/// weighted by their area.
+///
+/// Vertices no face uses, or whose faces' normals cancel, get the
+/// normal `(0, 0, 1)` of the plane the tilings are built in.
+#[cfg(any(feature = "obj", feature = "gltf"))]
+pub(crate) fn vertex_normals(points: &[[f32; 3]], face_index: &crate::FaceIndex) -> Vec<[f32; 3]> {
+    let mut normals = vec![[0.0f64; 3]; points.len()];
+    for face in face_index {
+        let corners: Vec<[f32; 3]> = face.iter().map(|&v| points[v as usize]).collect();
+        let normal = newell_normal(&corners);
+        for &vertex in face {
+            for k in 0..3 {
+                normals[vertex as usize][k] += normal[k];
+            }
+        }
+    }
+
+    normals
+        .iter()
+        .map(|n| {
+            let length = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+            if length > 0.0 {
+                [
+                    (n[0] / length) as f32,
+                    (n[1] / length) as f32,
+                    (n[2] / length) as f32,
+                ]
+            } else {
+                [0.0, 0.0, 1.0]
+            }
+        })
+        .collect()
+}
+
+/// Splits a planar polygon, given by its corners in 3D, into triangles
+/// by ear clipping. Returns indices into `polygon`.
+///
+/// Works for non-convex polygons. Corners lying on the straight line
+/// between their neighbours, as left by splitting T-junctions, are
+/// fanned in afterwards so no sliver triangles are created.
+#[cfg(any(feature = "stl", feature = "gltf"))]
+pub(crate) fn triangulate(polygon: &[[f32; 3]]) -> Vec<[usize; 3]> {
+    let n = polygon.len();
+    if n < 3 {
+        return Vec::new();
+    }
+
+    // Drop the normal's dominant axis to get a 2D polygon.
+    let normal = newell_normal(polygon);
+    let axis = (0..3)
+        .max_by(|&a, &b| normal[a].abs().partial_cmp(&normal[b].abs()).unwrap())
+        .unwrap();
+    let projected: Vec<DPoint> = polygon
+        .iter()
+        .map(|p| [p[(axis + 1) % 3] as f64, p[(axis + 2) % 3] as f64])
+        .collect();
+    let sign = signed_area(&projected).signum();
+
+    // Twice the signed area of the corner at `b`, and whether its sides
+    // are parallel up to single precision noise.
+    let area =
+        |a: usize, b: usize, c: usize| sign * cross(projected[a], projected[b], projected[c]);
+    let sides = |a: usize, b: usize, c: usize| {
+        let (p, q, r) = (projected[a], projected[b], projected[c]);
+        ([q[0] - p[0], q[1] - p[1]], [r[0] - q[0], r[1] - q[1]])
+    };
+    let is_flat = |a: usize, b: usize, c: usize| {
+        let (u, v) = sides(a, b, c);
+        area(a, b, c).abs() <= 1.0e-5 * u[0].hypot(u[1]) * v[0].hypot(v[1])
+    };
+    let is_straight = |a: usize, b: usize, c: usize| {
+        let (u, v) = sides(a, b, c);
+        is_flat(a, b, c) && u[0] * v[0] + u[1] * v[1] > 0.0
+    };
+
+    // Straight corners are dropped while clipping and fanned in
+    // afterwards, in reverse order, so no sliver triangles are created.
+    let mut straight = Vec::new();
+    let drop_straight = |remaining: &mut Vec<usize>, straight: &mut Vec<[usize; 3]>| {
+        while remaining.len() >= 3 {
+            let m = remaining.len();
+            match (0..m).find(|&k| {
+                is_straight(
+                    remaining[(k + m - 1) % m],
+                    remaining[k],
+                    remaining[(k + 1) % m],
+                )
+            }) {
+                Some(k) => {
+                    straight.push([
+                        remaining[(k + m - 1) % m],
+                        remaining[k],
+                        remaining[(k + 1) % m],
+                    ]);
+                    remaining.remove(k);
+                }
+                None => break,
+            }
+        }
+    };
+
+    let mut remaining: Vec<usize> = (0..n).collect();
+    drop_straight(&mut remaining, &mut straight);
+    let mut triangles = Vec::with_capacity(n - 2);
+
+    while remaining.len() > 3 {
+        let m = remaining.len();
+        let corner = |k: usize| {
+            (
+                remaining[(k + m - 1) % m],
+                remaining[k],
+                remaining[(k + 1) % m],
+            )
+        };
+
+        // An ear must not contain any other corner. Corners on its
+        // boundary are only tolerated when there is no other choice.
+        let is_ear = |k: usize, closed: bool| {
+            let (a, b, c) = corner(k);
+            area(a, b, c) > 0.0
+                && !is_flat(a, b, c)
+                && remaining.iter().all(|&i| {
+                    i == a
+                        || i == b
+                        || i == c
+                        || [area(a, b, i), area(b, c, i), area(c, a, i)]
+                            .iter()
+                            .any(|&side| if closed { side < 0.0 } else { side <= 0.0 })
+                })
+        };
+        // Without any ear the polygon is degenerate; clip its most
+        // convex corner so we still terminate.
+        let k = (0..m)
+            .find(|&k| is_ear(k, true))
+            .or_else(|| (0..m).find(|&k| is_ear(k, false)))
+            .unwrap_or_else(|| {
+                let area = |k: usize| {
+                    let (a, b, c) = corner(k);
+                    area(a, b, c)
+                };
+                (0..m)
+                    .max_by(|&k, &l| area(k).partial_cmp(&area(l)).unwrap())
+                    .unwrap()
+            });
+
+        let (a, b, c) = corner(k);
+        triangles.push([a, b, c]);
+        remaining.remove(k);
+        drop_straight(&mut remaining, &mut straight);
+    }
+    // What is left may have collapsed onto a line.
+    if remaining.len() == 3 {
+        triangles.push([remaining[0], remaining[1], remaining[2]]);
+    }
+
+    for &[a, b, c] in straight.iter().rev() {
+        if let Some(t) = triangles
+            .iter()
+            .position(|t| (0..3).any(|j| t[j] == a && t[(j + 1) % 3] == c))
+        {
+            let j = (0..3).find(|&j| triangles[t][j] == a).unwrap();
+            let apex = triangles[t][(j + 2) % 3];
+            triangles[t] = [a, b, apex];
+            triangles.push([b, c, apex]);
+        }
+    }
+
+    triangles
+}
+
+#[test]
+#[cfg(feature = "stl")]
+fn triangulate_non_convex() {
+    // An L-shape with a corner on the straight side at the bottom.
+    let polygon = [
+        [0.0, 0.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [2.0, 0.0, 0.0],
+        [2.0, 1.0, 0.0],
+        [1.0, 1.0, 0.0],
+        [1.0, 2.0, 0.0],
+        [0.0, 2.0, 0.0],
+    ];
+    let triangles = triangulate(&polygon);
+    assert_eq!(triangles.len(), 5);
+
+    let corners: Vec<DPoint> = polygon.iter().map(|p| [p[0] as f64, p[1] as f64]).collect();
+    let area: f64 = triangles
+        .iter()
+        .map(|t| signed_area(&[corners[t[0]], corners[t[1]], corners[t[2]]]))
+        .inspect(|&area| assert!(area > 0.0))
+        .sum();
+    assert_eq!(area, 2.0 * 3.0);
+}
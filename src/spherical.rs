@@ -0,0 +1,443 @@
+//! Regular and semi-regular tilings of the sphere.
+//!
+//! All tilings are built with Wythoff's kaleidoscopic construction: a
+//! generator point inside the fundamental triangle of the symmetry
+//! group is reflected (or, for the snub solids, rotated) until its
+//! orbit is complete. Edges join the closest pairs of points and faces
+//! are traced around the resulting graph.
+use crate::{Error, FaceIndex, Point3, Points3, VertexKey};
+use std::f64::consts::PI;
+
+type DVec3 = [f64; 3];
+
+/// The Archimedean solids, seen as semi-regular tilings of the sphere.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Archimedean {
+    /// `3.6.6`
+    TruncatedTetrahedron,
+    /// `3.4.3.4`
+    Cuboctahedron,
+    /// `3.8.8`
+    TruncatedCube,
+    /// `4.6.6`
+    TruncatedOctahedron,
+    /// `3.4.4.4`
+    Rhombicuboctahedron,
+    /// `4.6.8`
+    TruncatedCuboctahedron,
+    /// `3.3.3.3.4`
+    SnubCube,
+    /// `3.5.3.5`
+    Icosidodecahedron,
+    /// `3.10.10`
+    TruncatedDodecahedron,
+    /// `5.6.6`
+    TruncatedIcosahedron,
+    /// `3.4.5.4`
+    Rhombicosidodecahedron,
+    /// `4.6.10`
+    TruncatedIcosidodecahedron,
+    /// `3.3.3.3.5`
+    SnubDodecahedron,
+}
+
+/// Which mirrors of the fundamental triangle the generator point lies
+/// off, in the order: opposite the vertex, the edge midpoint and the
+/// face center of `{p, q}`.
+#[derive(Clone, Copy)]
+enum Generator {
+    Reflect([bool; 3]),
+    Snub,
+}
+
+impl Archimedean {
+    fn construction(self) -> (u32, u32, Generator) {
+        use Generator::*;
+        match self {
+            Archimedean::TruncatedTetrahedron => (3, 3, Reflect([true, true, false])),
+            Archimedean::Cuboctahedron => (4, 3, Reflect([false, true, false])),
+            Archimedean::TruncatedCube => (4, 3, Reflect([true, true, false])),
+            Archimedean::TruncatedOctahedron => (3, 4, Reflect([true, true, false])),
+            Archimedean::Rhombicuboctahedron => (4, 3, Reflect([true, false, true])),
+            Archimedean::TruncatedCuboctahedron => (4, 3, Reflect([true, true, true])),
+            Archimedean::SnubCube => (4, 3, Snub),
+            Archimedean::Icosidodecahedron => (5, 3, Reflect([false, true, false])),
+            Archimedean::TruncatedDodecahedron => (5, 3, Reflect([true, true, false])),
+            Archimedean::TruncatedIcosahedron => (3, 5, Reflect([true, true, false])),
+            Archimedean::Rhombicosidodecahedron => (5, 3, Reflect([true, false, true])),
+            Archimedean::TruncatedIcosidodecahedron => (5, 3, Reflect([true, true, true])),
+            Archimedean::SnubDodecahedron => (5, 3, Snub),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Archimedean::TruncatedTetrahedron => "TRUNCATED-TETRAHEDRON",
+            Archimedean::Cuboctahedron => "CUBOCTAHEDRON",
+            Archimedean::TruncatedCube => "TRUNCATED-CUBE",
+            Archimedean::TruncatedOctahedron => "TRUNCATED-OCTAHEDRON",
+            Archimedean::Rhombicuboctahedron => "RHOMBICUBOCTAHEDRON",
+            Archimedean::TruncatedCuboctahedron => "TRUNCATED-CUBOCTAHEDRON",
+            Archimedean::SnubCube => "SNUB-CUBE",
+            Archimedean::Icosidodecahedron => "ICOSIDODECAHEDRON",
+            Archimedean::TruncatedDodecahedron => "TRUNCATED-DODECAHEDRON",
+            Archimedean::TruncatedIcosahedron => "TRUNCATED-ICOSAHEDRON",
+            Archimedean::Rhombicosidodecahedron => "RHOMBICOSIDODECAHEDRON",
+            Archimedean::TruncatedIcosidodecahedron => "TRUNCATED-ICOSIDODECAHEDRON",
+            Archimedean::SnubDodecahedron => "SNUB-DODECAHEDRON",
+        }
+    }
+}
+
+fn dot(a: &DVec3, b: &DVec3) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: &DVec3, b: &DVec3) -> DVec3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn scale(a: &DVec3, s: f64) -> DVec3 {
+    [a[0] * s, a[1] * s, a[2] * s]
+}
+
+fn sub(a: &DVec3, b: &DVec3) -> DVec3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn normalize(a: &DVec3) -> DVec3 {
+    scale(a, 1.0 / dot(a, a).sqrt())
+}
+
+fn distance_squared(a: &DVec3, b: &DVec3) -> f64 {
+    let d = sub(a, b);
+    dot(&d, &d)
+}
+
+fn reflect(v: &DVec3, n: &DVec3) -> DVec3 {
+    sub(v, &scale(n, 2.0 * dot(v, n)))
+}
+
+/// The fundamental triangle of `{p, q}` and its three mirrors.
+struct Kaleidoscope {
+    /// Vertex, edge midpoint and face center.
+    corners: [DVec3; 3],
+    /// Unit normals of the mirrors opposite each corner, pointing
+    /// inside the triangle.
+    mirrors: [DVec3; 3],
+}
+
+impl Kaleidoscope {
+    fn new(p: u32, q: u32) -> Self {
+        let (p, q) = (PI / p as f64, PI / q as f64);
+
+        let circumradius = (p.tan() * q.tan()).recip().acos();
+        let inradius = (q.cos() / p.sin()).acos();
+
+        let face = [0.0, 0.0, 1.0];
+        let vertex = [circumradius.sin(), 0.0, circumradius.cos()];
+        let edge = [
+            inradius.sin() * p.cos(),
+            inradius.sin() * p.sin(),
+            inradius.cos(),
+        ];
+        let corners = [vertex, edge, face];
+
+        let mirrors = [0, 1, 2].map(|i| {
+            let n = normalize(&cross(&corners[(i + 1) % 3], &corners[(i + 2) % 3]));
+            if dot(&n, &corners[i]) < 0.0 {
+                scale(&n, -1.0)
+            } else {
+                n
+            }
+        });
+
+        Self { corners, mirrors }
+    }
+
+    /// The point inside the triangle equally far from the `active`
+    /// mirrors and on all the others.
+    fn generator(&self, active: [bool; 3]) -> DVec3 {
+        let w = active.map(|a| if a { 1.0 } else { 0.0 });
+        let m = &self.mirrors;
+
+        // Cramer's rule for `m[i] · x = w[i]`.
+        let det = dot(&m[0], &cross(&m[1], &m[2]));
+        let x = [
+            dot(
+                &[w[0], m[0][1], m[0][2]],
+                &cross(&[w[1], m[1][1], m[1][2]], &[w[2], m[2][1], m[2][2]]),
+            ),
+            dot(
+                &[m[0][0], w[0], m[0][2]],
+                &cross(&[m[1][0], w[1], m[1][2]], &[m[2][0], w[2], m[2][2]]),
+            ),
+            dot(
+                &[m[0][0], m[0][1], w[0]],
+                &cross(&[m[1][0], m[1][1], w[1]], &[m[2][0], m[2][1], w[2]]),
+            ),
+        ];
+
+        normalize(&scale(&x, 1.0 / det))
+    }
+
+    /// Rotations about the vertex, edge midpoint and face center.
+    fn rotate(&self, v: &DVec3, corner: usize) -> DVec3 {
+        let m = &self.mirrors;
+        let (a, b) = match corner {
+            0 => (1, 2),
+            1 => (0, 2),
+            _ => (0, 1),
+        };
+        reflect(&reflect(v, &m[b]), &m[a])
+    }
+
+    /// The point whose images under the three rotations are all equally
+    /// far away, found with Newton's method.
+    fn snub_generator(&self) -> DVec3 {
+        let [v, e, f] = &self.corners;
+        let point = |s: f64, t: f64| {
+            normalize(&[
+                f[0] + s * (v[0] - f[0]) + t * (e[0] - f[0]),
+                f[1] + s * (v[1] - f[1]) + t * (e[1] - f[1]),
+                f[2] + s * (v[2] - f[2]) + t * (e[2] - f[2]),
+            ])
+        };
+        let residual = |s: f64, t: f64| {
+            let x = point(s, t);
+            let d = [0, 1, 2].map(|c| distance_squared(&x, &self.rotate(&x, c)));
+            [d[0] - d[1], d[2] - d[1]]
+        };
+
+        let (mut s, mut t) = (1.0 / 3.0, 1.0 / 3.0);
+        let h = 1.0e-7;
+        for _ in 0..50 {
+            let r = residual(s, t);
+            if r[0].abs() + r[1].abs() < 1.0e-14 {
+                break;
+            }
+            let rs = residual(s + h, t);
+            let rt = residual(s, t + h);
+            let j = [
+                (rs[0] - r[0]) / h,
+                (rt[0] - r[0]) / h,
+                (rs[1] - r[1]) / h,
+                (rt[1] - r[1]) / h,
+            ];
+            let det = j[0] * j[3] - j[1] * j[2];
+            s -= (j[3] * r[0] - j[1] * r[1]) / det;
+            t -= (j[0] * r[1] - j[2] * r[0]) / det;
+        }
+
+        point(s, t)
+    }
+
+    /// The orbit of `generator` under the group generated by `maps`.
+    fn orbit(&self, generator: DVec3, maps: &[&dyn Fn(&DVec3) -> DVec3]) -> Vec<DVec3> {
+        let mut orbit = vec![generator];
+        let mut i = 0;
+        while i < orbit.len() {
+            for map in maps {
+                let image = map(&orbit[i]);
+                if orbit.iter().all(|v| distance_squared(v, &image) > 1.0e-12) {
+                    orbit.push(image);
+                }
+            }
+            i += 1;
+        }
+        orbit
+    }
+}
+
+/// Traces the faces of a convex polyhedron with vertices `points` whose
+/// edges are exactly the closest vertex pairs.
+fn faces_of(points: &[DVec3]) -> FaceIndex {
+    let mut edge_length = f64::MAX;
+    for (i, a) in points.iter().enumerate() {
+        for b in &points[i + 1..] {
+            edge_length = edge_length.min(distance_squared(a, b));
+        }
+    }
+
+    // Neighbours of every vertex, sorted counter-clockwise as seen
+    // from outside.
+    let neighbors: Vec<Vec<usize>> = points
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            let helper = if v[0].abs() < 0.9 {
+                [1.0, 0.0, 0.0]
+            } else {
+                [0.0, 1.0, 0.0]
+            };
+            let u = normalize(&cross(&helper, v));
+            let w = cross(v, &u);
+
+            let mut neighbors: Vec<(f64, usize)> = points
+                .iter()
+                .enumerate()
+                .filter(|&(j, n)| j != i && distance_squared(v, n) < edge_length * (1.0 + 1.0e-6))
+                .map(|(j, n)| {
+                    let d = sub(n, v);
+                    (dot(&d, &w).atan2(dot(&d, &u)), j)
+                })
+                .collect();
+            neighbors.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+            neighbors.into_iter().map(|(_, j)| j).collect()
+        })
+        .collect();
+
+    let mut visited = std::collections::HashSet::new();
+    let mut face_index = FaceIndex::new();
+
+    for (start, start_neighbors) in neighbors.iter().enumerate() {
+        for &next in start_neighbors {
+            if visited.contains(&(start, next)) {
+                continue;
+            }
+
+            let mut face = Vec::new();
+            let (mut a, mut b) = (start, next);
+            while visited.insert((a, b)) {
+                face.push(a as VertexKey);
+                // The neighbour of `b` just before `a` in
+                // counter-clockwise order closes the face on the left.
+                let around = &neighbors[b];
+                let index = around.iter().position(|&n| n == a).unwrap();
+                let c = around[(index + around.len() - 1) % around.len()];
+                a = b;
+                b = c;
+            }
+            face_index.push(face);
+        }
+    }
+
+    face_index
+}
+
+/// A regular or semi-regular tiling of the unit sphere.
+///
+/// Faces are flat polygons spanned by points on the sphere, i.e. the
+/// tiling doubles as a Platonic or Archimedean solid.
+pub struct SphericalTiling {
+    face_index: FaceIndex,
+    points: Points3,
+    name: String,
+    topology: crate::topology::TopologyCache,
+    classes: crate::class::ClassCache,
+}
+
+impl SphericalTiling {
+    default_methods! { Point3 }
+
+    fn wythoff(p: u32, q: u32, generator: Generator, name: String) -> Self {
+        let kaleidoscope = Kaleidoscope::new(p, q);
+        let m = kaleidoscope.mirrors;
+
+        let points = match generator {
+            Generator::Reflect(active) => kaleidoscope.orbit(
+                kaleidoscope.generator(active),
+                &[&|v| reflect(v, &m[0]), &|v| reflect(v, &m[1]), &|v| {
+                    reflect(v, &m[2])
+                }],
+            ),
+            Generator::Snub => kaleidoscope.orbit(
+                kaleidoscope.snub_generator(),
+                &[&|v| kaleidoscope.rotate(v, 0), &|v| {
+                    kaleidoscope.rotate(v, 2)
+                }],
+            ),
+        };
+
+        Self {
+            face_index: faces_of(&points),
+            points: points
+                .iter()
+                .map(|v| Point3::new(v[0] as _, v[1] as _, v[2] as _))
+                .collect(),
+            name,
+            topology: Default::default(),
+            classes: Default::default(),
+        }
+    }
+
+    /// Creates the regular tiling `{p, q}` of the sphere: regular
+    /// `p`-gons, `q` of which meet at every vertex.
+    ///
+    /// `{3, 3}`, `{4, 3}`, `{3, 4}`, `{5, 3}` and `{3, 5}` are the
+    /// tetrahedron, cube, octahedron, dodecahedron and icosahedron.
+    ///
+    /// # Errors
+    /// Returns [`Error::SchlafliSymbol`] unless `p` and `q` are at
+    /// least three and `(p - 2)(q - 2) < 4`, i.e. `1/p + 1/q > 1/2`.
+    pub fn regular(p: u32, q: u32) -> Result<Self, Error> {
+        if p < 3 || q < 3 || (p as u64 - 2) * (q as u64 - 2) >= 4 {
+            return Err(Error::SchlafliSymbol { p, q });
+        }
+
+        Ok(Self::wythoff(
+            p,
+            q,
+            Generator::Reflect([true, false, false]),
+            format!("SPHERICAL-{}-{}", p, q),
+        ))
+    }
+
+    /// Creates the semi-regular tiling of the sphere corresponding to
+    /// `solid`.
+    ///
+    /// ![](https://upload.wikimedia.org/wikipedia/commons/thumb/b/b3/Uniform_tiling_532-t012.png/1920px-Uniform_tiling_532-t012.png)
+    pub fn archimedean(solid: Archimedean) -> Self {
+        let (p, q, generator) = solid.construction();
+        Self::wythoff(p, q, generator, solid.name().to_string())
+    }
+}
+
+#[test]
+fn spherical() {
+    assert!(SphericalTiling::regular(6, 3).is_err());
+    assert!(SphericalTiling::regular(u32::MAX, u32::MAX).is_err());
+
+    for &(p, q, vertices, faces) in &[
+        (3, 3, 4, 4),
+        (4, 3, 8, 6),
+        (3, 4, 6, 8),
+        (5, 3, 20, 12),
+        (3, 5, 12, 20),
+    ] {
+        let tiling = SphericalTiling::regular(p, q).unwrap();
+        assert_eq!(tiling.points().len(), vertices);
+        assert_eq!(tiling.faces().len(), faces);
+        assert!(tiling.faces().iter().all(|face| face.len() == p as usize));
+    }
+
+    for &(solid, vertices, faces) in &[
+        (Archimedean::TruncatedTetrahedron, 12, 8),
+        (Archimedean::Cuboctahedron, 12, 14),
+        (Archimedean::TruncatedCube, 24, 14),
+        (Archimedean::TruncatedOctahedron, 24, 14),
+        (Archimedean::Rhombicuboctahedron, 24, 26),
+        (Archimedean::TruncatedCuboctahedron, 48, 26),
+        (Archimedean::SnubCube, 24, 38),
+        (Archimedean::Icosidodecahedron, 30, 32),
+        (Archimedean::TruncatedDodecahedron, 60, 32),
+        (Archimedean::TruncatedIcosahedron, 60, 32),
+        (Archimedean::Rhombicosidodecahedron, 60, 62),
+        (Archimedean::TruncatedIcosidodecahedron, 120, 62),
+        (Archimedean::SnubDodecahedron, 60, 92),
+    ] {
+        let tiling = SphericalTiling::archimedean(solid);
+        assert_eq!(tiling.points().len(), vertices, "{:?}", solid);
+        assert_eq!(tiling.faces().len(), faces, "{:?}", solid);
+
+        // Faces wind counter-clockwise seen from outside.
+        for face in tiling.faces() {
+            let [a, b, c] = [0, 1, 2].map(|i| tiling.points()[face[i] as usize]);
+            assert!((b - a).cross(c - b).dot(a) > 0.0);
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! [STL](https://en.wikipedia.org/wiki/STL_(file_format)) writer.
+use crate::{polygon, Encoding, FaceIndex, Position};
+use std::io::Write;
+
+/// Writes the mesh with every face split into triangles.
+pub(crate) fn write<P: Position>(
+    name: &str,
+    points: &[P],
+    face_index: &FaceIndex,
+    encoding: Encoding,
+    reverse_face_winding: bool,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let triangles: Vec<[[f32; 3]; 3]> = face_index
+        .iter()
+        .flat_map(|face| {
+            let corners: Vec<[f32; 3]> = face.iter().map(|&v| points[v as usize].xyz()).collect();
+            polygon::triangulate(&corners)
+                .into_iter()
+                .map(move |[a, b, c]| {
+                    if reverse_face_winding {
+                        [corners[c], corners[b], corners[a]]
+                    } else {
+                        [corners[a], corners[b], corners[c]]
+                    }
+                })
+        })
+        .collect();
+
+    let mut file = Vec::new();
+
+    match encoding {
+        Encoding::Ascii => {
+            writeln!(file, "solid {}-tiling", name)?;
+            for triangle in &triangles {
+                let [x, y, z] = normal(triangle);
+                writeln!(file, "facet normal {} {} {}", x, y, z)?;
+                writeln!(file, "outer loop")?;
+                for [x, y, z] in triangle {
+                    writeln!(file, "vertex {} {} {}", x, y, z)?;
+                }
+                writeln!(file, "endloop")?;
+                writeln!(file, "endfacet")?;
+            }
+            writeln!(file, "endsolid {}-tiling", name)?;
+        }
+        Encoding::Binary => {
+            let mut header = [0u8; 80];
+            let title = format!("{}-tiling", name);
+            let length = title.len().min(header.len());
+            header[..length].copy_from_slice(&title.as_bytes()[..length]);
+            file.write_all(&header)?;
+            file.write_all(&(triangles.len() as u32).to_le_bytes())?;
+
+            for triangle in &triangles {
+                for coordinate in normal(triangle).iter().chain(triangle.iter().flatten()) {
+                    file.write_all(&coordinate.to_le_bytes())?;
+                }
+                // Attribute byte count.
+                file.write_all(&[0, 0])?;
+            }
+        }
+    }
+
+    Ok(file)
+}
+
+fn normal([a, b, c]: &[[f32; 3]; 3]) -> [f32; 3] {
+    let u = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+    let v = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+    let n = [
+        u[1] * v[2] - u[2] * v[1],
+        u[2] * v[0] - u[0] * v[2],
+        u[0] * v[1] - u[1] * v[0],
+    ];
+    let length = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+    if length > 0.0 {
+        [n[0] / length, n[1] / length, n[2] / length]
+    } else {
+        n
+    }
+}
+
+#[test]
+fn stl() -> Result<(), Box<dyn std::error::Error>> {
+    // Hats are not convex.
+    let tiling = crate::AperiodicTiling::hat(1);
+    let triangles: usize = tiling.faces().iter().map(|face| face.len() - 2).sum();
+
+    let ascii = String::from_utf8(tiling.to_stl(Encoding::Ascii, false)?)?;
+    assert_eq!(ascii.matches("facet normal").count(), triangles);
+    for line in ascii
+        .lines()
+        .filter(|line| line.starts_with("facet normal"))
+    {
+        let normal: Vec<f32> = line[13..].split(' ').map(|x| x.parse().unwrap()).collect();
+        assert_eq!(normal, [0.0, 0.0, 1.0]);
+    }
+
+    let binary = tiling.to_stl(Encoding::Binary, true)?;
+    assert_eq!(binary.len(), 84 + 50 * triangles);
+    assert_eq!(&binary[80..84], &(triangles as u32).to_le_bytes());
+    // The first normal points down once the winding is reversed.
+    assert_eq!(&binary[92..96], &(-1.0f32).to_le_bytes());
+
+    Ok(())
+}
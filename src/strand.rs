@@ -0,0 +1,229 @@
+//! Strands joined from pieces of line that meet on the edges of a
+//! tiling, and their over/under crossings.
+use crate::{affine::DPoint, Point, Polyline};
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
+
+/// A path through a pattern, e.g. a line of a star pattern.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Strand {
+    /// The points along the strand.
+    pub points: Polyline,
+    /// Whether the last point connects back to the first.
+    pub closed: bool,
+}
+
+/// A point where two strands cross.
+///
+/// Both sides are given as the index of the strand and the index of
+/// the crossing in the strand's points, e.g. to lift or lower a tube
+/// following the strand there.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Crossing {
+    /// Where the strands cross.
+    pub position: Point,
+    /// The strand passing over and the index of the crossing in it.
+    pub over: (usize, usize),
+    /// The strand passing under and the index of the crossing in it.
+    pub under: (usize, usize),
+}
+
+/// A polyline whose ends are identified by keys. Pieces with an equal
+/// end key are joined there.
+pub(crate) struct Piece<K> {
+    pub(crate) points: Vec<DPoint>,
+    pub(crate) ends: [K; 2],
+}
+
+/// Where two pieces cross away from their ends. A piece's parameter is
+/// the index of the segment plus the fraction along it.
+pub(crate) type InteriorCrossing = ([(usize, f64); 2], DPoint);
+
+/// Joins `pieces` into strands, open ones first.
+///
+/// Crossings are given by the pieces crossing in their interior and by
+/// pairs of end keys whose joints lie on top of each other. Crossings
+/// whose joints are not on a strand, e.g. at the boundary, are dropped.
+/// Along every strand, crossings alternate between over and under as
+/// far as possible.
+pub(crate) fn assemble<K: Copy + Eq + Hash>(
+    pieces: &[Piece<K>],
+    interior: &[InteriorCrossing],
+    joints: &[[K; 2]],
+) -> (Vec<Strand>, Vec<Crossing>) {
+    let mut ends: HashMap<K, Vec<(usize, usize)>> = HashMap::new();
+    for (p, piece) in pieces.iter().enumerate() {
+        for (e, &key) in piece.ends.iter().enumerate() {
+            ends.entry(key).or_default().push((p, e));
+        }
+    }
+
+    // Interior crossings along each piece as (parameter, crossing,
+    // side).
+    let mut along: Vec<Vec<(f64, usize, usize)>> = vec![Vec::new(); pieces.len()];
+    for (c, (sides, _)) in interior.iter().enumerate() {
+        for (side, &(p, t)) in sides.iter().enumerate() {
+            along[p].push((t, c, side));
+        }
+    }
+    for crossings in &mut along {
+        crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+    }
+
+    let mut strands: Vec<(Vec<DPoint>, bool)> = Vec::new();
+    // (strand, point) of both sides of each interior crossing.
+    let mut occurrences = vec![[(0, 0); 2]; interior.len()];
+    let mut joint_points: HashMap<K, (usize, usize)> = HashMap::new();
+    let mut visited = vec![false; pieces.len()];
+
+    for open in [true, false] {
+        for start in 0..pieces.len() {
+            if visited[start] {
+                continue;
+            }
+            // Open strands start at a free end.
+            let start_end = if open {
+                match (0..2).find(|&e| ends[&pieces[start].ends[e]].len() == 1) {
+                    Some(e) => e,
+                    None => continue,
+                }
+            } else {
+                0
+            };
+
+            let s = strands.len();
+            let mut points: Vec<DPoint> = Vec::new();
+            let mut closed = false;
+            let (mut p, mut e) = (start, start_end);
+            loop {
+                visited[p] = true;
+                let piece = &pieces[p];
+                let n = piece.points.len();
+                let forward = e == 0;
+                let mut crossings = along[p].clone();
+                if !forward {
+                    crossings.reverse();
+                }
+
+                for i in 0..n {
+                    if i > 0 {
+                        let segment = if forward { i - 1 } else { n - 1 - i };
+                        for &(t, c, side) in &crossings {
+                            if t.floor() as usize == segment {
+                                occurrences[c][side] = (s, points.len());
+                                points.push(interior[c].1);
+                            }
+                        }
+                    }
+                    // The first point is the joint with the previous
+                    // piece.
+                    if i > 0 || points.is_empty() {
+                        points.push(piece.points[if forward { i } else { n - 1 - i }]);
+                    }
+                }
+
+                let key = piece.ends[1 - e];
+                match ends[&key].iter().find(|&&end| end != (p, 1 - e)) {
+                    Some(&(next, next_end)) if (next, next_end) == (start, start_end) => {
+                        points.pop();
+                        joint_points.insert(key, (s, 0));
+                        closed = true;
+                        break;
+                    }
+                    Some(&(next, next_end)) if !visited[next] => {
+                        joint_points.insert(key, (s, points.len() - 1));
+                        p = next;
+                        e = next_end;
+                    }
+                    _ => break,
+                }
+            }
+            strands.push((points, closed));
+        }
+    }
+
+    occurrences.extend(
+        joints
+            .iter()
+            .filter_map(|[a, b]| Some([*joint_points.get(a)?, *joint_points.get(b)?])),
+    );
+
+    // Consecutive crossings along a strand alternate, so fixing one
+    // crossing fixes all crossings connected to it.
+    let mut by_strand: Vec<Vec<(usize, usize, usize)>> = vec![Vec::new(); strands.len()];
+    for (c, sides) in occurrences.iter().enumerate() {
+        for (side, &(s, i)) in sides.iter().enumerate() {
+            by_strand[s].push((i, c, side));
+        }
+    }
+    let mut rank = vec![[0; 2]; occurrences.len()];
+    for list in &mut by_strand {
+        list.sort_unstable();
+        for (r, &(_, c, side)) in list.iter().enumerate() {
+            rank[c][side] = r;
+        }
+    }
+
+    let mut over: Vec<Option<usize>> = vec![None; occurrences.len()];
+    let mut queue = VecDeque::new();
+    for first in 0..occurrences.len() {
+        if over[first].is_some() {
+            continue;
+        }
+        over[first] = Some(0);
+        queue.push_back(first);
+
+        while let Some(c) = queue.pop_front() {
+            for side in 0..2 {
+                let is_over = over[c] == Some(side);
+                let (s, _) = occurrences[c][side];
+                let (list, r) = (&by_strand[s], rank[c][side]);
+                let mut neighbours = vec![];
+                if r > 0 {
+                    neighbours.push(r - 1);
+                } else if strands[s].1 && list.len() > 1 {
+                    neighbours.push(list.len() - 1);
+                }
+                if r + 1 < list.len() {
+                    neighbours.push(r + 1);
+                } else if strands[s].1 && list.len() > 1 {
+                    neighbours.push(0);
+                }
+
+                for neighbour in neighbours {
+                    let (_, next, next_side) = list[neighbour];
+                    if over[next].is_none() {
+                        over[next] = Some(if is_over { 1 - next_side } else { next_side });
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+    }
+
+    let to_point = |p: DPoint| Point::new(p[0] as f32, p[1] as f32);
+    let crossings = occurrences
+        .iter()
+        .zip(over)
+        .map(|(sides, over)| {
+            let over = over.unwrap();
+            let (s, i) = sides[over];
+            Crossing {
+                position: to_point(strands[s].0[i]),
+                over: sides[over],
+                under: sides[1 - over],
+            }
+        })
+        .collect();
+    let strands = strands
+        .into_iter()
+        .map(|(points, closed)| Strand {
+            points: points.into_iter().map(to_point).collect(),
+            closed,
+        })
+        .collect();
+
+    (strands, crossings)
+}
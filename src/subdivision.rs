@@ -0,0 +1,411 @@
+//! Subdivision schemes refining the faces of a tiling.
+use crate::{topology, FaceIndex, Mesh, Position};
+use std::collections::HashMap;
+
+/// A subdivision scheme.
+///
+/// The triangle schemes [`Loop`](Subdivision::Loop) and
+/// [`Sqrt3`](Subdivision::Sqrt3) first split faces with more than
+/// three corners into triangles around their centroid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Subdivision {
+    /// Catmull-Clark: every face with `n` corners becomes `n`
+    /// quadrilaterals and the surface is smoothed.
+    CatmullClark,
+    /// Loop: every triangle becomes four and the surface is smoothed.
+    Loop,
+    /// Kobbelt's √3: a vertex is inserted into every triangle and the
+    /// interior edges are flipped, so every triangle becomes three.
+    /// Boundary edges are not split.
+    Sqrt3,
+    /// Like Catmull-Clark for faces with more than three corners and
+    /// like Loop for triangles, but without smoothing: new vertices
+    /// are placed at edge midpoints and face centroids.
+    Midpoint,
+}
+
+type Vector = [f64; 3];
+
+fn add(a: Vector, b: Vector) -> Vector {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn scale(a: Vector, s: f64) -> Vector {
+    a.map(|c| c * s)
+}
+
+fn average(points: impl IntoIterator<Item = Vector>) -> Vector {
+    let (sum, count) = points
+        .into_iter()
+        .fold(([0.0; 3], 0), |(sum, count), p| (add(sum, p), count + 1));
+    scale(sum, 1.0 / count.max(1) as f64)
+}
+
+/// The adjacency a scheme needs.
+struct Neighbourhood {
+    /// Every edge as its end points and faces.
+    edges: Vec<topology::Edge>,
+    /// Edge indices by end points in ascending order.
+    lookup: HashMap<(u32, u32), usize>,
+    /// The vertices sharing an edge with each vertex.
+    neighbours: Vec<Vec<u32>>,
+    /// The vertices sharing a boundary edge with each vertex.
+    boundary: Vec<Vec<u32>>,
+    /// The faces around each vertex.
+    faces: Vec<Vec<usize>>,
+}
+
+impl Neighbourhood {
+    fn new(vertex_count: usize, face_index: &FaceIndex) -> Self {
+        let edges = topology::edges(face_index);
+        let mut lookup = HashMap::new();
+        let mut neighbours = vec![Vec::new(); vertex_count];
+        let mut boundary = vec![Vec::new(); vertex_count];
+        for (e, edge) in edges.iter().enumerate() {
+            let [a, b] = edge.vertices;
+            lookup.insert((a.min(b), a.max(b)), e);
+            neighbours[a as usize].push(b);
+            neighbours[b as usize].push(a);
+            if edge.is_boundary() {
+                boundary[a as usize].push(b);
+                boundary[b as usize].push(a);
+            }
+        }
+        let mut faces = vec![Vec::new(); vertex_count];
+        for (f, face) in face_index.iter().enumerate() {
+            for &v in face {
+                faces[v as usize].push(f);
+            }
+        }
+
+        Self {
+            edges,
+            lookup,
+            neighbours,
+            boundary,
+            faces,
+        }
+    }
+
+    fn edge(&self, a: u32, b: u32) -> usize {
+        self.lookup[&(a.min(b), a.max(b))]
+    }
+
+    fn is_boundary(&self, v: usize) -> bool {
+        !self.boundary[v].is_empty()
+    }
+
+    /// Moves a boundary vertex by the cubic B-spline rule, unless it is
+    /// fixed or the boundary is not a simple curve there.
+    fn boundary_vertex(&self, positions: &[Vector], v: usize, fixed: bool) -> Vector {
+        match self.boundary[v][..] {
+            [a, b] if !fixed => add(
+                scale(positions[v], 0.75),
+                scale(add(positions[a as usize], positions[b as usize]), 0.125),
+            ),
+            _ => positions[v],
+        }
+    }
+}
+
+/// Splits faces with more than three corners into triangles around
+/// their centroid.
+fn triangulate(positions: &mut Vec<Vector>, face_index: &FaceIndex) -> FaceIndex {
+    let mut triangles = FaceIndex::with_capacity(face_index.len());
+    for face in face_index {
+        if face.len() == 3 {
+            triangles.push(face.clone());
+            continue;
+        }
+        positions.push(average(face.iter().map(|&v| positions[v as usize])));
+        let center = positions.len() as u32 - 1;
+        for i in 0..face.len() {
+            triangles.push(vec![face[i], face[(i + 1) % face.len()], center]);
+        }
+    }
+    triangles
+}
+
+pub(crate) fn subdivide<P: Position>(
+    name: &str,
+    points: &[P],
+    face_index: &FaceIndex,
+    scheme: Subdivision,
+    fixed_boundary: bool,
+) -> Mesh<P> {
+    let mut positions: Vec<Vector> = points.iter().map(|p| p.xyz().map(|c| c as f64)).collect();
+    let face_index = match scheme {
+        Subdivision::Loop | Subdivision::Sqrt3 => triangulate(&mut positions, face_index),
+        _ => face_index.clone(),
+    };
+    let n = positions.len();
+    let near = Neighbourhood::new(n, &face_index);
+    let centroids: Vec<Vector> = face_index
+        .iter()
+        .map(|face| average(face.iter().map(|&v| positions[v as usize])))
+        .collect();
+    let midpoint = |a: u32, b: u32| scale(add(positions[a as usize], positions[b as usize]), 0.5);
+
+    // New vertices: the old ones, then one per edge, then one per face
+    // where needed.
+    let edge_key = |a: u32, b: u32| (n + near.edge(a, b)) as u32;
+    let face_key = |f: usize| (n + near.edges.len() + f) as u32;
+    let mut subdivided = FaceIndex::new();
+
+    let (vertices, edge_points, face_points): (Vec<Vector>, Vec<Vector>, Vec<Vector>) = match scheme
+    {
+        Subdivision::CatmullClark => {
+            let edge_points: Vec<Vector> = near
+                .edges
+                .iter()
+                .map(|edge| {
+                    let [a, b] = edge.vertices;
+                    match edge.faces {
+                        (f, Some(g)) => scale(
+                            add(
+                                add(positions[a as usize], positions[b as usize]),
+                                add(centroids[f], centroids[g]),
+                            ),
+                            0.25,
+                        ),
+                        _ => midpoint(a, b),
+                    }
+                })
+                .collect();
+            let vertices = (0..n)
+                .map(|v| {
+                    if near.is_boundary(v) {
+                        return near.boundary_vertex(&positions, v, fixed_boundary);
+                    }
+                    let valence = near.neighbours[v].len() as f64;
+                    if near.faces[v].is_empty() {
+                        return positions[v];
+                    }
+                    let faces = average(near.faces[v].iter().map(|&f| centroids[f]));
+                    let edges = average(near.neighbours[v].iter().map(|&w| midpoint(v as u32, w)));
+                    scale(
+                        add(
+                            add(faces, scale(edges, 2.0)),
+                            scale(positions[v], valence - 3.0),
+                        ),
+                        1.0 / valence,
+                    )
+                })
+                .collect();
+            for (f, face) in face_index.iter().enumerate() {
+                let m = face.len();
+                for i in 0..m {
+                    let (a, b, c) = (face[(i + m - 1) % m], face[i], face[(i + 1) % m]);
+                    subdivided.push(vec![face_key(f), edge_key(a, b), b, edge_key(b, c)]);
+                }
+            }
+            (vertices, edge_points, centroids)
+        }
+        Subdivision::Loop => {
+            let opposite = |f: usize, a: u32, b: u32| {
+                face_index[f]
+                    .iter()
+                    .copied()
+                    .find(|&v| v != a && v != b)
+                    .unwrap()
+            };
+            let edge_points: Vec<Vector> = near
+                .edges
+                .iter()
+                .map(|edge| {
+                    let [a, b] = edge.vertices;
+                    match edge.faces {
+                        (f, Some(g)) => add(
+                            scale(add(positions[a as usize], positions[b as usize]), 0.375),
+                            scale(
+                                add(
+                                    positions[opposite(f, a, b) as usize],
+                                    positions[opposite(g, a, b) as usize],
+                                ),
+                                0.125,
+                            ),
+                        ),
+                        _ => midpoint(a, b),
+                    }
+                })
+                .collect();
+            let vertices = (0..n)
+                .map(|v| {
+                    if near.is_boundary(v) {
+                        return near.boundary_vertex(&positions, v, fixed_boundary);
+                    }
+                    let valence = near.neighbours[v].len();
+                    if valence == 0 {
+                        return positions[v];
+                    }
+                    let beta = if valence == 3 {
+                        3.0 / 16.0
+                    } else {
+                        3.0 / (8.0 * valence as f64)
+                    };
+                    add(
+                        scale(positions[v], 1.0 - valence as f64 * beta),
+                        scale(
+                            near.neighbours[v]
+                                .iter()
+                                .fold([0.0; 3], |sum, &w| add(sum, positions[w as usize])),
+                            beta,
+                        ),
+                    )
+                })
+                .collect();
+            for face in &face_index {
+                let [a, b, c] = [face[0], face[1], face[2]];
+                let (ab, bc, ca) = (edge_key(a, b), edge_key(b, c), edge_key(c, a));
+                subdivided.push(vec![a, ab, ca]);
+                subdivided.push(vec![ab, b, bc]);
+                subdivided.push(vec![ca, bc, c]);
+                subdivided.push(vec![ab, bc, ca]);
+            }
+            (vertices, edge_points, Vec::new())
+        }
+        Subdivision::Sqrt3 => {
+            let vertices = (0..n)
+                .map(|v| {
+                    if near.is_boundary(v) {
+                        return near.boundary_vertex(&positions, v, fixed_boundary);
+                    }
+                    let valence = near.neighbours[v].len() as f64;
+                    if valence == 0.0 {
+                        return positions[v];
+                    }
+                    let alpha = (4.0 - 2.0 * (2.0 * std::f64::consts::PI / valence).cos()) / 9.0;
+                    add(
+                        scale(positions[v], 1.0 - alpha),
+                        scale(
+                            average(near.neighbours[v].iter().map(|&w| positions[w as usize])),
+                            alpha,
+                        ),
+                    )
+                })
+                .collect();
+            // Edges are not split, so face points follow the old
+            // vertices directly.
+            let face_key = |f: usize| (n + f) as u32;
+            for edge in &near.edges {
+                let [a, b] = edge.vertices;
+                match edge.faces {
+                    (f, Some(g)) => {
+                        subdivided.push(vec![face_key(f), a, face_key(g)]);
+                        subdivided.push(vec![face_key(g), b, face_key(f)]);
+                    }
+                    (f, None) => subdivided.push(vec![a, b, face_key(f)]),
+                }
+            }
+            (vertices, centroids, Vec::new())
+        }
+        Subdivision::Midpoint => {
+            let edge_points = near
+                .edges
+                .iter()
+                .map(|edge| midpoint(edge.vertices[0], edge.vertices[1]))
+                .collect();
+            for (f, face) in face_index.iter().enumerate() {
+                let m = face.len();
+                if m == 3 {
+                    let [a, b, c] = [face[0], face[1], face[2]];
+                    let (ab, bc, ca) = (edge_key(a, b), edge_key(b, c), edge_key(c, a));
+                    subdivided.push(vec![a, ab, ca]);
+                    subdivided.push(vec![ab, b, bc]);
+                    subdivided.push(vec![ca, bc, c]);
+                    subdivided.push(vec![ab, bc, ca]);
+                } else {
+                    for i in 0..m {
+                        let (a, b, c) = (face[(i + m - 1) % m], face[i], face[(i + 1) % m]);
+                        subdivided.push(vec![face_key(f), edge_key(a, b), b, edge_key(b, c)]);
+                    }
+                }
+            }
+            (positions.clone(), edge_points, centroids)
+        }
+    };
+
+    // Drop the centroids of triangles, which midpoint subdivision does
+    // not use.
+    let mut keys = vec![None; vertices.len() + edge_points.len() + face_points.len()];
+    let mut used = vec![false; keys.len()];
+    for &v in subdivided.iter().flatten() {
+        used[v as usize] = true;
+    }
+    let mut points = Vec::new();
+    for (p, position) in vertices
+        .into_iter()
+        .chain(edge_points)
+        .chain(face_points)
+        .enumerate()
+    {
+        if used[p] {
+            keys[p] = Some(points.len() as u32);
+            points.push(P::from_xyz(position.map(|c| c as f32)));
+        }
+    }
+    for face in &mut subdivided {
+        for v in face.iter_mut() {
+            *v = keys[*v as usize].unwrap();
+        }
+    }
+
+    Mesh::new(&format!("{}-subdivided", name), points, subdivided)
+}
+
+#[test]
+fn subdivision_schemes() {
+    use crate::{Point, Point3, RegularTiling, SphericalTiling};
+
+    // A flat grid stays a grid, with its boundary fixed or not.
+    let squares = RegularTiling::square(5, 5);
+    for &fixed in &[true, false] {
+        let refined = squares.subdivide(Subdivision::CatmullClark, fixed);
+        assert_eq!(refined.faces().len(), 4 * squares.faces().len());
+        assert_eq!(
+            refined.points().len(),
+            squares.points().len() + squares.edges().len() + squares.faces().len()
+        );
+        let corner: Point = refined.points()[0];
+        assert_eq!(corner == Point::zero(), fixed);
+        for p in refined.points() {
+            let inside = |c: f32| (0.0..=4.0).contains(&c);
+            assert!(inside(p.x) && inside(p.y));
+        }
+    }
+    let midpoints = squares.subdivide(Subdivision::Midpoint, false);
+    assert_eq!(
+        &midpoints.points()[..squares.points().len()],
+        &squares.points()[..]
+    );
+
+    // Closed polyhedra shrink towards the center.
+    let cube = SphericalTiling::regular(4, 3).unwrap();
+    let smooth = cube.subdivide(Subdivision::CatmullClark, false);
+    assert_eq!(smooth.points().len(), 8 + 12 + 6);
+    assert_eq!(smooth.faces().len(), 24);
+    assert!(smooth.points().iter().all(|p| p.mag() < 1.0));
+
+    let icosahedron = SphericalTiling::regular(3, 5).unwrap();
+    let looped = icosahedron.subdivide(Subdivision::Loop, false);
+    assert_eq!((looped.points().len(), looped.faces().len()), (12 + 30, 80));
+    let sqrt3 = icosahedron.subdivide(Subdivision::Sqrt3, false);
+    assert_eq!((sqrt3.points().len(), sqrt3.faces().len()), (12 + 20, 60));
+    for mesh in &[looped, sqrt3] {
+        assert!(mesh.points().iter().all(|p: &Point3| p.mag() < 1.0));
+        // Still closed: every edge has two faces.
+        assert!(mesh.edges().iter().all(|edge| !edge.is_boundary()));
+    }
+
+    // Triangles get four triangles each, other faces a quadrilateral
+    // per corner.
+    let mixed = crate::SemiRegularTiling::seven(20, 20);
+    let expected: usize = mixed
+        .faces()
+        .iter()
+        .map(|face| if face.len() == 3 { 4 } else { face.len() })
+        .sum();
+    let refined = mixed.subdivide(Subdivision::Midpoint, true);
+    assert_eq!(refined.faces().len(), expected);
+    assert!(refined.faces().iter().all(|face| face.len() <= 4));
+}
@@ -0,0 +1,259 @@
+//! Planar tilings wrapped onto surfaces in 3D.
+use crate::{builder::WELD_TOLERANCE, Face, FaceIndex, Mesh, Point, Point3, Position};
+use std::{collections::HashMap, f32::consts::PI};
+
+/// A surface parametrized over the unit square `(u, v)`.
+///
+/// The bounding box of a tiling is stretched onto the unit square, so
+/// the tiling wraps around exactly once. Counter-clockwise faces end up
+/// facing outwards.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Surface {
+    /// `u` runs around the `z` axis and `v` up from `0` to `height`.
+    Cylinder { radius: f32, height: f32 },
+    /// Like a cylinder, but narrowing from `radius` at the bottom to the
+    /// apex at `height`.
+    Cone { radius: f32, height: f32 },
+    /// `u` runs around the `z` axis and `v` around the tube.
+    Torus {
+        major_radius: f32,
+        minor_radius: f32,
+    },
+    /// `u` is the longitude and `v` the latitude from the south to the
+    /// north pole.
+    Sphere { radius: f32 },
+}
+
+impl Surface {
+    /// The position at `(u, v)`.
+    pub fn position(self, u: f32, v: f32) -> Point3 {
+        let angle = 2.0 * PI * u;
+        match self {
+            Surface::Cylinder { radius, height } => {
+                Point3::new(radius * angle.cos(), radius * angle.sin(), height * v)
+            }
+            Surface::Cone { radius, height } => {
+                let radius = radius * (1.0 - v);
+                Point3::new(radius * angle.cos(), radius * angle.sin(), height * v)
+            }
+            Surface::Torus {
+                major_radius,
+                minor_radius,
+            } => {
+                let tube = 2.0 * PI * v;
+                let radius = major_radius + minor_radius * tube.cos();
+                Point3::new(
+                    radius * angle.cos(),
+                    radius * angle.sin(),
+                    minor_radius * tube.sin(),
+                )
+            }
+            Surface::Sphere { radius } => {
+                let latitude = PI * (v - 0.5);
+                Point3::new(
+                    radius * latitude.cos() * angle.cos(),
+                    radius * latitude.cos() * angle.sin(),
+                    radius * latitude.sin(),
+                )
+            }
+        }
+    }
+}
+
+/// Maps the `x`, `y` coordinates of every point with `f`, after
+/// splitting every edge into `segments` pieces.
+///
+/// Points inserted on an edge are shared by both faces along it, so
+/// the result has no cracks. If `weld` is set, points mapped onto the
+/// same position, e.g. on a seam or at a pole, become one.
+pub(crate) fn map<P: Position>(
+    name: &str,
+    points: &[P],
+    face_index: &FaceIndex,
+    f: impl Fn(Point) -> Point3,
+    segments: u32,
+    weld: bool,
+) -> Mesh<Point3> {
+    let mut planar: Vec<Point> = points
+        .iter()
+        .map(|p| {
+            let [x, y, _] = p.xyz();
+            Point::new(x, y)
+        })
+        .collect();
+
+    let segments = segments.max(1);
+    // Points inserted on an edge, in order from its lower to its
+    // higher vertex.
+    let mut splits: HashMap<(u32, u32), Vec<u32>> = HashMap::new();
+    let mut mapped_faces = FaceIndex::with_capacity(face_index.len());
+
+    for face in face_index {
+        let mut mapped = Vec::with_capacity(face.len() * segments as usize);
+        for i in 0..face.len() {
+            let (a, b) = (face[i], face[(i + 1) % face.len()]);
+            mapped.push(a);
+            if segments == 1 {
+                continue;
+            }
+
+            let (low, high) = (a.min(b), a.max(b));
+            let inserted = splits.entry((low, high)).or_insert_with(|| {
+                let (p, q) = (planar[low as usize], planar[high as usize]);
+                (1..segments)
+                    .map(|s| {
+                        planar.push(p + (q - p) * (s as f32 / segments as f32));
+                        planar.len() as u32 - 1
+                    })
+                    .collect()
+            });
+            if a == low {
+                mapped.extend(inserted.iter());
+            } else {
+                mapped.extend(inserted.iter().rev());
+            }
+        }
+        mapped_faces.push(mapped);
+    }
+
+    let mut positions: Vec<Point3> = planar.into_iter().map(f).collect();
+    if weld {
+        (positions, mapped_faces) = weld_points(&positions, &mapped_faces);
+    }
+
+    Mesh::new(&format!("{}-mapped", name), positions, mapped_faces)
+}
+
+/// Merges points closer than [`WELD_TOLERANCE`]. Corners of a face that
+/// become one, e.g. along an edge squeezed into a pole, are kept once.
+fn weld_points(points: &[Point3], face_index: &FaceIndex) -> (Vec<Point3>, FaceIndex) {
+    let cell = |p: Point3| [p.x, p.y, p.z].map(|c| (c as f64 / WELD_TOLERANCE).floor() as i64);
+
+    let mut welded: Vec<Point3> = Vec::new();
+    let mut cells: HashMap<[i64; 3], Vec<u32>> = HashMap::new();
+    let remap: Vec<u32> = points
+        .iter()
+        .map(|&p| {
+            let [x, y, z] = cell(p);
+            for neighbour in (x - 1..=x + 1).flat_map(|x| {
+                (y - 1..=y + 1).flat_map(move |y| (z - 1..=z + 1).map(move |z| [x, y, z]))
+            }) {
+                for &key in cells.get(&neighbour).into_iter().flatten() {
+                    let q = welded[key as usize];
+                    if [q.x - p.x, q.y - p.y, q.z - p.z]
+                        .iter()
+                        .all(|d| (d.abs() as f64) < WELD_TOLERANCE)
+                    {
+                        return key;
+                    }
+                }
+            }
+            welded.push(p);
+            let key = welded.len() as u32 - 1;
+            cells.entry([x, y, z]).or_default().push(key);
+            key
+        })
+        .collect();
+
+    let face_index = face_index
+        .iter()
+        .map(|face| {
+            let mut welded_face: Face = face.iter().map(|&v| remap[v as usize]).collect();
+            welded_face.dedup();
+            if welded_face.len() > 1 && welded_face.first() == welded_face.last() {
+                welded_face.pop();
+            }
+            welded_face
+        })
+        .collect();
+
+    (welded, face_index)
+}
+
+/// The function mapping the bounding box of `points` onto `surface`.
+pub(crate) fn parametrization<P: Position>(
+    points: &[P],
+    surface: Surface,
+) -> impl Fn(Point) -> Point3 {
+    let mut min = [f32::MAX; 2];
+    let mut max = [f32::MIN; 2];
+    for p in points {
+        let xyz = p.xyz();
+        for k in 0..2 {
+            min[k] = min[k].min(xyz[k]);
+            max[k] = max[k].max(xyz[k]);
+        }
+    }
+    let extent = [0, 1].map(|k| {
+        let extent = max[k] - min[k];
+        if extent > 0.0 {
+            extent
+        } else {
+            1.0
+        }
+    });
+
+    move |p: Point| surface.position((p.x - min[0]) / extent[0], (p.y - min[1]) / extent[1])
+}
+
+#[test]
+fn map_onto_surfaces() {
+    let tiling = crate::RegularTiling::square(4, 8);
+    let edges = tiling.edges().len();
+
+    let cylinder = tiling.map_onto(
+        Surface::Cylinder {
+            radius: 2.0,
+            height: 3.0,
+        },
+        3,
+    );
+    assert_eq!(cylinder.faces().len(), tiling.faces().len());
+    // The 4 points and 3 edges on the seam are welded.
+    assert_eq!(
+        cylinder.points().len(),
+        tiling.points().len() + 2 * edges - 4 - 2 * 3
+    );
+    // Subdivided edges are still shared by two faces, and only the top
+    // and bottom are open.
+    assert_eq!(cylinder.edges().len(), 3 * (edges - 3));
+    for edge in cylinder.edges().iter().filter(|edge| edge.is_boundary()) {
+        let [a, b] = edge.vertices.map(|v| cylinder.points()[v as usize].z);
+        assert!(a == b && (a == 0.0 || (a - 3.0).abs() < 1.0e-6));
+    }
+    for p in cylinder.points() {
+        assert!(((p.x * p.x + p.y * p.y).sqrt() - 2.0).abs() < 1.0e-5);
+        assert!(p.z >= 0.0 && p.z <= 3.0 + 1.0e-6);
+    }
+
+    // Closed surfaces are closed.
+    for surface in [
+        Surface::Sphere { radius: 1.5 },
+        Surface::Torus {
+            major_radius: 2.0,
+            minor_radius: 0.5,
+        },
+    ] {
+        let mesh = tiling.map_onto(surface, 2);
+        assert!(mesh.edges().iter().all(|edge| !edge.is_boundary()));
+        assert!(mesh.faces().iter().all(|face| face.len() >= 3));
+    }
+
+    // Faces at the poles lose the corners squeezed into them.
+    let sphere = tiling.map_onto(Surface::Sphere { radius: 1.5 }, 1);
+    assert_eq!(
+        sphere.faces().iter().filter(|face| face.len() == 3).count(),
+        2 * 7
+    );
+    assert!(sphere
+        .points()
+        .iter()
+        .all(|p| (p.mag() - 1.5).abs() < 1.0e-5));
+
+    // A heightfield.
+    let terrain = tiling.map_with(|p| Point3::new(p.x, p.y, (p.x * p.y).sin()), 2);
+    assert!(terrain
+        .points()
+        .iter()
+        .all(|p| (p.z - (p.x * p.y).sin()).abs() < 1.0e-6));
+}
@@ -0,0 +1,280 @@
+//! Adjacency derived from a face index.
+use crate::FaceIndex;
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::OnceLock,
+};
+
+/// An edge of a tiling, listed once even if two faces share it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Edge {
+    /// The end points, in the order the first face traverses them.
+    pub vertices: [u32; 2],
+    /// The face that traverses the edge from `vertices[0]` to
+    /// `vertices[1]` (i.e. the one on its left for counter-clockwise
+    /// faces) and the face on the other side, if any.
+    ///
+    /// Non-manifold edges only list their first two faces.
+    pub faces: (usize, Option<usize>),
+}
+
+impl Edge {
+    /// Whether only one face has this edge.
+    pub fn is_boundary(&self) -> bool {
+        self.faces.1.is_none()
+    }
+}
+
+/// Unique edges in the order they first occur in `face_index`.
+pub(crate) fn edges(face_index: &FaceIndex) -> Vec<Edge> {
+    let mut edges: Vec<Edge> = Vec::new();
+    let mut lookup = HashMap::new();
+
+    for (f, face) in face_index.iter().enumerate() {
+        for i in 0..face.len() {
+            let (a, b) = (face[i], face[(i + 1) % face.len()]);
+            match lookup.get(&(a.min(b), a.max(b))) {
+                Some(&e) => {
+                    let edge: &mut Edge = &mut edges[e];
+                    if edge.faces.1.is_none() && edge.faces.0 != f {
+                        edge.faces.1 = Some(f);
+                    }
+                }
+                None => {
+                    lookup.insert((a.min(b), a.max(b)), edges.len());
+                    edges.push(Edge {
+                        vertices: [a, b],
+                        faces: (f, None),
+                    });
+                }
+            }
+        }
+    }
+
+    edges
+}
+
+/// The faces around every interior vertex, in counter-clockwise order
+/// for counter-clockwise faces, by ascending vertex.
+///
+/// A vertex is interior if its faces close up around it.
+pub(crate) fn fans(face_index: &FaceIndex) -> Vec<(u32, Vec<usize>)> {
+    let mut owners = HashMap::new();
+    let mut first_face = std::collections::BTreeMap::new();
+    for (f, face) in face_index.iter().enumerate() {
+        for i in 0..face.len() {
+            owners.insert((face[i], face[(i + 1) % face.len()]), f);
+            first_face.entry(face[i]).or_insert(f);
+        }
+    }
+    let previous = |f: usize, v: u32| {
+        let face = &face_index[f];
+        let i = face.iter().position(|&w| w == v).unwrap();
+        face[(i + face.len() - 1) % face.len()]
+    };
+
+    first_face
+        .into_iter()
+        .filter_map(|(v, start)| {
+            let mut fan = vec![start];
+            let mut f = start;
+            loop {
+                // The face across the edge into this corner comes next.
+                f = *owners.get(&(v, previous(f, v)))?;
+                if f == start {
+                    return Some((v, fan));
+                }
+                if fan.len() > face_index.len() {
+                    return None;
+                }
+                fan.push(f);
+            }
+        })
+        .collect()
+}
+
+/// Which faces count as neighbours of a face.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Adjacency {
+    /// Faces sharing an edge.
+    Edge,
+    /// Faces sharing at least a vertex.
+    Vertex,
+}
+
+/// Neighbourhoods of the vertices and faces of a tiling.
+#[derive(Clone, Debug)]
+pub(crate) struct Topology {
+    edges: Vec<Edge>,
+    vertex_neighbours: Vec<Vec<u32>>,
+    vertex_faces: Vec<Vec<usize>>,
+    edge_neighbours: Vec<Vec<usize>>,
+    vertex_neighbour_faces: Vec<Vec<usize>>,
+}
+
+impl Topology {
+    pub(crate) fn new(vertex_count: usize, face_index: &FaceIndex) -> Self {
+        let mut vertex_neighbours = vec![BTreeSet::new(); vertex_count];
+        let mut vertex_faces = vec![Vec::new(); vertex_count];
+        for (f, face) in face_index.iter().enumerate() {
+            for i in 0..face.len() {
+                let (a, b) = (face[i], face[(i + 1) % face.len()]);
+                vertex_neighbours[a as usize].insert(b);
+                vertex_neighbours[b as usize].insert(a);
+                if !vertex_faces[a as usize].contains(&f) {
+                    vertex_faces[a as usize].push(f);
+                }
+            }
+        }
+
+        let edges = edges(face_index);
+        let mut edge_neighbours = vec![Vec::new(); face_index.len()];
+        for edge in &edges {
+            if let (f, Some(g)) = edge.faces {
+                edge_neighbours[f].push(g);
+                edge_neighbours[g].push(f);
+            }
+        }
+        let vertex_neighbour_faces = face_index
+            .iter()
+            .enumerate()
+            .map(|(f, face)| {
+                face.iter()
+                    .flat_map(|&v| vertex_faces[v as usize].iter().copied())
+                    .filter(|&g| g != f)
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .collect()
+            })
+            .collect();
+        for neighbours in &mut edge_neighbours {
+            neighbours.sort_unstable();
+            neighbours.dedup();
+        }
+
+        Self {
+            edges,
+            vertex_neighbours: vertex_neighbours
+                .into_iter()
+                .map(|neighbours| neighbours.into_iter().collect())
+                .collect(),
+            vertex_faces,
+            edge_neighbours,
+            vertex_neighbour_faces,
+        }
+    }
+
+    /// Every edge once, see [`edges()`](edges).
+    pub(crate) fn edges(&self) -> &[Edge] {
+        &self.edges
+    }
+
+    pub(crate) fn vertex_neighbours(&self, v: u32) -> &[u32] {
+        &self.vertex_neighbours[v as usize]
+    }
+
+    pub(crate) fn vertex_faces(&self, v: u32) -> &[usize] {
+        &self.vertex_faces[v as usize]
+    }
+
+    pub(crate) fn face_neighbours(&self, f: usize, adjacency: Adjacency) -> &[usize] {
+        match adjacency {
+            Adjacency::Edge => &self.edge_neighbours[f],
+            Adjacency::Vertex => &self.vertex_neighbour_faces[f],
+        }
+    }
+
+    /// `start` and everything at most `k` steps away from it, ordered by
+    /// distance.
+    pub(crate) fn ring<'a, T: Copy + Ord + 'a, N: Fn(T) -> &'a [T]>(
+        start: T,
+        k: u32,
+        neighbours: N,
+    ) -> Vec<T> {
+        let mut ring = vec![start];
+        let mut seen = BTreeSet::from([start]);
+        let mut frontier = 0;
+        for _ in 0..k {
+            let end = ring.len();
+            for i in frontier..end {
+                for &next in neighbours(ring[i]) {
+                    if seen.insert(next) {
+                        ring.push(next);
+                    }
+                }
+            }
+            if ring.len() == end {
+                break;
+            }
+            frontier = end;
+        }
+        ring
+    }
+}
+
+/// A tiling's [`Topology`], derived from its faces when first needed.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TopologyCache(OnceLock<Topology>);
+
+impl TopologyCache {
+    pub(crate) fn get(&self, vertex_count: usize, face_index: &FaceIndex) -> &Topology {
+        self.0
+            .get_or_init(|| Topology::new(vertex_count, face_index))
+    }
+}
+
+#[test]
+fn edges_of_two_squares() {
+    let face_index = vec![vec![0, 1, 4, 3], vec![1, 2, 5, 4]];
+    let edges = edges(&face_index);
+
+    assert_eq!(edges.len(), 7);
+    assert_eq!(edges.iter().filter(|edge| edge.is_boundary()).count(), 6);
+    assert_eq!(
+        edges.iter().find(|edge| !edge.is_boundary()),
+        Some(&Edge {
+            vertices: [1, 4],
+            faces: (0, Some(1)),
+        })
+    );
+}
+
+#[test]
+fn fans_of_a_square_grid() {
+    let squares = crate::RegularTiling::square(4, 4);
+    let fans = fans(squares.faces());
+
+    // Only the four inner vertices are surrounded.
+    assert_eq!(fans.len(), 4);
+    for (v, fan) in &fans {
+        assert_eq!(fan.len(), 4);
+        assert!(fan.iter().all(|&f| squares.faces()[f].contains(v)));
+    }
+}
+
+#[test]
+fn neighbourhoods_of_a_square_grid() {
+    let squares = crate::RegularTiling::square(5, 5);
+
+    // The center vertex and a corner.
+    assert_eq!(squares.vertex_neighbours(12), &[7, 11, 13, 17]);
+    assert_eq!(squares.vertex_degree(12), 4);
+    assert_eq!(squares.vertex_degree(0), 2);
+    assert_eq!(squares.vertex_faces(12).len(), 4);
+
+    // An inner face of the 4 × 4 faces.
+    let f = squares
+        .faces()
+        .iter()
+        .position(|face| face.contains(&6) && face.contains(&12))
+        .unwrap();
+    assert_eq!(squares.face_neighbours(f, Adjacency::Edge).len(), 4);
+    assert_eq!(squares.face_neighbours(f, Adjacency::Vertex).len(), 8);
+
+    assert_eq!(squares.vertex_ring(12, 0), vec![12]);
+    assert_eq!(squares.vertex_ring(12, 1).len(), 5);
+    // A diamond of radius two.
+    assert_eq!(squares.vertex_ring(12, 2).len(), 13);
+    assert_eq!(squares.vertex_ring(12, 10).len(), 25);
+    assert_eq!(squares.face_ring(f, 1, Adjacency::Vertex).len(), 9);
+}
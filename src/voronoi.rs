@@ -0,0 +1,364 @@
+//! Delaunay triangulations and Voronoi diagrams of point sets.
+use crate::{
+    affine::DPoint,
+    builder::{MeshBuilder, WELD_TOLERANCE},
+    polygon, FaceIndex, Point, Points, Position,
+};
+use std::collections::HashMap;
+
+/// Points on a circumcircle closer than this to it, relative to its
+/// radius, count as on it.
+const COCIRCULAR_TOLERANCE: f64 = 1.0e-6;
+
+/// The center and squared radius of the circle through `a`, `b`, `c`.
+fn circumcircle(a: DPoint, b: DPoint, c: DPoint) -> (DPoint, f64) {
+    let (bx, by) = (b[0] - a[0], b[1] - a[1]);
+    let (cx, cy) = (c[0] - a[0], c[1] - a[1]);
+    let d = 2.0 * (bx * cy - by * cx);
+    let (b2, c2) = (bx * bx + by * by, cx * cx + cy * cy);
+    let (ux, uy) = ((cy * b2 - by * c2) / d, (bx * c2 - cx * b2) / d);
+    ([a[0] + ux, a[1] + uy], ux * ux + uy * uy)
+}
+
+/// The points without duplicates, in the order they first occur.
+fn distinct(points: impl Iterator<Item = DPoint>) -> Vec<DPoint> {
+    let mut builder = MeshBuilder::new();
+    for p in points {
+        builder.vertex(p);
+    }
+    builder.build_f64().0
+}
+
+/// The counter-clockwise Delaunay triangles of distinct `points`, by
+/// the Bowyer-Watson algorithm.
+fn triangulate(points: &[DPoint]) -> Vec<[usize; 3]> {
+    if points.len() < 3 {
+        return Vec::new();
+    }
+
+    // A triangle containing all points, far enough away not to cut off
+    // triangles along the convex hull.
+    let (mut min, mut max) = (points[0], points[0]);
+    for p in points {
+        min = [min[0].min(p[0]), min[1].min(p[1])];
+        max = [max[0].max(p[0]), max[1].max(p[1])];
+    }
+    let center = [(min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0];
+    let size = 1.0e3 * (max[0] - min[0]).max(max[1] - min[1]).max(1.0);
+    let mut all = points.to_vec();
+    all.extend_from_slice(&[
+        [center[0] - size, center[1] - size],
+        [center[0] + size, center[1] - size],
+        [center[0], center[1] + size],
+    ]);
+    let n = points.len();
+
+    let circle = |[a, b, c]: [usize; 3]| circumcircle(all[a], all[b], all[c]);
+    let mut triangles = vec![([n, n + 1, n + 2], circle([n, n + 1, n + 2]))];
+    for (i, &p) in points.iter().enumerate() {
+        let (bad, good): (Vec<_>, Vec<_>) = triangles.into_iter().partition(|&(_, (c, r2))| {
+            (p[0] - c[0]).powi(2) + (p[1] - c[1]).powi(2) < r2 * (1.0 - COCIRCULAR_TOLERANCE)
+        });
+        triangles = good;
+
+        // The cavity's boundary: edges of exactly one bad triangle.
+        let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+        for (t, _) in &bad {
+            for k in 0..3 {
+                let (a, b) = (t[k], t[(k + 1) % 3]);
+                *edges.entry((a.min(b), a.max(b))).or_default() += 1;
+            }
+        }
+        for (t, _) in &bad {
+            for k in 0..3 {
+                let (a, b) = (t[k], t[(k + 1) % 3]);
+                if edges[&(a.min(b), a.max(b))] == 1 {
+                    triangles.push(([a, b, i], circle([a, b, i])));
+                }
+            }
+        }
+    }
+
+    triangles
+        .into_iter()
+        .map(|(t, _)| t)
+        .filter(|t| t.iter().all(|&v| v < n))
+        .collect()
+}
+
+/// Merges neighbouring triangles with the same circumcircle into
+/// convex polygons.
+fn merge_cocircular(points: &[DPoint], triangles: &[[usize; 3]]) -> FaceIndex {
+    fn root(parent: &mut [usize], mut t: usize) -> usize {
+        while parent[t] != t {
+            parent[t] = parent[parent[t]];
+            t = parent[t];
+        }
+        t
+    }
+
+    let circles: Vec<_> = triangles
+        .iter()
+        .map(|t| circumcircle(points[t[0]], points[t[1]], points[t[2]]))
+        .collect();
+    let mut parent: Vec<usize> = (0..triangles.len()).collect();
+    let mut owners = HashMap::new();
+    for (t, triangle) in triangles.iter().enumerate() {
+        for k in 0..3 {
+            owners.insert((triangle[k], triangle[(k + 1) % 3]), t);
+        }
+    }
+    for (t, triangle) in triangles.iter().enumerate() {
+        for k in 0..3 {
+            if let Some(&u) = owners.get(&(triangle[(k + 1) % 3], triangle[k])) {
+                let ((c, r2), (d, s2)) = (circles[t], circles[u]);
+                let tolerance = COCIRCULAR_TOLERANCE * r2.sqrt();
+                if (c[0] - d[0]).abs() < tolerance
+                    && (c[1] - d[1]).abs() < tolerance
+                    && (r2.sqrt() - s2.sqrt()).abs() < tolerance
+                {
+                    let (rt, ru) = (root(&mut parent, t), root(&mut parent, u));
+                    parent[rt.max(ru)] = rt.min(ru);
+                }
+            }
+        }
+    }
+
+    // Walk the outline of every group from each vertex to the next.
+    let mut outlines: Vec<HashMap<usize, usize>> = vec![HashMap::new(); triangles.len()];
+    for (t, triangle) in triangles.iter().enumerate() {
+        let group = root(&mut parent, t);
+        for k in 0..3 {
+            let (a, b) = (triangle[k], triangle[(k + 1) % 3]);
+            match owners.get(&(b, a)) {
+                Some(&u) if root(&mut parent, u) == group => {}
+                _ => {
+                    outlines[group].insert(a, b);
+                }
+            }
+        }
+    }
+    outlines
+        .into_iter()
+        .filter(|outline| !outline.is_empty())
+        .map(|outline| {
+            let start = *outline.keys().min().unwrap();
+            let mut face = vec![start as u32];
+            let mut v = outline[&start];
+            while v != start {
+                face.push(v as u32);
+                v = outline[&v];
+            }
+            face
+        })
+        .collect()
+}
+
+/// Clips the convex `polygon` to the side of the line through `p` with
+/// normal `normal` it points away from.
+fn clip(polygon: &[DPoint], p: DPoint, normal: DPoint) -> Vec<DPoint> {
+    let side = |q: DPoint| (q[0] - p[0]) * normal[0] + (q[1] - p[1]) * normal[1];
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    for i in 0..polygon.len() {
+        let (a, b) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+        let (sa, sb) = (side(a), side(b));
+        if sa <= 0.0 {
+            clipped.push(a);
+        }
+        if (sa < 0.0 && sb > 0.0) || (sa > 0.0 && sb < 0.0) {
+            let t = sa / (sa - sb);
+            clipped.push([a[0] + t * (b[0] - a[0]), a[1] + t * (b[1] - a[1])]);
+        }
+    }
+    clipped
+}
+
+/// The planar centroid of every face.
+pub(crate) fn centroids<P: Position>(points: &[P], face_index: &FaceIndex) -> Points {
+    face_index
+        .iter()
+        .map(|face| {
+            let corners: Vec<DPoint> = face
+                .iter()
+                .map(|&v| {
+                    let [x, y, _] = points[v as usize].xyz();
+                    [x as f64, y as f64]
+                })
+                .collect();
+            let [x, y] = polygon::centroid(&corners);
+            Point::new(x as _, y as _)
+        })
+        .collect()
+}
+
+/// The planar bounding box of `points`.
+pub(crate) fn bounds<P: Position>(points: &[P]) -> (Point, Point) {
+    let mut min = Point::broadcast(f32::INFINITY);
+    let mut max = Point::broadcast(f32::NEG_INFINITY);
+    for p in points {
+        let [x, y, _] = p.xyz();
+        min = min.min_by_component(Point::new(x, y));
+        max = max.max_by_component(Point::new(x, y));
+    }
+    (min, max)
+}
+
+pub(crate) fn delaunay(
+    points: impl Iterator<Item = DPoint>,
+    cocircular: bool,
+) -> (Points, FaceIndex) {
+    let points = distinct(points);
+    let triangles = triangulate(&points);
+    let face_index = if cocircular {
+        merge_cocircular(&points, &triangles)
+    } else {
+        triangles
+            .iter()
+            .map(|t| t.iter().map(|&v| v as u32).collect())
+            .collect()
+    };
+
+    let points = points
+        .into_iter()
+        .map(|p| Point::new(p[0] as _, p[1] as _))
+        .collect();
+    (points, face_index)
+}
+
+pub(crate) fn voronoi(
+    sites: impl Iterator<Item = DPoint>,
+    min: DPoint,
+    max: DPoint,
+) -> (Points, FaceIndex) {
+    let sites = distinct(sites);
+    let triangles = triangulate(&sites);
+
+    let mut neighbours = vec![Vec::new(); sites.len()];
+    for t in &triangles {
+        for k in 0..3 {
+            let (a, b) = (t[k], t[(k + 1) % 3]);
+            neighbours[a].push(b);
+            neighbours[b].push(a);
+        }
+    }
+    // Without triangles, e.g. for collinear sites, every site bounds
+    // every other.
+    if triangles.is_empty() {
+        for (i, neighbours) in neighbours.iter_mut().enumerate() {
+            neighbours.extend((0..sites.len()).filter(|&j| j != i));
+        }
+    }
+
+    let mut builder = MeshBuilder::new();
+    for (i, neighbours) in neighbours.iter_mut().enumerate() {
+        neighbours.sort_unstable();
+        neighbours.dedup();
+
+        let mut cell = vec![min, [max[0], min[1]], max, [min[0], max[1]]];
+        let p = sites[i];
+        for &j in neighbours.iter() {
+            let q = sites[j];
+            cell = clip(
+                &cell,
+                [(p[0] + q[0]) / 2.0, (p[1] + q[1]) / 2.0],
+                [q[0] - p[0], q[1] - p[1]],
+            );
+        }
+
+        // Drop corners the clipping left (nearly) on top of each other.
+        let close = |a: DPoint, b: DPoint| {
+            (a[0] - b[0]).abs() < WELD_TOLERANCE && (a[1] - b[1]).abs() < WELD_TOLERANCE
+        };
+        let mut corners: Vec<DPoint> = Vec::with_capacity(cell.len());
+        for corner in cell {
+            if !matches!(corners.last(), Some(&last) if close(last, corner)) {
+                corners.push(corner);
+            }
+        }
+        while corners.len() > 1 && close(corners[0], corners[corners.len() - 1]) {
+            corners.pop();
+        }
+        if corners.len() >= 3 && polygon::signed_area(&corners) > 0.0 {
+            builder.polygon(&corners);
+        }
+    }
+    builder.split_t_junctions();
+    builder.build()
+}
+
+#[test]
+fn delaunay_and_voronoi() {
+    use crate::{Mesh, RegularTiling};
+
+    // Jittered points: no point lies inside a triangle's circumcircle
+    // and the triangles cover the convex hull of the square's corners.
+    let mut random = crate::random::Random::new(5);
+    let mut points = vec![
+        Point::new(0.0, 0.0),
+        Point::new(10.0, 0.0),
+        Point::new(10.0, 10.0),
+        Point::new(0.0, 10.0),
+    ];
+    for y in 1..10 {
+        for x in 1..10 {
+            let jitter = |random: &mut crate::random::Random| (random.unit() - 0.5) as f32 * 0.8;
+            points.push(Point::new(
+                x as f32 + jitter(&mut random),
+                y as f32 + jitter(&mut random),
+            ));
+        }
+    }
+    let triangles = Mesh::delaunay(&points, false);
+    assert_eq!(triangles.points().len(), points.len());
+    assert!(triangles.faces().iter().all(|face| face.len() == 3));
+    assert!((polygon::mesh_area(&triangles) - 100.0).abs() < 1.0e-3);
+    for face in triangles.faces() {
+        let corner = |k: usize| {
+            let p = triangles.points()[face[k] as usize];
+            [p.x as f64, p.y as f64]
+        };
+        let (c, r2) = circumcircle(corner(0), corner(1), corner(2));
+        for p in triangles.points() {
+            let d2 = (p.x as f64 - c[0]).powi(2) + (p.y as f64 - c[1]).powi(2);
+            assert!(d2 > r2 * (1.0 - 1.0e-4));
+        }
+    }
+
+    // Voronoi cells tile the rectangle, one per site.
+    let cells = Mesh::voronoi(&points, Point::new(-1.0, -1.0), Point::new(11.0, 11.0));
+    assert_eq!(cells.faces().len(), points.len());
+    assert!((polygon::mesh_area(&cells) - 144.0).abs() < 1.0e-3);
+    assert!(cells.edges().iter().filter(|e| e.is_boundary()).count() >= 4);
+
+    // For a square grid, the Delaunay "triangulation" of the centroids
+    // is the dual and their Voronoi cells the squares themselves.
+    let squares = RegularTiling::square(5, 5);
+    let dual = squares.centroid_delaunay(true);
+    assert_eq!(dual.faces().len(), squares.dual().faces().len());
+    assert!(dual.faces().iter().all(|face| face.len() == 4));
+    assert_eq!(squares.centroid_delaunay(false).faces().len(), 18);
+    let cells = squares.centroid_voronoi();
+    assert_eq!(cells.faces().len(), 16);
+    assert!(cells.faces().iter().all(|face| face.len() == 4));
+    assert!((polygon::mesh_area(&cells) - 16.0).abs() < 1.0e-4);
+
+    // Centroids of hexagons triangulate into unit triangles, scaled by
+    // √3, and the cells of inner hexagons are the hexagons.
+    let hexagons = RegularTiling::hexagon(12, 12);
+    let dual = hexagons.centroid_delaunay(true);
+    let inner = dual
+        .faces()
+        .iter()
+        .filter(|face| face.len() == 3)
+        .filter(|face| {
+            (0..3).all(|k| {
+                let a = dual.points()[face[k] as usize];
+                let b = dual.points()[face[(k + 1) % 3] as usize];
+                ((a - b).mag() - 3f32.sqrt()).abs() < 1.0e-4
+            })
+        })
+        .count();
+    assert!(inner >= hexagons.dual().faces().len());
+    let cells = hexagons.centroid_voronoi();
+    assert!(cells.faces().iter().any(|face| face.len() == 6));
+}
@@ -0,0 +1,145 @@
+//! Strands following the medial graph of a tiling, woven over and
+//! under each other.
+use crate::{
+    affine::DPoint,
+    strand::{self, Crossing, Piece, Strand},
+    FaceIndex, Mesh, Point3, Position,
+};
+use std::collections::{BTreeSet, HashMap};
+
+/// The strands of a weave.
+///
+/// Strands run from edge midpoint to edge midpoint, crossing straight
+/// over every edge shared by two faces. Where strands meet, they
+/// alternate between passing over and under.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Weave {
+    /// The strands, each joined from edge midpoint to edge midpoint.
+    pub strands: Vec<Strand>,
+    /// The points where two strands cross.
+    pub crossings: Vec<Crossing>,
+}
+
+impl Weave {
+    /// The strands in 3D, raised by `height` where they pass over and
+    /// lowered by it where they pass under, e.g. as the center lines of
+    /// tubes.
+    pub fn lift(&self, height: f32) -> Vec<Vec<Point3>> {
+        let mut lifted: Vec<Vec<Point3>> = self
+            .strands
+            .iter()
+            .map(|strand| {
+                strand
+                    .points
+                    .iter()
+                    .map(|p| Point3::new(p.x, p.y, 0.0))
+                    .collect()
+            })
+            .collect();
+        for crossing in &self.crossings {
+            lifted[crossing.over.0][crossing.over.1].z = height;
+            lifted[crossing.under.0][crossing.under.1].z = -height;
+        }
+        lifted
+    }
+}
+
+/// The medial graph as a mesh: its vertices are the midpoints of the
+/// edges and every face is connected to the midpoints of its edges.
+pub(crate) fn medial<P: Position>(name: &str, points: &[P], face_index: &FaceIndex) -> Mesh<P> {
+    let mut midpoints = Vec::new();
+    let mut lookup = HashMap::new();
+
+    let medial_faces = face_index
+        .iter()
+        .map(|face| {
+            (0..face.len())
+                .map(|i| {
+                    let (a, b) = (face[i], face[(i + 1) % face.len()]);
+                    *lookup.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                        let (a, b) = (points[a as usize].xyz(), points[b as usize].xyz());
+                        midpoints.push(P::from_xyz([0, 1, 2].map(|k| (a[k] + b[k]) / 2.0)));
+                        midpoints.len() as u32 - 1
+                    })
+                })
+                .collect()
+        })
+        .collect();
+
+    Mesh::new(&format!("{}-medial", name), midpoints, medial_faces)
+}
+
+pub(crate) fn weave<P: Position>(points: &[P], face_index: &FaceIndex) -> Weave {
+    let mut pieces = Vec::new();
+    let mut edges = BTreeSet::new();
+
+    for face in face_index {
+        let n = face.len();
+        let middle = |i: usize| -> DPoint {
+            let (a, b) = (
+                points[face[i] as usize].xyz(),
+                points[face[(i + 1) % n] as usize].xyz(),
+            );
+            [(a[0] + b[0]) as f64 / 2.0, (a[1] + b[1]) as f64 / 2.0]
+        };
+        // A strand leaving an edge towards its end vertex continues
+        // straight into the neighbouring face as the one leaving
+        // towards that face's end vertex.
+        let key = |i: usize, towards_end: bool| {
+            let (a, b) = (face[i], face[(i + 1) % n]);
+            (a.min(b), a.max(b), towards_end)
+        };
+
+        for i in 0..n {
+            let j = (i + 1) % n;
+            pieces.push(Piece {
+                points: vec![middle(i), middle(j)],
+                ends: [key(i, true), key(j, false)],
+            });
+            edges.insert((face[i].min(face[j]), face[i].max(face[j])));
+        }
+    }
+
+    let joints: Vec<_> = edges
+        .into_iter()
+        .map(|(a, b)| [(a, b, true), (a, b, false)])
+        .collect();
+    let (strands, crossings) = strand::assemble(&pieces, &[], &joints);
+
+    Weave { strands, crossings }
+}
+
+#[test]
+fn weave_squares() {
+    let squares = crate::RegularTiling::square(6, 6);
+
+    let medial = squares.medial();
+    assert_eq!(medial.points().len(), squares.edges().len());
+    assert_eq!(medial.faces().len(), squares.faces().len());
+    // Every corner of a face gives one edge of the medial graph.
+    let corners: usize = squares.faces().iter().map(|face| face.len()).sum();
+    assert_eq!(medial.edges().len(), corners);
+
+    let weave = squares.weave();
+    let interior_edges = squares.edges().iter().filter(|e| !e.is_boundary()).count();
+    assert_eq!(weave.crossings.len(), interior_edges);
+    // Strands run diagonally, from boundary to boundary.
+    assert!(weave.strands.iter().all(|strand| !strand.closed));
+    let pieces: usize = weave.strands.iter().map(|s| s.points.len() - 1).sum();
+    assert_eq!(pieces, corners);
+
+    let lifted = weave.lift(0.25);
+    for (s, strand) in lifted.iter().enumerate() {
+        let heights: Vec<f32> = strand.iter().map(|p| p.z).filter(|&z| z != 0.0).collect();
+        assert!(
+            heights.windows(2).all(|w| w[0] == -w[1]),
+            "strand {} does not alternate",
+            s
+        );
+    }
+
+    let hexagons = crate::RegularTiling::hexagon(12, 12);
+    let weave = hexagons.weave();
+    let interior_edges = hexagons.edges().iter().filter(|e| !e.is_boundary()).count();
+    assert_eq!(weave.crossings.len(), interior_edges);
+}